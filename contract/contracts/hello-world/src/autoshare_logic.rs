@@ -1,15 +1,27 @@
 use crate::base::errors::Error;
 use crate::base::events::{
-    emit_contribution, emit_distribution, AdminTransferred, AutoshareCreated, AutoshareUpdated,
-    ContractPaused, ContractUnpaused, FundraisingStarted, GroupActivated, GroupDeactivated,
-    GroupDeleted, GroupNameUpdated, Withdrawal,
+    emit_contribution, emit_distribution, AddressDenylisted, AddressRemovedFromDenylist,
+    AdminTransferred, AutoshareCreated, AutoshareUpdated, BlockedShareEscrowed, ContractPaused,
+    ContractUnpaused, FundraisingStarted, GroupActivated, GroupDeactivated, GroupDeleted,
+    GroupDeposit, GroupFrozen, GroupNameUpdated, GroupOwnershipTransferred, GroupUnfrozen,
+    InvitationAccepted, InvitationDeclined, InvoiceCancelled, InvoiceCreated, InvoicePaid,
+    MandateCollected, MemberInvited, MemberLeft, OperationPaused, OperationUnpaused,
+    PendingPayoutReleased, PromoCodeRedeemed, ProposalCreated, ProposalExecuted, ReferralRewarded,
+    SplitChangeCancelled, SplitChangeQueued, UsageSponsored, VoteCast, Withdrawal,
 };
 
 use crate::base::types::{
-    AutoShareDetails, DistributionHistory, DistributionRecord, FundraisingConfig,
-    FundraisingContribution, GroupMember, GroupStats, MemberAmount, PaymentHistory,
+    AutoShareDetails, BatchDistribution, BatchDistributionResult, BlockedSharePolicy,
+    DenylistEntry, DistributionHistory, DistributionPreview, DistributionRecord, FundraisingConfig,
+    FundraisingContribution, GovernanceConfig, GroupFreeze, GroupManager, GroupMember,
+    GroupSettings, GroupStats, Invitation, Invoice, InvoiceStatus, LeavePolicy, ManagerPermission,
+    Mandate, MemberAmount, OracleConfig, PauseCategory, PaymentHistory, PendingSplitChange,
+    PriceTier, PromoCode, Proposal, ProposalAction, ReferralStats, RoundingPolicy, SponsorPool,
+    SponsorSpending, SwapReceipt, TokenAmount, UsageQuote,
 };
-use soroban_sdk::{contracttype, token, Address, BytesN, Env, String, Vec};
+use crate::interfaces::price_oracle::{Asset, PriceOracleClient};
+use crate::interfaces::swap_router::SwapRouterClient;
+use soroban_sdk::{contracttype, token, Address, Bytes, BytesN, Env, Map, String, Vec};
 
 extern crate alloc;
 use alloc::string::String as AllocString;
@@ -33,12 +45,59 @@ pub enum DataKey {
     GroupStats(BytesN<32>),
     IsPaused,
     MemberGroups(Address),
+    PriceTiers,
+    SponsorPool(BytesN<32>),
+    SponsorPoolSpending(BytesN<32>),
+    GroupSponsorPools(BytesN<32>),
+    CreatorSponsorPools(Address),
+    ReferralRate,
+    ReferralStats(Address),
+    PromoCode(BytesN<32>),
+    FeeWaiver(Address),
+    GroupBalance(BytesN<32>, Address),
+    Mandate(BytesN<32>, Address),
+    Invoice(BytesN<32>),
+    GroupInvoices(BytesN<32>),
+    IdempotencyKey(BytesN<32>, BytesN<32>),
+    GroupSettings(BytesN<32>),
+    DustCredit(BytesN<32>, Address),
+    PendingPayout(BytesN<32>, Address, Address),
+    SwapRouter,
+    PriceOracle,
+    GroupInvitations(BytesN<32>),
+    GroupGovernance(BytesN<32>),
+    GroupProposals(BytesN<32>),
+    PendingGroupOwner(BytesN<32>),
+    GroupManagers(BytesN<32>),
+    PendingSplitChange(BytesN<32>),
+    GroupFreeze(BytesN<32>),
+    PausedOperations,
+    Denylist(Address),
+    BlockedSharePolicy,
+    ReservedBalance(Address),
+    MemberInvitations(Address),
+    GroupPendingTokens(BytesN<32>),
+    EscrowedPayout(BytesN<32>, Address, Address),
+}
+
+/// A governed group's open proposals, and the id the next one will get.
+#[contracttype]
+struct ProposalBook {
+    next_id: u32,
+    proposals: Vec<Proposal>,
 }
 
 const DAY_IN_LEDGERS: u32 = 17280;
 const PERSISTENT_BUMP_THRESHOLD: u32 = 7 * DAY_IN_LEDGERS; // 1 week
 const PERSISTENT_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS; // 30 days
 const MAX_MEMBERS: u32 = 50; // Maximum number of members per group to prevent DoS
+const MAX_BATCH_SIZE: u32 = 20; // Maximum number of distributions per batch call
+const MAX_DISTRIBUTION_TOKENS: u32 = 10; // Maximum number of tokens in one multi-token distribution
+const MAX_MEMO_LENGTH: u32 = 64; // Maximum length of a payment memo or reference
+const IDEMPOTENCY_RETENTION: u64 = 7 * 24 * 60 * 60; // Idempotency keys are remembered for 7 days
+const VALUE_DECIMALS: u32 = 7; // Decimals of amounts valued in the oracle's reference currency
+const MAX_PAYOUT_SLIPPAGE_BPS: i128 = 100; // Preferred-token payouts may fall 1% below the oracle quote
+const MAX_OPEN_PROPOSALS: u32 = 10; // Maximum number of proposals a governed group can have open
 
 fn bump_persistent<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, key: &K) {
     if env.storage().persistent().has(key) {
@@ -50,6 +109,15 @@ fn bump_persistent<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, ke
     }
 }
 
+fn validate_memo(memo: &Option<String>) -> Result<(), Error> {
+    if let Some(memo) = memo {
+        if memo.is_empty() || memo.len() > MAX_MEMO_LENGTH {
+            return Err(Error::InvalidInput);
+        }
+    }
+    Ok(())
+}
+
 fn is_valid_name(name: &String) -> bool {
     let alloc_str: AllocString = name.to_string();
     let trimmed = alloc_str.trim();
@@ -62,6 +130,7 @@ fn is_valid_name(name: &String) -> bool {
     true
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn create_autoshare(
     env: Env,
     id: BytesN<32>,
@@ -69,13 +138,15 @@ pub fn create_autoshare(
     creator: Address,
     usage_count: u32,
     payment_token: Address,
+    referrer: Option<Address>,
+    promo_code: Option<Bytes>,
 ) -> Result<(), Error> {
     creator.require_auth();
 
     // Check if contract is paused
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::Subscriptions)?;
+
+    require_not_denylisted(&env, &creator)?;
 
     if !is_valid_name(&name) {
         return Err(Error::EmptyName);
@@ -99,13 +170,19 @@ pub fn create_autoshare(
         return Err(Error::UnsupportedToken);
     }
 
-    // Calculate total cost
-    let usage_fee = get_usage_fee(env.clone());
-    let total_cost = (usage_count as i128) * (usage_fee as i128);
+    if referrer.as_ref() == Some(&creator) {
+        return Err(Error::InvalidInput);
+    }
+
+    // Calculate total cost, applying tiers, promo codes and fee waivers
+    let price = price_purchase(&env, &creator, &id, usage_count, promo_code)?;
+    let quote = price.quote.clone();
 
     // Transfer tokens from creator to contract
-    let token_client = token::Client::new(&env, &payment_token);
-    token_client.transfer(&creator, env.current_contract_address(), &total_cost);
+    if quote.total_cost > 0 {
+        let token_client = token::Client::new(&env, &payment_token);
+        token_client.transfer(&creator, env.current_contract_address(), &quote.total_cost);
+    }
 
     let details = AutoShareDetails {
         id: id.clone(),
@@ -133,13 +210,11 @@ pub fn create_autoshare(
     bump_persistent(&env, &all_groups_key);
 
     // Record payment history
-    record_payment(
-        env.clone(),
-        creator.clone(),
-        id.clone(),
-        usage_count,
-        total_cost,
-    );
+    record_payment(env.clone(), creator.clone(), id.clone(), price);
+
+    if let Some(referrer) = referrer {
+        credit_referral(&env, &referrer, &id, &payment_token, quote.total_cost);
+    }
 
     AutoshareCreated {
         creator: creator.clone(),
@@ -331,9 +406,9 @@ pub fn add_group_member(
     // Require caller auth and check pause
     caller.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    require_not_denylisted(&env, &address)?;
 
     let key = DataKey::AutoShare(id.clone());
     let mut details: AutoShareDetails = env
@@ -343,54 +418,73 @@ pub fn add_group_member(
         .ok_or(Error::NotFound)?;
     bump_persistent(&env, &key);
 
-    // Only the group creator can add members
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
-    }
+    // The creator or a manager with the ManageMembers permission can add members
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageMembers)?;
+
+    require_creator_control(&env, &id)?;
+    require_no_split_timelock(&env, &id)?;
+    require_not_frozen(&env, &id)?;
 
     if !details.is_active {
         return Err(Error::GroupInactive);
     }
 
-    // Check if already a member
+    // Check if already a member or already invited
     for member in details.members.iter() {
         if member.address == address {
             return Err(Error::AlreadyExists);
         }
     }
+    let mut invitations = read_invitations(&env, &id);
+    if invitations
+        .iter()
+        .any(|invitation| invitation.invitee == address)
+    {
+        return Err(Error::AlreadyExists);
+    }
 
     // Check if adding this member would exceed MAX_MEMBERS
-    if details.members.len() >= MAX_MEMBERS {
+    if details.members.len() + invitations.len() >= MAX_MEMBERS {
         return Err(Error::MaxMembersExceeded);
     }
 
-    // Add new member
-    details.members.push_back(GroupMember {
-        address: address.clone(),
-        percentage,
-    });
-
-    // Validate total percentage after adding
-    validate_members(&details.members)?;
+    if percentage == 0 {
+        return Err(Error::InvalidInput);
+    }
 
-    // Save updated details
-    env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+    // Accepted and offered shares together cannot exceed 100%
+    let members_total: u32 = details.members.iter().map(|m| m.percentage).sum();
+    if members_total + invited_share(&invitations) + percentage > 100 {
+        return Err(Error::InvalidTotalPercentage);
+    }
 
-    // Update MemberGroups index
-    let member_groups_key = DataKey::MemberGroups(address.clone());
-    let mut member_groups: Vec<BytesN<32>> = env
-        .storage()
-        .persistent()
-        .get(&member_groups_key)
-        .unwrap_or(Vec::new(&env));
+    // The creator authorizes this call, so they join without an invitation
+    if address == details.creator {
+        details.members.push_back(GroupMember {
+            address: address.clone(),
+            percentage,
+            preferred_token: None,
+            payout_address: None,
+        });
+        env.storage().persistent().set(&key, &details);
+        bump_persistent(&env, &key);
+        link_member_group(&env, &address, &id);
+        return Ok(());
+    }
 
-    member_groups.push_back(id.clone());
-    env.storage()
-        .persistent()
-        .set(&member_groups_key, &member_groups);
-    bump_persistent(&env, &member_groups_key);
+    invitations.push_back(Invitation {
+        invitee: address.clone(),
+        percentage,
+        invited_at: env.ledger().timestamp(),
+    });
+    write_invitations(&env, &id, &invitations);
 
+    MemberInvited {
+        group_id: id,
+        invitee: address,
+        percentage,
+    }
+    .publish(&env);
     Ok(())
 }
 
@@ -402,9 +496,7 @@ pub fn remove_group_member(
 ) -> Result<(), Error> {
     caller.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
     let key = DataKey::AutoShare(id.clone());
     let mut details: AutoShareDetails = env
@@ -414,9 +506,11 @@ pub fn remove_group_member(
         .ok_or(Error::NotFound)?;
     bump_persistent(&env, &key);
 
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
-    }
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageMembers)?;
+
+    require_creator_control(&env, &id)?;
+    require_no_split_timelock(&env, &id)?;
+    require_not_frozen(&env, &id)?;
 
     if !details.is_active {
         return Err(Error::GroupInactive);
@@ -432,7 +526,21 @@ pub fn remove_group_member(
         }
     }
     if !found {
-        return Err(Error::MemberNotFound);
+        // A pending invitation is withdrawn instead
+        let mut invitations = read_invitations(&env, &id);
+        let idx = invitations
+            .iter()
+            .position(|invitation| invitation.invitee == member_address)
+            .ok_or(Error::MemberNotFound)?;
+        invitations.remove(idx as u32);
+        write_invitations(&env, &id, &invitations);
+
+        AutoshareUpdated {
+            id: id.clone(),
+            updater: caller,
+        }
+        .publish(&env);
+        return Ok(());
     }
 
     details.members = new_members.clone();
@@ -440,29 +548,7 @@ pub fn remove_group_member(
     bump_persistent(&env, &key);
 
     // Update MemberGroups index
-    let member_groups_key = DataKey::MemberGroups(member_address.clone());
-    let member_groups: Vec<BytesN<32>> = env
-        .storage()
-        .persistent()
-        .get(&member_groups_key)
-        .unwrap_or(Vec::new(&env));
-
-    let mut new_member_groups: Vec<BytesN<32>> = Vec::new(&env);
-    let mut group_removed = false;
-    for group_id in member_groups.iter() {
-        if group_id != id {
-            new_member_groups.push_back(group_id);
-        } else {
-            group_removed = true;
-        }
-    }
-
-    if group_removed {
-        env.storage()
-            .persistent()
-            .set(&member_groups_key, &new_member_groups);
-        bump_persistent(&env, &member_groups_key);
-    }
+    unlink_member_group(&env, &member_address, &id);
 
     AutoshareUpdated {
         id: id.clone(),
@@ -472,257 +558,209 @@ pub fn remove_group_member(
     Ok(())
 }
 
-// ============================================================================
-// Admin Management
-// ============================================================================
-
-pub fn initialize_admin(env: Env, admin: Address) {
-    admin.require_auth();
-    let admin_key = DataKey::Admin;
-
-    // Only set if not already initialized
-    if !env.storage().persistent().has(&admin_key) {
-        env.storage().persistent().set(&admin_key, &admin);
-        bump_persistent(&env, &admin_key);
+/// Joins a group the caller was invited to. The offered percentage becomes the
+/// member's share from the next distribution.
+pub fn accept_invitation(env: Env, id: BytesN<32>, invitee: Address) -> Result<(), Error> {
+    invitee.require_auth();
 
-        // Initialize default usage fee (10 tokens per usage)
-        let usage_fee_key = DataKey::UsageFee;
-        env.storage().persistent().set(&usage_fee_key, &10u32);
-        bump_persistent(&env, &usage_fee_key);
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-        // Initialize empty supported tokens list
-        let tokens_key = DataKey::SupportedTokens;
-        let empty_tokens: Vec<Address> = Vec::new(&env);
-        env.storage().persistent().set(&tokens_key, &empty_tokens);
-        bump_persistent(&env, &tokens_key);
-    } else {
-        bump_persistent(&env, &admin_key);
-    }
-}
+    require_not_denylisted(&env, &invitee)?;
 
-fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
-    let admin_key = DataKey::Admin;
-    let admin: Address = env
+    let key = DataKey::AutoShare(id.clone());
+    let mut details: AutoShareDetails = env
         .storage()
         .persistent()
-        .get(&admin_key)
-        .ok_or(Error::Unauthorized)?;
-    bump_persistent(env, &admin_key);
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
 
-    if admin != *caller {
-        return Err(Error::Unauthorized);
+    if !details.is_active {
+        return Err(Error::GroupInactive);
     }
 
-    Ok(())
-}
+    require_not_frozen(&env, &id)?;
 
-pub fn get_admin(env: Env) -> Result<Address, Error> {
-    let admin_key = DataKey::Admin;
-    let result: Option<Address> = env.storage().persistent().get(&admin_key);
-    if result.is_some() {
-        bump_persistent(&env, &admin_key);
+    if details.members.iter().any(|m| m.address == invitee) {
+        return Err(Error::AlreadyExists);
     }
-    result.ok_or(Error::NotFound)
-}
 
-pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) -> Result<(), Error> {
-    current_admin.require_auth();
-    require_admin(&env, &current_admin)?;
+    let mut invitations = read_invitations(&env, &id);
+    let idx = invitations
+        .iter()
+        .position(|invitation| invitation.invitee == invitee)
+        .ok_or(Error::NotFound)? as u32;
+    let invitation = invitations.get(idx).unwrap();
+    invitations.remove(idx);
 
-    let admin_key = DataKey::Admin;
-    env.storage().persistent().set(&admin_key, &new_admin);
-    bump_persistent(&env, &admin_key);
-    AdminTransferred {
-        old_admin: current_admin,
-        new_admin,
+    if details.members.len() >= MAX_MEMBERS {
+        return Err(Error::MaxMembersExceeded);
     }
-    .publish(&env);
-    Ok(())
-}
 
-// ============================================================================
-// Pause Management
-// ============================================================================
+    let members_total: u32 = details.members.iter().map(|m| m.percentage).sum();
+    if members_total + invitation.percentage > 100 {
+        return Err(Error::InvalidTotalPercentage);
+    }
 
-pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
+    details.members.push_back(GroupMember {
+        address: invitee.clone(),
+        percentage: invitation.percentage,
+        preferred_token: None,
+        payout_address: None,
+    });
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+    write_invitations(&env, &id, &invitations);
 
-    let pause_key = DataKey::IsPaused;
-    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
-    bump_persistent(&env, &pause_key);
+    link_member_group(&env, &invitee, &id);
 
-    if is_paused {
-        return Err(Error::AlreadyPaused);
+    InvitationAccepted {
+        group_id: id,
+        member: invitee,
     }
-
-    env.storage().persistent().set(&pause_key, &true);
-    bump_persistent(&env, &pause_key);
-    ContractPaused {}.publish(&env);
+    .publish(&env);
     Ok(())
 }
 
-pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
+/// Turns down an invitation. The offered share stays unassigned until the
+/// creator rebalances the split.
+pub fn decline_invitation(env: Env, id: BytesN<32>, invitee: Address) -> Result<(), Error> {
+    invitee.require_auth();
 
-    let pause_key = DataKey::IsPaused;
-    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
-    bump_persistent(&env, &pause_key);
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-    if !is_paused {
-        return Err(Error::NotPaused);
+    if !env
+        .storage()
+        .persistent()
+        .has(&DataKey::AutoShare(id.clone()))
+    {
+        return Err(Error::NotFound);
     }
 
-    env.storage().persistent().set(&pause_key, &false);
-    bump_persistent(&env, &pause_key);
-    ContractUnpaused {}.publish(&env);
-    Ok(())
-}
+    let mut invitations = read_invitations(&env, &id);
+    let idx = invitations
+        .iter()
+        .position(|invitation| invitation.invitee == invitee)
+        .ok_or(Error::NotFound)?;
+    invitations.remove(idx as u32);
+    write_invitations(&env, &id, &invitations);
 
-pub fn get_paused_status(env: &Env) -> bool {
-    let pause_key = DataKey::IsPaused;
-    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
-    if is_paused {
-        bump_persistent(env, &pause_key);
+    InvitationDeclined {
+        group_id: id,
+        invitee,
     }
-    is_paused
+    .publish(&env);
+    Ok(())
 }
 
-// ============================================================================
-// Supported Tokens Management
-// ============================================================================
-
-pub fn add_supported_token(env: Env, token: Address, admin: Address) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
-
-    let tokens_key = DataKey::SupportedTokens;
-    let mut tokens: Vec<Address> = env
+pub fn get_group_invitations(env: Env, id: BytesN<32>) -> Result<Vec<Invitation>, Error> {
+    if !env
         .storage()
         .persistent()
-        .get(&tokens_key)
-        .unwrap_or(Vec::new(&env));
-    if !tokens.is_empty() {
-        bump_persistent(&env, &tokens_key);
+        .has(&DataKey::AutoShare(id.clone()))
+    {
+        return Err(Error::NotFound);
     }
+    Ok(read_invitations(&env, &id))
+}
 
-    // Check if token is already supported
-    for existing_token in tokens.iter() {
-        if existing_token == token {
-            return Err(Error::AlreadyExists);
+fn read_invitations(env: &Env, id: &BytesN<32>) -> Vec<Invitation> {
+    let key = DataKey::GroupInvitations(id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(invitations) => {
+            bump_persistent(env, &key);
+            invitations
         }
+        None => Vec::new(env),
     }
-
-    tokens.push_back(token);
-    env.storage().persistent().set(&tokens_key, &tokens);
-    bump_persistent(&env, &tokens_key);
-    Ok(())
 }
 
-pub fn remove_supported_token(env: Env, token: Address, admin: Address) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
-
-    let tokens_key = DataKey::SupportedTokens;
-    let tokens: Vec<Address> = env
+/// Stores a group's invitations and keeps each invitee's MemberInvitations
+/// index in step with them.
+fn write_invitations(env: &Env, id: &BytesN<32>, invitations: &Vec<Invitation>) {
+    let key = DataKey::GroupInvitations(id.clone());
+    let old_invitations: Vec<Invitation> = env
         .storage()
         .persistent()
-        .get(&tokens_key)
-        .unwrap_or(Vec::new(&env));
-    if !tokens.is_empty() {
-        bump_persistent(&env, &tokens_key);
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    for old in old_invitations.iter() {
+        if !invitations.iter().any(|inv| inv.invitee == old.invitee) {
+            update_invitation_index(env, &old.invitee, id, false);
+        }
     }
-
-    let mut new_tokens: Vec<Address> = Vec::new(&env);
-    let mut found = false;
-
-    for existing_token in tokens.iter() {
-        if existing_token != token {
-            new_tokens.push_back(existing_token);
-        } else {
-            found = true;
+    for new in invitations.iter() {
+        if !old_invitations.iter().any(|inv| inv.invitee == new.invitee) {
+            update_invitation_index(env, &new.invitee, id, true);
         }
     }
 
-    if !found {
-        return Err(Error::NotFound);
+    if invitations.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, invitations);
+        bump_persistent(env, &key);
     }
+}
 
-    env.storage().persistent().set(&tokens_key, &new_tokens);
-    bump_persistent(&env, &tokens_key);
-    Ok(())
-}
-
-pub fn get_supported_tokens(env: Env) -> Vec<Address> {
-    let tokens_key = DataKey::SupportedTokens;
-    let result: Option<Vec<Address>> = env.storage().persistent().get(&tokens_key);
-    if result.is_some() {
-        bump_persistent(&env, &tokens_key);
-    }
-    result.unwrap_or(Vec::new(&env))
-}
+/// Adds a group to, or removes it from, an invitee's MemberInvitations index.
+fn update_invitation_index(env: &Env, invitee: &Address, id: &BytesN<32>, invited: bool) {
+    let key = DataKey::MemberInvitations(invitee.clone());
+    let group_ids: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
 
-pub fn is_token_supported(env: Env, token: Address) -> bool {
-    let tokens = get_supported_tokens(env);
-    for supported_token in tokens.iter() {
-        if supported_token == token {
-            return true;
+    let mut updated: Vec<BytesN<32>> = Vec::new(env);
+    for group_id in group_ids.iter() {
+        if group_id != *id {
+            updated.push_back(group_id);
         }
     }
-    false
-}
-
-// ============================================================================
-// Payment Configuration
-// ============================================================================
-
-pub fn set_usage_fee(env: Env, fee: u32, admin: Address) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
-    if fee == 0 {
-        return Err(Error::InvalidAmount);
+    if invited {
+        updated.push_back(id.clone());
     }
 
-    let fee_key = DataKey::UsageFee;
-    env.storage().persistent().set(&fee_key, &fee);
-    bump_persistent(&env, &fee_key);
-    Ok(())
-}
-
-pub fn get_usage_fee(env: Env) -> u32 {
-    let fee_key = DataKey::UsageFee;
-    let result: Option<u32> = env.storage().persistent().get(&fee_key);
-    if result.is_some() {
-        bump_persistent(&env, &fee_key);
+    if updated.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &updated);
+        bump_persistent(env, &key);
     }
-    result.unwrap_or(10u32)
 }
 
-// ============================================================================
-// Subscription Management
-// ============================================================================
+/// Total percentage offered to invitees who have not answered yet.
+fn invited_share(invitations: &Vec<Invitation>) -> u32 {
+    invitations
+        .iter()
+        .map(|invitation| invitation.percentage)
+        .sum()
+}
 
-pub fn topup_subscription(
+/// Sets the token a member is paid in and the address payouts go to. Called by
+/// the member; `None` clears a preference.
+pub fn set_payout_preferences(
     env: Env,
     id: BytesN<32>,
-    additional_usages: u32,
-    payment_token: Address,
-    payer: Address,
+    member: Address,
+    preferred_token: Option<Address>,
+    payout_address: Option<Address>,
 ) -> Result<(), Error> {
-    payer.require_auth();
+    member.require_auth();
 
-    // Check if contract is paused
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    if let Some(token) = &preferred_token {
+        if !is_token_supported(env.clone(), token.clone()) {
+            return Err(Error::UnsupportedToken);
+        }
     }
 
-    // Validate usage count
-    if additional_usages == 0 {
-        return Err(Error::InvalidUsageCount);
+    if let Some(address) = &payout_address {
+        require_not_denylisted(&env, address)?;
     }
 
-    // Verify group exists
     let key = DataKey::AutoShare(id.clone());
     let mut details: AutoShareDetails = env
         .storage()
@@ -731,184 +769,2270 @@ pub fn topup_subscription(
         .ok_or(Error::NotFound)?;
     bump_persistent(&env, &key);
 
-    // Verify token is supported
-    if !is_token_supported(env.clone(), payment_token.clone()) {
-        return Err(Error::UnsupportedToken);
-    }
+    require_not_frozen(&env, &id)?;
 
-    // Calculate cost
-    let usage_fee = get_usage_fee(env.clone());
-    let total_cost = (additional_usages as i128) * (usage_fee as i128);
-
-    // Transfer tokens from payer to contract
-    let token_client = token::Client::new(&env, &payment_token);
-    token_client.transfer(&payer, env.current_contract_address(), &total_cost);
-
-    // Update usage counts
-    details.usage_count += additional_usages;
-    details.total_usages_paid += additional_usages;
+    let idx = details
+        .members
+        .iter()
+        .position(|m| m.address == member)
+        .ok_or(Error::MemberNotFound)? as u32;
+    let mut entry = details.members.get(idx).unwrap();
+    entry.preferred_token = preferred_token;
+    entry.payout_address = payout_address;
+    details.members.set(idx, entry);
 
-    // Save updated details
     env.storage().persistent().set(&key, &details);
     bump_persistent(&env, &key);
 
-    // Record payment history
-    record_payment(env, payer, id, additional_usages, total_cost);
-
+    AutoshareUpdated {
+        id,
+        updater: member,
+    }
+    .publish(&env);
     Ok(())
 }
 
-// ============================================================================
-// Payment History
-// ============================================================================
-
-fn record_payment(
-    env: Env,
-    user: Address,
-    group_id: BytesN<32>,
-    usages_purchased: u32,
-    amount_paid: i128,
-) {
-    let timestamp = env.ledger().timestamp();
+/// Removes the caller from a group. Under `LeavePolicy::Redistribute` their
+/// percentage is shared among the remaining members in proportion to their shares;
+/// otherwise the creator must rebalance the split before the next distribution.
+pub fn leave_group(env: Env, id: BytesN<32>, member: Address) -> Result<(), Error> {
+    member.require_auth();
 
-    let payment = PaymentHistory {
-        user: user.clone(),
-        group_id: group_id.clone(),
-        usages_purchased,
-        amount_paid,
-        timestamp,
-    };
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-    // Add to user's payment history
-    let user_history_key = DataKey::UserPaymentHistory(user.clone());
-    let mut user_history: Vec<PaymentHistory> = env
+    let key = DataKey::AutoShare(id.clone());
+    let mut details: AutoShareDetails = env
         .storage()
         .persistent()
-        .get(&user_history_key)
-        .unwrap_or(Vec::new(&env));
-    if !user_history.is_empty() {
-        bump_persistent(&env, &user_history_key);
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    require_not_frozen(&env, &id)?;
+
+    let mut remaining: Vec<GroupMember> = Vec::new(&env);
+    for entry in details.members.iter() {
+        if entry.address != member {
+            remaining.push_back(entry);
+        }
+    }
+    if remaining.len() == details.members.len() {
+        return Err(Error::MemberNotFound);
     }
-    user_history.push_back(payment.clone());
-    env.storage()
-        .persistent()
-        .set(&user_history_key, &user_history);
-    bump_persistent(&env, &user_history_key);
 
-    // Add to group's payment history
-    let group_history_key = DataKey::GroupPaymentHistory(group_id);
-    let mut group_history: Vec<PaymentHistory> = env
-        .storage()
-        .persistent()
-        .get(&group_history_key)
-        .unwrap_or(Vec::new(&env));
-    if !group_history.is_empty() {
-        bump_persistent(&env, &group_history_key);
+    if get_group_settings(env.clone(), id.clone()).leave_policy == LeavePolicy::Redistribute {
+        let target = 100 - invited_share(&read_invitations(&env, &id));
+        redistribute_percentages(&mut remaining, target);
     }
-    group_history.push_back(payment);
-    env.storage()
-        .persistent()
-        .set(&group_history_key, &group_history);
-    bump_persistent(&env, &group_history_key);
-}
 
-pub fn get_user_payment_history(env: Env, user: Address) -> Vec<PaymentHistory> {
-    let user_history_key = DataKey::UserPaymentHistory(user);
-    let result: Option<Vec<PaymentHistory>> = env.storage().persistent().get(&user_history_key);
-    if result.is_some() {
-        bump_persistent(&env, &user_history_key);
+    details.members = remaining;
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+
+    unlink_member_group(&env, &member, &id);
+
+    MemberLeft {
+        group_id: id,
+        member,
     }
-    result.unwrap_or(Vec::new(&env))
+    .publish(&env);
+    Ok(())
 }
 
-pub fn get_group_payment_history(env: Env, id: BytesN<32>) -> Vec<PaymentHistory> {
-    let group_history_key = DataKey::GroupPaymentHistory(id);
-    let result: Option<Vec<PaymentHistory>> = env.storage().persistent().get(&group_history_key);
-    if result.is_some() {
-        bump_persistent(&env, &group_history_key);
+/// Scales the members' percentages up to `target`, keeping their proportions.
+/// Rounding leftovers go to the largest share.
+fn redistribute_percentages(members: &mut Vec<GroupMember>, target: u32) {
+    let total: u32 = members.iter().map(|m| m.percentage).sum();
+    if total == 0 {
+        return;
     }
-    result.unwrap_or(Vec::new(&env))
+
+    let mut assigned: u32 = 0;
+    let mut largest: u32 = 0;
+    // Compared before scaling, since earlier entries have already been scaled
+    let mut largest_percentage: u32 = 0;
+    for idx in 0..members.len() {
+        let mut entry = members.get(idx).unwrap();
+        if entry.percentage > largest_percentage {
+            largest = idx;
+            largest_percentage = entry.percentage;
+        }
+        entry.percentage = entry.percentage * target / total;
+        assigned += entry.percentage;
+        members.set(idx, entry);
+    }
+
+    let mut entry = members.get(largest).unwrap();
+    entry.percentage += target - assigned;
+    members.set(largest, entry);
 }
 
-// ============================================================================
-// Distribution History
-// ============================================================================
+/// Moves a member's memberships, open invitations, earnings, pending payouts,
+/// distribution records and vote weights on open proposals to `new_address`.
+/// Both addresses must authorize the change.
+pub fn change_payout_address(env: Env, member: Address, new_address: Address) -> Result<(), Error> {
+    member.require_auth();
+    new_address.require_auth();
 
-fn record_distribution(
-    env: Env,
-    group_id: BytesN<32>,
-    sender: Address,
-    total_amount: i128,
-    token: Address,
-    member_amounts: Vec<MemberAmount>,
-    distribution_number: u32,
-) {
-    let timestamp = env.ledger().timestamp();
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-    let distribution = DistributionHistory {
-        group_id: group_id.clone(),
-        sender: sender.clone(),
-        total_amount,
-        token: token.clone(),
-        member_amounts: member_amounts.clone(),
-        timestamp,
-        distribution_number,
-    };
+    if member == new_address {
+        return Err(Error::InvalidInput);
+    }
 
-    // Add to group's distribution history
-    let group_history_key = DataKey::GroupDistributionHistory(group_id.clone());
-    let mut group_history: Vec<DistributionHistory> = env
+    // Escrowed shares stay with the blocked address, which cannot move away from them
+    require_not_denylisted(&env, &member)?;
+    require_not_denylisted(&env, &new_address)?;
+
+    let groups_key = DataKey::MemberGroups(member.clone());
+    let group_ids: Vec<BytesN<32>> = env
         .storage()
         .persistent()
-        .get(&group_history_key)
+        .get(&groups_key)
         .unwrap_or(Vec::new(&env));
-    group_history.push_back(distribution.clone());
-    env.storage()
+    let invited_ids: Vec<BytesN<32>> = env
+        .storage()
         .persistent()
-        .set(&group_history_key, &group_history);
+        .get(&DataKey::MemberInvitations(member.clone()))
+        .unwrap_or(Vec::new(&env));
+    if group_ids.is_empty() && invited_ids.is_empty() {
+        return Err(Error::MemberNotFound);
+    }
 
-    // Add to each member's distribution history
-    for member_amount in member_amounts.iter() {
-        let member_history_key = DataKey::MemberDistributions(member_amount.address.clone());
-        let mut member_history: Vec<DistributionRecord> = env
+    let new_groups_key = DataKey::MemberGroups(new_address.clone());
+    let mut new_group_ids: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&new_groups_key)
+        .unwrap_or(Vec::new(&env));
+
+    for id in group_ids.iter() {
+        let key = DataKey::AutoShare(id.clone());
+        // Deleted groups are skipped
+        let mut details: AutoShareDetails = match env.storage().persistent().get(&key) {
+            Some(details) => details,
+            None => continue,
+        };
+        require_not_frozen(&env, &id)?;
+        require_no_open_vote(&env, &id, &member)?;
+        move_vote_weights(&env, &id, &member, &new_address)?;
+
+        if read_invitations(&env, &id)
+            .iter()
+            .any(|inv| inv.invitee == new_address)
+        {
+            return Err(Error::DuplicateMember);
+        }
+        for idx in 0..details.members.len() {
+            let mut entry = details.members.get(idx).unwrap();
+            if entry.address == new_address {
+                return Err(Error::DuplicateMember);
+            }
+            if entry.address == member {
+                entry.address = new_address.clone();
+                details.members.set(idx, entry);
+            }
+        }
+        env.storage().persistent().set(&key, &details);
+        bump_persistent(&env, &key);
+
+        let earnings_key = DataKey::MemberGroupEarnings(member.clone(), id.clone());
+        let earnings: i128 = env.storage().persistent().get(&earnings_key).unwrap_or(0);
+        if earnings > 0 {
+            env.storage().persistent().remove(&earnings_key);
+            let new_earnings_key = DataKey::MemberGroupEarnings(new_address.clone(), id.clone());
+            env.storage().persistent().set(&new_earnings_key, &earnings);
+            bump_persistent(&env, &new_earnings_key);
+        }
+
+        // Every token ever held for the group, including ones no longer supported
+        for token in read_pending_tokens(&env, &id).iter() {
+            let pending_key = DataKey::PendingPayout(id.clone(), member.clone(), token.clone());
+            let pending: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+            if pending > 0 {
+                env.storage().persistent().remove(&pending_key);
+                let new_pending_key =
+                    DataKey::PendingPayout(id.clone(), new_address.clone(), token);
+                env.storage().persistent().set(&new_pending_key, &pending);
+                bump_persistent(&env, &new_pending_key);
+            }
+        }
+
+        new_group_ids.push_back(id.clone());
+        AutoshareUpdated {
+            id,
+            updater: member.clone(),
+        }
+        .publish(&env);
+    }
+
+    // Open invitations follow the member to the new address
+    for id in invited_ids.iter() {
+        let details: AutoShareDetails = match env
             .storage()
             .persistent()
-            .get(&member_history_key)
-            .unwrap_or(Vec::new(&env));
-        let record = DistributionRecord {
-            group_id: group_id.clone(),
-            amount: member_amount.amount,
-            token: token.clone(),
-            timestamp,
+            .get(&DataKey::AutoShare(id.clone()))
+        {
+            Some(details) => details,
+            None => continue,
         };
-        member_history.push_back(record);
+        require_not_frozen(&env, &id)?;
+
+        let mut invitations = read_invitations(&env, &id);
+        if details.members.iter().any(|m| m.address == new_address)
+            || invitations.iter().any(|inv| inv.invitee == new_address)
+        {
+            return Err(Error::DuplicateMember);
+        }
+        for idx in 0..invitations.len() {
+            let mut invitation = invitations.get(idx).unwrap();
+            if invitation.invitee == member {
+                invitation.invitee = new_address.clone();
+                invitations.set(idx, invitation);
+            }
+        }
+        write_invitations(&env, &id, &invitations);
+    }
+
+    if !group_ids.is_empty() {
+        env.storage().persistent().remove(&groups_key);
         env.storage()
             .persistent()
-            .set(&member_history_key, &member_history);
+            .set(&new_groups_key, &new_group_ids);
+        bump_persistent(&env, &new_groups_key);
+    }
+
+    let history_key = DataKey::MemberDistributions(member);
+    let history: Option<Vec<DistributionRecord>> = env.storage().persistent().get(&history_key);
+    if let Some(history) = history {
+        env.storage().persistent().remove(&history_key);
+        let new_history_key = DataKey::MemberDistributions(new_address);
+        let mut new_history: Vec<DistributionRecord> = env
+            .storage()
+            .persistent()
+            .get(&new_history_key)
+            .unwrap_or(Vec::new(&env));
+        new_history.append(&history);
+        env.storage()
+            .persistent()
+            .set(&new_history_key, &new_history);
+        bump_persistent(&env, &new_history_key);
     }
+
+    Ok(())
 }
 
-pub fn get_group_distributions(env: Env, id: BytesN<32>) -> Vec<DistributionHistory> {
-    let group_history_key = DataKey::GroupDistributionHistory(id);
-    env.storage()
+/// Adds a group to a member's MemberGroups index.
+fn link_member_group(env: &Env, member: &Address, id: &BytesN<32>) {
+    let member_groups_key = DataKey::MemberGroups(member.clone());
+    let mut member_groups: Vec<BytesN<32>> = env
+        .storage()
         .persistent()
-        .get(&group_history_key)
-        .unwrap_or(Vec::new(&env))
-}
+        .get(&member_groups_key)
+        .unwrap_or(Vec::new(env));
 
-pub fn get_member_distributions(env: Env, member: Address) -> Vec<DistributionRecord> {
-    let member_history_key = DataKey::MemberDistributions(member);
+    member_groups.push_back(id.clone());
     env.storage()
         .persistent()
-        .get(&member_history_key)
-        .unwrap_or(Vec::new(&env))
+        .set(&member_groups_key, &member_groups);
+    bump_persistent(env, &member_groups_key);
 }
 
-// ============================================================================
-// Usage Tracking
-// ============================================================================
+/// Removes a group from a member's MemberGroups index.
+fn unlink_member_group(env: &Env, member: &Address, id: &BytesN<32>) {
+    let member_groups_key = DataKey::MemberGroups(member.clone());
+    let member_groups: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&member_groups_key)
+        .unwrap_or(Vec::new(env));
+
+    let mut new_member_groups: Vec<BytesN<32>> = Vec::new(env);
+    let mut group_removed = false;
+    for group_id in member_groups.iter() {
+        if group_id != *id {
+            new_member_groups.push_back(group_id);
+        } else {
+            group_removed = true;
+        }
+    }
+
+    if group_removed {
+        env.storage()
+            .persistent()
+            .set(&member_groups_key, &new_member_groups);
+        bump_persistent(env, &member_groups_key);
+    }
+}
+
+// ============================================================================
+// Admin Management
+// ============================================================================
+
+pub fn initialize_admin(env: Env, admin: Address) {
+    admin.require_auth();
+    let admin_key = DataKey::Admin;
+
+    // Only set if not already initialized
+    if !env.storage().persistent().has(&admin_key) {
+        env.storage().persistent().set(&admin_key, &admin);
+        bump_persistent(&env, &admin_key);
+
+        // Initialize default usage fee (10 tokens per usage)
+        let usage_fee_key = DataKey::UsageFee;
+        env.storage().persistent().set(&usage_fee_key, &10u32);
+        bump_persistent(&env, &usage_fee_key);
+
+        // Initialize empty supported tokens list
+        let tokens_key = DataKey::SupportedTokens;
+        let empty_tokens: Vec<Address> = Vec::new(&env);
+        env.storage().persistent().set(&tokens_key, &empty_tokens);
+        bump_persistent(&env, &tokens_key);
+    } else {
+        bump_persistent(&env, &admin_key);
+    }
+}
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    let admin_key = DataKey::Admin;
+    let admin: Address = env
+        .storage()
+        .persistent()
+        .get(&admin_key)
+        .ok_or(Error::Unauthorized)?;
+    bump_persistent(env, &admin_key);
+
+    if admin != *caller {
+        return Err(Error::Unauthorized);
+    }
+
+    Ok(())
+}
+
+pub fn get_admin(env: Env) -> Result<Address, Error> {
+    let admin_key = DataKey::Admin;
+    let result: Option<Address> = env.storage().persistent().get(&admin_key);
+    if result.is_some() {
+        bump_persistent(&env, &admin_key);
+    }
+    result.ok_or(Error::NotFound)
+}
+
+pub fn transfer_admin(env: Env, current_admin: Address, new_admin: Address) -> Result<(), Error> {
+    current_admin.require_auth();
+    require_admin(&env, &current_admin)?;
+
+    let admin_key = DataKey::Admin;
+    env.storage().persistent().set(&admin_key, &new_admin);
+    bump_persistent(&env, &admin_key);
+    AdminTransferred {
+        old_admin: current_admin,
+        new_admin,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+// ============================================================================
+// Pause Management
+// ============================================================================
+
+pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let pause_key = DataKey::IsPaused;
+    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
+    bump_persistent(&env, &pause_key);
+
+    if is_paused {
+        return Err(Error::AlreadyPaused);
+    }
+
+    env.storage().persistent().set(&pause_key, &true);
+    bump_persistent(&env, &pause_key);
+    ContractPaused {}.publish(&env);
+    Ok(())
+}
+
+pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let pause_key = DataKey::IsPaused;
+    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
+    bump_persistent(&env, &pause_key);
+
+    if !is_paused {
+        return Err(Error::NotPaused);
+    }
+
+    env.storage().persistent().set(&pause_key, &false);
+    bump_persistent(&env, &pause_key);
+    ContractUnpaused {}.publish(&env);
+    Ok(())
+}
+
+pub fn get_paused_status(env: &Env) -> bool {
+    let pause_key = DataKey::IsPaused;
+    let is_paused: bool = env.storage().persistent().get(&pause_key).unwrap_or(false);
+    if is_paused {
+        bump_persistent(env, &pause_key);
+    }
+    is_paused
+}
+
+pub fn pause_operation(env: Env, admin: Address, category: PauseCategory) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let mut paused = read_paused_operations(&env);
+    if paused.contains(&category) {
+        return Err(Error::AlreadyPaused);
+    }
+
+    paused.push_back(category.clone());
+    write_paused_operations(&env, &paused);
+    OperationPaused { category }.publish(&env);
+    Ok(())
+}
+
+pub fn unpause_operation(env: Env, admin: Address, category: PauseCategory) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let mut paused = read_paused_operations(&env);
+    let index = paused.first_index_of(&category).ok_or(Error::NotPaused)?;
+
+    paused.remove(index);
+    write_paused_operations(&env, &paused);
+    OperationUnpaused { category }.publish(&env);
+    Ok(())
+}
+
+/// Returns whether each operation category is currently paused, either on its
+/// own or through the global pause.
+pub fn get_pause_map(env: &Env) -> Map<PauseCategory, bool> {
+    let globally_paused = get_paused_status(env);
+    let paused = read_paused_operations(env);
+    let mut map = Map::new(env);
+    for category in [
+        PauseCategory::Distributions,
+        PauseCategory::Contributions,
+        PauseCategory::Withdrawals,
+        PauseCategory::Subscriptions,
+        PauseCategory::GroupManagement,
+        PauseCategory::Sponsorship,
+    ] {
+        let is_paused = globally_paused || paused.contains(&category);
+        map.set(category, is_paused);
+    }
+    map
+}
+
+fn read_paused_operations(env: &Env) -> Vec<PauseCategory> {
+    let key = DataKey::PausedOperations;
+    let paused: Option<Vec<PauseCategory>> = env.storage().persistent().get(&key);
+    match paused {
+        Some(paused) => {
+            bump_persistent(env, &key);
+            paused
+        }
+        None => Vec::new(env),
+    }
+}
+
+fn write_paused_operations(env: &Env, paused: &Vec<PauseCategory>) {
+    let key = DataKey::PausedOperations;
+    env.storage().persistent().set(&key, paused);
+    bump_persistent(env, &key);
+}
+
+fn is_operation_paused(env: &Env, category: &PauseCategory) -> bool {
+    read_paused_operations(env).contains(category)
+}
+
+/// Fails with `ContractPaused` while the contract or the given category is paused.
+fn require_operation_active(env: &Env, category: PauseCategory) -> Result<(), Error> {
+    if get_paused_status(env) || is_operation_paused(env, &category) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Denylist
+// ============================================================================
+
+pub fn add_to_denylist(
+    env: Env,
+    admin: Address,
+    address: Address,
+    reason: u32,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::Denylist(address.clone());
+    if env.storage().persistent().has(&key) {
+        bump_persistent(&env, &key);
+        return Err(Error::AlreadyExists);
+    }
+
+    let entry = DenylistEntry {
+        address: address.clone(),
+        reason,
+        added_by: admin,
+        added_at: env.ledger().timestamp(),
+    };
+    env.storage().persistent().set(&key, &entry);
+    bump_persistent(&env, &key);
+
+    AddressDenylisted { address, reason }.publish(&env);
+    Ok(())
+}
+
+pub fn remove_from_denylist(env: Env, admin: Address, address: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::Denylist(address.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::NotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    AddressRemovedFromDenylist { address }.publish(&env);
+    Ok(())
+}
+
+pub fn get_denylist_entry(env: &Env, address: &Address) -> Option<DenylistEntry> {
+    let key = DataKey::Denylist(address.clone());
+    let entry: Option<DenylistEntry> = env.storage().persistent().get(&key);
+    if entry.is_some() {
+        bump_persistent(env, &key);
+    }
+    entry
+}
+
+pub fn is_denylisted(env: &Env, address: &Address) -> bool {
+    get_denylist_entry(env, address).is_some()
+}
+
+pub fn set_blocked_share_policy(
+    env: Env,
+    admin: Address,
+    policy: BlockedSharePolicy,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::BlockedSharePolicy;
+    env.storage().persistent().set(&key, &policy);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+/// Returns how denylisted members' shares are handled. Distributions to them are
+/// rejected unless the admin chose otherwise.
+pub fn get_blocked_share_policy(env: &Env) -> BlockedSharePolicy {
+    let key = DataKey::BlockedSharePolicy;
+    match env.storage().persistent().get(&key) {
+        Some(policy) => {
+            bump_persistent(env, &key);
+            policy
+        }
+        None => BlockedSharePolicy::Reject,
+    }
+}
+
+/// Returns the shares escrowed for `member` while they or their payout address
+/// were denylisted.
+pub fn get_escrowed_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128 {
+    let key = DataKey::EscrowedPayout(id, member, token);
+    let escrowed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if escrowed > 0 {
+        bump_persistent(&env, &key);
+    }
+    escrowed
+}
+
+/// Pays out a member's escrowed shares once neither they nor their payout address
+/// is denylisted. Only the admin can release escrow; it stays keyed to the address
+/// it was withheld from, so moving the membership does not move it.
+pub fn release_escrowed_payout(
+    env: Env,
+    admin: Address,
+    id: BytesN<32>,
+    member: Address,
+    token: Address,
+) -> Result<i128, Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    require_operation_active(&env, PauseCategory::Withdrawals)?;
+
+    require_not_denylisted(&env, &member)?;
+
+    let key = DataKey::EscrowedPayout(id.clone(), member.clone(), token.clone());
+    let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount <= 0 {
+        return Err(Error::InsufficientBalance);
+    }
+
+    env.storage().persistent().remove(&key);
+    adjust_reserved(&env, &token, -amount);
+    release_to_member(&env, &id, &member, &token, amount)?;
+
+    PendingPayoutReleased {
+        group_id: id,
+        member,
+        token,
+        amount,
+    }
+    .publish(&env);
+    Ok(amount)
+}
+
+fn require_not_denylisted(env: &Env, address: &Address) -> Result<(), Error> {
+    if is_denylisted(env, address) {
+        return Err(Error::AddressDenylisted);
+    }
+    Ok(())
+}
+
+/// Whether payouts to `recipient` are blocked, either because it is denylisted
+/// or because the member's payout address is.
+fn is_recipient_denylisted(env: &Env, details: &AutoShareDetails, recipient: &Address) -> bool {
+    if is_denylisted(env, recipient) {
+        return true;
+    }
+    details
+        .members
+        .iter()
+        .find(|m| m.address == *recipient)
+        .and_then(|m| m.payout_address)
+        .is_some_and(|payout_address| is_denylisted(env, &payout_address))
+}
+
+// ============================================================================
+// Supported Tokens Management
+// ============================================================================
+
+pub fn add_supported_token(env: Env, token: Address, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let tokens_key = DataKey::SupportedTokens;
+    let mut tokens: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&tokens_key)
+        .unwrap_or(Vec::new(&env));
+    if !tokens.is_empty() {
+        bump_persistent(&env, &tokens_key);
+    }
+
+    // Check if token is already supported
+    for existing_token in tokens.iter() {
+        if existing_token == token {
+            return Err(Error::AlreadyExists);
+        }
+    }
+
+    tokens.push_back(token);
+    env.storage().persistent().set(&tokens_key, &tokens);
+    bump_persistent(&env, &tokens_key);
+    Ok(())
+}
+
+pub fn remove_supported_token(env: Env, token: Address, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let tokens_key = DataKey::SupportedTokens;
+    let tokens: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(&tokens_key)
+        .unwrap_or(Vec::new(&env));
+    if !tokens.is_empty() {
+        bump_persistent(&env, &tokens_key);
+    }
+
+    let mut new_tokens: Vec<Address> = Vec::new(&env);
+    let mut found = false;
+
+    for existing_token in tokens.iter() {
+        if existing_token != token {
+            new_tokens.push_back(existing_token);
+        } else {
+            found = true;
+        }
+    }
+
+    if !found {
+        return Err(Error::NotFound);
+    }
+
+    env.storage().persistent().set(&tokens_key, &new_tokens);
+    bump_persistent(&env, &tokens_key);
+    Ok(())
+}
+
+pub fn get_supported_tokens(env: Env) -> Vec<Address> {
+    let tokens_key = DataKey::SupportedTokens;
+    let result: Option<Vec<Address>> = env.storage().persistent().get(&tokens_key);
+    if result.is_some() {
+        bump_persistent(&env, &tokens_key);
+    }
+    result.unwrap_or(Vec::new(&env))
+}
+
+pub fn is_token_supported(env: Env, token: Address) -> bool {
+    let tokens = get_supported_tokens(env);
+    for supported_token in tokens.iter() {
+        if supported_token == token {
+            return true;
+        }
+    }
+    false
+}
+
+// ============================================================================
+// Payment Configuration
+// ============================================================================
+
+pub fn set_usage_fee(env: Env, fee: u32, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+    if fee == 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let fee_key = DataKey::UsageFee;
+    env.storage().persistent().set(&fee_key, &fee);
+    bump_persistent(&env, &fee_key);
+    Ok(())
+}
+
+pub fn get_usage_fee(env: Env) -> u32 {
+    let fee_key = DataKey::UsageFee;
+    let result: Option<u32> = env.storage().persistent().get(&fee_key);
+    if result.is_some() {
+        bump_persistent(&env, &fee_key);
+    }
+    result.unwrap_or(10u32)
+}
+
+/// Sets the router used by `distribute_with_swap` (admin only).
+pub fn set_swap_router(env: Env, router: Address, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::SwapRouter;
+    env.storage().persistent().set(&key, &router);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+pub fn get_swap_router(env: Env) -> Option<Address> {
+    let key = DataKey::SwapRouter;
+    let result: Option<Address> = env.storage().persistent().get(&key);
+    if result.is_some() {
+        bump_persistent(&env, &key);
+    }
+    result
+}
+
+/// Sets the SEP-40 price oracle used to value contributions (admin only).
+/// Prices older than `max_price_age` seconds are rejected.
+pub fn set_price_oracle(
+    env: Env,
+    oracle: Address,
+    max_price_age: u64,
+    admin: Address,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+    if max_price_age == 0 {
+        return Err(Error::InvalidInput);
+    }
+
+    let key = DataKey::PriceOracle;
+    let config = OracleConfig {
+        oracle,
+        max_price_age,
+    };
+    env.storage().persistent().set(&key, &config);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+pub fn get_price_oracle(env: Env) -> Option<OracleConfig> {
+    let key = DataKey::PriceOracle;
+    let result: Option<OracleConfig> = env.storage().persistent().get(&key);
+    if result.is_some() {
+        bump_persistent(&env, &key);
+    }
+    result
+}
+
+/// Values `amount` of `token` in the oracle's reference currency with
+/// `VALUE_DECIMALS` decimals, using the oracle's last price.
+fn value_in_reference(env: &Env, token: &Address, amount: i128) -> Result<i128, Error> {
+    let config = get_price_oracle(env.clone()).ok_or(Error::OracleNotSet)?;
+    let oracle = PriceOracleClient::new(env, &config.oracle);
+    let price = fresh_price(env, &oracle, &config, token)?;
+
+    // value = amount * price / 10^oracle_decimals, rescaled from the token's
+    // decimals to VALUE_DECIMALS
+    let token_decimals = token::TokenClient::new(env, token).decimals();
+    let mut value = amount.checked_mul(price).ok_or(Error::InvalidAmount)?;
+    let mut divisor_decimals = oracle.decimals();
+    if token_decimals < VALUE_DECIMALS {
+        value = value
+            .checked_mul(10i128.pow(VALUE_DECIMALS - token_decimals))
+            .ok_or(Error::InvalidAmount)?;
+    } else {
+        divisor_decimals += token_decimals - VALUE_DECIMALS;
+    }
+    Ok(value / 10i128.pow(divisor_decimals))
+}
+
+/// Converts `amount` of `token_in` into `token_out` at the oracle's prices.
+fn oracle_quote(
+    env: &Env,
+    token_in: &Address,
+    amount: i128,
+    token_out: &Address,
+) -> Result<i128, Error> {
+    let config = get_price_oracle(env.clone()).ok_or(Error::OracleNotSet)?;
+    let oracle = PriceOracleClient::new(env, &config.oracle);
+    let price_in = fresh_price(env, &oracle, &config, token_in)?;
+    let price_out = fresh_price(env, &oracle, &config, token_out)?;
+
+    // out = amount * price_in / price_out, rescaled between the token decimals
+    let decimals_in = token::TokenClient::new(env, token_in).decimals();
+    let decimals_out = token::TokenClient::new(env, token_out).decimals();
+    let mut numerator = amount.checked_mul(price_in).ok_or(Error::InvalidAmount)?;
+    let mut denominator = price_out;
+    if decimals_out >= decimals_in {
+        numerator = numerator
+            .checked_mul(10i128.pow(decimals_out - decimals_in))
+            .ok_or(Error::InvalidAmount)?;
+    } else {
+        denominator = denominator
+            .checked_mul(10i128.pow(decimals_in - decimals_out))
+            .ok_or(Error::InvalidAmount)?;
+    }
+    Ok(numerator / denominator)
+}
+
+/// Returns the oracle's price for `token`, rejecting missing, stale and
+/// future-dated prices.
+fn fresh_price(
+    env: &Env,
+    oracle: &PriceOracleClient,
+    config: &OracleConfig,
+    token: &Address,
+) -> Result<i128, Error> {
+    let price = oracle
+        .lastprice(&Asset::Stellar(token.clone()))
+        .ok_or(Error::PriceUnavailable)?;
+    if price.price <= 0 {
+        return Err(Error::PriceUnavailable);
+    }
+    // A price dated after the current ledger cannot be trusted either
+    let now = env.ledger().timestamp();
+    if price.timestamp > now || now - price.timestamp > config.max_price_age {
+        return Err(Error::StalePrice);
+    }
+    Ok(price.price)
+}
+
+/// Replaces the volume discount tiers (admin only).
+/// Tiers must be ordered by strictly increasing `min_usages`, starting at 1 or more,
+/// and discounts are whole percentages of the usage fee. An empty list clears all tiers.
+pub fn set_price_tiers(env: Env, tiers: Vec<PriceTier>, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let mut previous_min: u32 = 0;
+    for tier in tiers.iter() {
+        if tier.min_usages <= previous_min {
+            return Err(Error::InvalidInput);
+        }
+        if tier.discount_percentage > 100 {
+            return Err(Error::InvalidInput);
+        }
+        previous_min = tier.min_usages;
+    }
+
+    let tiers_key = DataKey::PriceTiers;
+    env.storage().persistent().set(&tiers_key, &tiers);
+    bump_persistent(&env, &tiers_key);
+    Ok(())
+}
+
+pub fn get_price_tiers(env: Env) -> Vec<PriceTier> {
+    let tiers_key = DataKey::PriceTiers;
+    let result: Option<Vec<PriceTier>> = env.storage().persistent().get(&tiers_key);
+    if result.is_some() {
+        bump_persistent(&env, &tiers_key);
+    }
+    result.unwrap_or(Vec::new(&env))
+}
+
+/// Prices a purchase of `usage_count` usages.
+/// The highest tier whose `min_usages` is reached sets the discount; below the
+/// first tier the full usage fee applies.
+pub fn get_usage_quote(env: Env, usage_count: u32) -> UsageQuote {
+    let usage_fee = get_usage_fee(env.clone()) as i128;
+
+    let mut discount_percentage: u32 = 0;
+    for tier in get_price_tiers(env).iter() {
+        if usage_count >= tier.min_usages {
+            discount_percentage = tier.discount_percentage;
+        }
+    }
+
+    let unit_price = usage_fee * ((100 - discount_percentage) as i128) / 100;
+    UsageQuote {
+        usage_count,
+        unit_price,
+        total_cost: unit_price * (usage_count as i128),
+    }
+}
+
+// ============================================================================
+// Subscription Management
+// ============================================================================
+
+pub fn topup_subscription(
+    env: Env,
+    id: BytesN<32>,
+    additional_usages: u32,
+    payment_token: Address,
+    payer: Address,
+    referrer: Option<Address>,
+    promo_code: Option<Bytes>,
+) -> Result<(), Error> {
+    payer.require_auth();
+
+    // Check if contract is paused
+    require_operation_active(&env, PauseCategory::Subscriptions)?;
+
+    // Validate usage count
+    if additional_usages == 0 {
+        return Err(Error::InvalidUsageCount);
+    }
+
+    // Verify group exists
+    let key = DataKey::AutoShare(id.clone());
+    let mut details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    // Anyone can pay for an active group's usages. Once the group is deactivated
+    // only the creator and managers allowed to top up can, ahead of reactivating it.
+    if !details.is_active {
+        require_group_permission(&env, &details, &payer, ManagerPermission::TopUp)?;
+    }
+
+    // Verify token is supported
+    if !is_token_supported(env.clone(), payment_token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    if referrer.as_ref() == Some(&payer) {
+        return Err(Error::InvalidInput);
+    }
+
+    // Calculate cost, applying tiers, promo codes and fee waivers
+    let price = price_purchase(&env, &payer, &id, additional_usages, promo_code)?;
+    let quote = price.quote.clone();
+
+    // Transfer tokens from payer to contract
+    if quote.total_cost > 0 {
+        let token_client = token::Client::new(&env, &payment_token);
+        token_client.transfer(&payer, env.current_contract_address(), &quote.total_cost);
+    }
+
+    // Update usage counts
+    details.usage_count += additional_usages;
+    details.total_usages_paid += additional_usages;
+
+    // Save updated details
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+
+    // Record payment history
+    record_payment(env.clone(), payer, id.clone(), price);
+
+    if let Some(referrer) = referrer {
+        credit_referral(&env, &referrer, &id, &payment_token, quote.total_cost);
+    }
+
+    Ok(())
+}
+
+// ============================================================================
+// Promo Codes & Fee Waivers
+// ============================================================================
+
+/// The price of a usage purchase after tiers, promo codes and fee waivers.
+struct PurchasePrice {
+    quote: UsageQuote,
+    promo_code: Option<BytesN<32>>,
+    fee_waived: bool,
+}
+
+/// Creates a promo code stored by the SHA-256 hash of the code (admin only).
+#[allow(clippy::too_many_arguments)]
+pub fn create_promo_code(
+    env: Env,
+    code_hash: BytesN<32>,
+    discount_percentage: u32,
+    free_usages: u32,
+    max_redemptions: u32,
+    expires_at: u64,
+    admin: Address,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::PromoCode(code_hash.clone());
+    if env.storage().persistent().has(&key) {
+        bump_persistent(&env, &key);
+        return Err(Error::AlreadyExists);
+    }
+
+    if discount_percentage > 100 || (discount_percentage == 0 && free_usages == 0) {
+        return Err(Error::InvalidInput);
+    }
+    if max_redemptions == 0 || expires_at <= env.ledger().timestamp() {
+        return Err(Error::InvalidInput);
+    }
+
+    let promo = PromoCode {
+        code_hash,
+        discount_percentage,
+        free_usages,
+        max_redemptions,
+        redemptions: 0,
+        expires_at,
+        is_active: true,
+    };
+    env.storage().persistent().set(&key, &promo);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+/// Stops a promo code from being redeemed (admin only).
+pub fn deactivate_promo_code(env: Env, code_hash: BytesN<32>, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::PromoCode(code_hash.clone());
+    let mut promo = get_promo_code(env.clone(), code_hash)?;
+    promo.is_active = false;
+    env.storage().persistent().set(&key, &promo);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+pub fn get_promo_code(env: Env, code_hash: BytesN<32>) -> Result<PromoCode, Error> {
+    let key = DataKey::PromoCode(code_hash);
+    let result: Option<PromoCode> = env.storage().persistent().get(&key);
+    if result.is_some() {
+        bump_persistent(&env, &key);
+    }
+    result.ok_or(Error::NotFound)
+}
+
+/// Exempts an address from paying usage fees (admin only).
+pub fn add_fee_waiver(env: Env, address: Address, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::FeeWaiver(address);
+    if env.storage().persistent().has(&key) {
+        return Err(Error::AlreadyExists);
+    }
+    env.storage().persistent().set(&key, &true);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+pub fn remove_fee_waiver(env: Env, address: Address, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    let key = DataKey::FeeWaiver(address);
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::NotFound);
+    }
+    env.storage().persistent().remove(&key);
+    Ok(())
+}
+
+pub fn is_fee_waived(env: Env, address: Address) -> bool {
+    let key = DataKey::FeeWaiver(address);
+    let waived = env.storage().persistent().has(&key);
+    if waived {
+        bump_persistent(&env, &key);
+    }
+    waived
+}
+
+/// Prices a usage purchase for `payer`.
+/// Waived payers pay nothing and do not consume a promo redemption. Otherwise the
+/// tiered quote is adjusted by the promo code, if any, and the redemption recorded.
+fn price_purchase(
+    env: &Env,
+    payer: &Address,
+    group_id: &BytesN<32>,
+    usage_count: u32,
+    promo_code: Option<Bytes>,
+) -> Result<PurchasePrice, Error> {
+    if is_fee_waived(env.clone(), payer.clone()) {
+        return Ok(PurchasePrice {
+            quote: UsageQuote {
+                usage_count,
+                unit_price: 0,
+                total_cost: 0,
+            },
+            promo_code: None,
+            fee_waived: true,
+        });
+    }
+
+    let mut quote = get_usage_quote(env.clone(), usage_count);
+    let code = match promo_code {
+        Some(code) => code,
+        None => {
+            return Ok(PurchasePrice {
+                quote,
+                promo_code: None,
+                fee_waived: false,
+            })
+        }
+    };
+
+    let code_hash: BytesN<32> = env.crypto().sha256(&code).into();
+    let key = DataKey::PromoCode(code_hash.clone());
+    let mut promo: PromoCode = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::InvalidPromoCode)?;
+    if !promo.is_active {
+        return Err(Error::InvalidPromoCode);
+    }
+    if env.ledger().timestamp() >= promo.expires_at {
+        return Err(Error::PromoCodeExpired);
+    }
+    if promo.redemptions >= promo.max_redemptions {
+        return Err(Error::PromoCodeExhausted);
+    }
+
+    let charged_usages = usage_count.saturating_sub(promo.free_usages);
+    quote.unit_price = quote.unit_price * ((100 - promo.discount_percentage) as i128) / 100;
+    quote.total_cost = quote.unit_price * (charged_usages as i128);
+
+    promo.redemptions += 1;
+    env.storage().persistent().set(&key, &promo);
+    bump_persistent(env, &key);
+
+    PromoCodeRedeemed {
+        code_hash: code_hash.clone(),
+        user: payer.clone(),
+        group_id: group_id.clone(),
+    }
+    .publish(env);
+
+    Ok(PurchasePrice {
+        quote,
+        promo_code: Some(code_hash),
+        fee_waived: false,
+    })
+}
+
+// ============================================================================
+// Referrals
+// ============================================================================
+
+/// Sets the share of usage fees credited to referrers, as a percentage (admin only).
+pub fn set_referral_rate(env: Env, rate: u32, admin: Address) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+    if rate > 100 {
+        return Err(Error::InvalidInput);
+    }
+
+    let rate_key = DataKey::ReferralRate;
+    env.storage().persistent().set(&rate_key, &rate);
+    bump_persistent(&env, &rate_key);
+    Ok(())
+}
+
+pub fn get_referral_rate(env: Env) -> u32 {
+    let rate_key = DataKey::ReferralRate;
+    let result: Option<u32> = env.storage().persistent().get(&rate_key);
+    if result.is_some() {
+        bump_persistent(&env, &rate_key);
+    }
+    result.unwrap_or(0)
+}
+
+pub fn get_referral_stats(env: Env, referrer: Address) -> ReferralStats {
+    let key = DataKey::ReferralStats(referrer);
+    let result: Option<ReferralStats> = env.storage().persistent().get(&key);
+    if let Some(stats) = result {
+        bump_persistent(&env, &key);
+        stats
+    } else {
+        ReferralStats {
+            referred_groups: Vec::new(&env),
+            total_earned: Vec::new(&env),
+            claimable: Vec::new(&env),
+        }
+    }
+}
+
+/// Adds `amount` to the entry for `token`, creating it if needed.
+fn add_token_amount(amounts: &mut Vec<TokenAmount>, token: &Address, amount: i128) {
+    for (idx, entry) in amounts.iter().enumerate() {
+        if entry.token == *token {
+            amounts.set(
+                idx as u32,
+                TokenAmount {
+                    token: entry.token,
+                    amount: entry.amount + amount,
+                },
+            );
+            return;
+        }
+    }
+    amounts.push_back(TokenAmount {
+        token: token.clone(),
+        amount,
+    });
+}
+
+/// Records a referred purchase and credits the referrer's share of the fee paid.
+fn credit_referral(
+    env: &Env,
+    referrer: &Address,
+    group_id: &BytesN<32>,
+    token: &Address,
+    fee_paid: i128,
+) {
+    let mut stats = get_referral_stats(env.clone(), referrer.clone());
+    if !stats.referred_groups.contains(group_id) {
+        stats.referred_groups.push_back(group_id.clone());
+    }
+
+    let reward = fee_paid * (get_referral_rate(env.clone()) as i128) / 100;
+    if reward > 0 {
+        add_token_amount(&mut stats.total_earned, token, reward);
+        add_token_amount(&mut stats.claimable, token, reward);
+        adjust_reserved(env, token, reward);
+        ReferralRewarded {
+            referrer: referrer.clone(),
+            group_id: group_id.clone(),
+            token: token.clone(),
+            amount: reward,
+        }
+        .publish(env);
+    }
+
+    let key = DataKey::ReferralStats(referrer.clone());
+    env.storage().persistent().set(&key, &stats);
+    bump_persistent(env, &key);
+}
+
+/// Pays out the referrer's claimable balance in `token` and returns the amount paid.
+pub fn claim_referral_rewards(env: Env, referrer: Address, token: Address) -> Result<i128, Error> {
+    referrer.require_auth();
+
+    require_operation_active(&env, PauseCategory::Withdrawals)?;
+
+    require_not_denylisted(&env, &referrer)?;
+
+    let mut stats = get_referral_stats(env.clone(), referrer.clone());
+    let mut claimed: i128 = 0;
+    for (idx, entry) in stats.claimable.iter().enumerate() {
+        if entry.token == token {
+            claimed = entry.amount;
+            stats.claimable.remove(idx as u32);
+            break;
+        }
+    }
+    if claimed <= 0 {
+        return Err(Error::InsufficientBalance);
+    }
+
+    let key = DataKey::ReferralStats(referrer.clone());
+    env.storage().persistent().set(&key, &stats);
+    bump_persistent(&env, &key);
+    adjust_reserved(&env, &token, -claimed);
+
+    let token_client = token::Client::new(&env, &token);
+    token_client.transfer(&env.current_contract_address(), &referrer, &claimed);
+    Ok(claimed)
+}
+
+// ============================================================================
+// Sponsorship
+// ============================================================================
+
+fn get_sponsor_pool_record(env: &Env, pool_id: &BytesN<32>) -> Result<SponsorPool, Error> {
+    let key = DataKey::SponsorPool(pool_id.clone());
+    let pool: SponsorPool = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(env, &key);
+    Ok(pool)
+}
+
+fn save_sponsor_pool(env: &Env, pool: &SponsorPool) {
+    let key = DataKey::SponsorPool(pool.id.clone());
+    env.storage().persistent().set(&key, pool);
+    bump_persistent(env, &key);
+}
+
+fn load_sponsor_pool_for(
+    env: &Env,
+    pool_id: &BytesN<32>,
+    sponsor: &Address,
+) -> Result<SponsorPool, Error> {
+    let pool = get_sponsor_pool_record(env, pool_id)?;
+    if pool.sponsor != *sponsor {
+        return Err(Error::Unauthorized);
+    }
+    Ok(pool)
+}
+
+fn get_pool_index<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env: &Env,
+    key: &K,
+) -> Vec<BytesN<32>> {
+    let pool_ids: Vec<BytesN<32>> = env.storage().persistent().get(key).unwrap_or(Vec::new(env));
+    if !pool_ids.is_empty() {
+        bump_persistent(env, key);
+    }
+    pool_ids
+}
+
+fn add_to_pool_index<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env: &Env,
+    key: &K,
+    pool_id: &BytesN<32>,
+) {
+    let mut pool_ids = get_pool_index(env, key);
+    pool_ids.push_back(pool_id.clone());
+    env.storage().persistent().set(key, &pool_ids);
+    bump_persistent(env, key);
+}
+
+fn remove_from_pool_index<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(
+    env: &Env,
+    key: &K,
+    pool_id: &BytesN<32>,
+) {
+    let pool_ids = get_pool_index(env, key);
+    let mut updated: Vec<BytesN<32>> = Vec::new(env);
+    for existing in pool_ids.iter() {
+        if existing != *pool_id {
+            updated.push_back(existing);
+        }
+    }
+    env.storage().persistent().set(key, &updated);
+    bump_persistent(env, key);
+}
+
+/// Creates an empty sponsor pool. Usage fees are paid from it in `token`,
+/// and each covered group can be sponsored at most `per_group_limit` usages.
+pub fn create_sponsor_pool(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    token: Address,
+    per_group_limit: u32,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Sponsorship)?;
+
+    let key = DataKey::SponsorPool(pool_id.clone());
+    if env.storage().persistent().has(&key) {
+        bump_persistent(&env, &key);
+        return Err(Error::AlreadyExists);
+    }
+
+    if per_group_limit == 0 {
+        return Err(Error::InvalidUsageCount);
+    }
+
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    let pool = SponsorPool {
+        id: pool_id,
+        sponsor,
+        token,
+        balance: 0,
+        per_group_limit,
+        sponsored_groups: Vec::new(&env),
+        sponsored_creators: Vec::new(&env),
+        total_spent: 0,
+    };
+    save_sponsor_pool(&env, &pool);
+    Ok(())
+}
+
+/// Adds funds to a sponsor pool. Anyone may fund a pool.
+pub fn fund_sponsor_pool(
+    env: Env,
+    pool_id: BytesN<32>,
+    funder: Address,
+    amount: i128,
+) -> Result<(), Error> {
+    funder.require_auth();
+
+    require_operation_active(&env, PauseCategory::Sponsorship)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let mut pool = get_sponsor_pool_record(&env, &pool_id)?;
+
+    let token_client = token::Client::new(&env, &pool.token);
+    token_client.transfer(&funder, env.current_contract_address(), &amount);
+
+    pool.balance += amount;
+    save_sponsor_pool(&env, &pool);
+    adjust_reserved(&env, &pool.token, amount);
+    Ok(())
+}
+
+/// Returns unspent funds from a sponsor pool to its sponsor.
+pub fn withdraw_sponsor_funds(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    amount: i128,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Withdrawals)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    if pool.balance < amount {
+        return Err(Error::InsufficientBalance);
+    }
+
+    pool.balance -= amount;
+    save_sponsor_pool(&env, &pool);
+    adjust_reserved(&env, &pool.token, -amount);
+
+    let token_client = token::Client::new(&env, &pool.token);
+    token_client.transfer(&env.current_contract_address(), &sponsor, &amount);
+    Ok(())
+}
+
+/// Updates how many usages the pool will cover for each group.
+pub fn set_sponsor_pool_limit(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    per_group_limit: u32,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Sponsorship)?;
+
+    if per_group_limit == 0 {
+        return Err(Error::InvalidUsageCount);
+    }
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    pool.per_group_limit = per_group_limit;
+    save_sponsor_pool(&env, &pool);
+    Ok(())
+}
+
+pub fn sponsor_group(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    group_id: BytesN<32>,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Sponsorship)?;
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    get_autoshare(env.clone(), group_id.clone())?;
+
+    if pool.sponsored_groups.contains(&group_id) {
+        return Err(Error::AlreadyExists);
+    }
+
+    pool.sponsored_groups.push_back(group_id.clone());
+    save_sponsor_pool(&env, &pool);
+    add_to_pool_index(&env, &DataKey::GroupSponsorPools(group_id), &pool_id);
+    Ok(())
+}
+
+pub fn unsponsor_group(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    group_id: BytesN<32>,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    let index = pool
+        .sponsored_groups
+        .first_index_of(&group_id)
+        .ok_or(Error::NotFound)?;
+
+    pool.sponsored_groups.remove(index);
+    save_sponsor_pool(&env, &pool);
+    remove_from_pool_index(&env, &DataKey::GroupSponsorPools(group_id), &pool_id);
+    Ok(())
+}
+
+/// Covers every group created by `creator`, including groups created later.
+pub fn sponsor_creator(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    creator: Address,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Sponsorship)?;
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    if pool.sponsored_creators.contains(&creator) {
+        return Err(Error::AlreadyExists);
+    }
+
+    pool.sponsored_creators.push_back(creator.clone());
+    save_sponsor_pool(&env, &pool);
+    add_to_pool_index(&env, &DataKey::CreatorSponsorPools(creator), &pool_id);
+    Ok(())
+}
+
+pub fn unsponsor_creator(
+    env: Env,
+    pool_id: BytesN<32>,
+    sponsor: Address,
+    creator: Address,
+) -> Result<(), Error> {
+    sponsor.require_auth();
+
+    let mut pool = load_sponsor_pool_for(&env, &pool_id, &sponsor)?;
+    let index = pool
+        .sponsored_creators
+        .first_index_of(&creator)
+        .ok_or(Error::NotFound)?;
+
+    pool.sponsored_creators.remove(index);
+    save_sponsor_pool(&env, &pool);
+    remove_from_pool_index(&env, &DataKey::CreatorSponsorPools(creator), &pool_id);
+    Ok(())
+}
+
+pub fn get_sponsor_pool(env: Env, pool_id: BytesN<32>) -> Result<SponsorPool, Error> {
+    get_sponsor_pool_record(&env, &pool_id)
+}
+
+/// Returns per-group spending for a sponsor pool.
+pub fn get_sponsor_spending(env: Env, pool_id: BytesN<32>) -> Vec<SponsorSpending> {
+    let key = DataKey::SponsorPoolSpending(pool_id);
+    let result: Option<Vec<SponsorSpending>> = env.storage().persistent().get(&key);
+    if result.is_some() {
+        bump_persistent(&env, &key);
+    }
+    result.unwrap_or(Vec::new(&env))
+}
+
+/// Returns the index of the group's entry in a pool's spending list.
+fn find_spending_entry(spending: &Vec<SponsorSpending>, group_id: &BytesN<32>) -> Option<u32> {
+    for (idx, entry) in spending.iter().enumerate() {
+        if entry.group_id == *group_id {
+            return Some(idx as u32);
+        }
+    }
+    None
+}
+
+/// Returns the first pool covering the group (group pools before creator pools)
+/// that can still pay `price` for it. Reads only.
+fn select_sponsor_pool(env: &Env, details: &AutoShareDetails, price: i128) -> Option<SponsorPool> {
+    let mut pool_ids = get_pool_index(env, &DataKey::GroupSponsorPools(details.id.clone()));
+    pool_ids.append(&get_pool_index(
+        env,
+        &DataKey::CreatorSponsorPools(details.creator.clone()),
+    ));
+
+    for pool_id in pool_ids.iter() {
+        let pool = match get_sponsor_pool_record(env, &pool_id) {
+            Ok(pool) => pool,
+            Err(_) => continue,
+        };
+        if pool.balance < price {
+            continue;
+        }
+
+        let spending = get_sponsor_spending(env.clone(), pool_id);
+        let usages_sponsored = match find_spending_entry(&spending, &details.id) {
+            Some(idx) => spending.get(idx).unwrap().usages_sponsored,
+            None => 0,
+        };
+        if usages_sponsored < pool.per_group_limit {
+            return Some(pool);
+        }
+    }
+    None
+}
+
+/// Buys a single usage for a group that has run out, using the first sponsor
+/// pool that covers the group, has enough balance and is under its per-group limit.
+/// Group pools are consulted before creator pools.
+fn sponsor_usage(env: &Env, details: &mut AutoShareDetails) -> Result<(), Error> {
+    let quote = get_usage_quote(env.clone(), 1);
+    let price = quote.unit_price;
+
+    let mut pool = select_sponsor_pool(env, details, price).ok_or(Error::NoUsagesRemaining)?;
+    let pool_id = pool.id.clone();
+
+    let spending_key = DataKey::SponsorPoolSpending(pool_id.clone());
+    let mut spending = get_sponsor_spending(env.clone(), pool_id.clone());
+    let entry_index = find_spending_entry(&spending, &details.id);
+    let mut entry = match entry_index {
+        Some(idx) => spending.get(idx).unwrap(),
+        None => SponsorSpending {
+            group_id: details.id.clone(),
+            usages_sponsored: 0,
+            amount_spent: 0,
+        },
+    };
+
+    entry.usages_sponsored += 1;
+    entry.amount_spent += price;
+    match entry_index {
+        Some(idx) => spending.set(idx, entry),
+        None => spending.push_back(entry),
+    }
+    env.storage().persistent().set(&spending_key, &spending);
+    bump_persistent(env, &spending_key);
+
+    pool.balance -= price;
+    pool.total_spent += price;
+    save_sponsor_pool(env, &pool);
+    // The fee now belongs to the contract
+    adjust_reserved(env, &pool.token, -price);
+
+    details.usage_count += 1;
+    details.total_usages_paid += 1;
+    record_payment(
+        env.clone(),
+        pool.sponsor.clone(),
+        details.id.clone(),
+        PurchasePrice {
+            quote,
+            promo_code: None,
+            fee_waived: false,
+        },
+    );
+
+    UsageSponsored {
+        pool_id,
+        group_id: details.id.clone(),
+        amount: price,
+    }
+    .publish(env);
+    Ok(())
+}
+
+// ============================================================================
+// Payment History
+// ============================================================================
+
+fn record_payment(env: Env, user: Address, group_id: BytesN<32>, price: PurchasePrice) {
+    let timestamp = env.ledger().timestamp();
+
+    let payment = PaymentHistory {
+        user: user.clone(),
+        group_id: group_id.clone(),
+        usages_purchased: price.quote.usage_count,
+        amount_paid: price.quote.total_cost,
+        unit_price: price.quote.unit_price,
+        promo_code: price.promo_code,
+        fee_waived: price.fee_waived,
+        timestamp,
+    };
+
+    // Add to user's payment history
+    let user_history_key = DataKey::UserPaymentHistory(user.clone());
+    let mut user_history: Vec<PaymentHistory> = env
+        .storage()
+        .persistent()
+        .get(&user_history_key)
+        .unwrap_or(Vec::new(&env));
+    if !user_history.is_empty() {
+        bump_persistent(&env, &user_history_key);
+    }
+    user_history.push_back(payment.clone());
+    env.storage()
+        .persistent()
+        .set(&user_history_key, &user_history);
+    bump_persistent(&env, &user_history_key);
+
+    // Add to group's payment history
+    let group_history_key = DataKey::GroupPaymentHistory(group_id);
+    let mut group_history: Vec<PaymentHistory> = env
+        .storage()
+        .persistent()
+        .get(&group_history_key)
+        .unwrap_or(Vec::new(&env));
+    if !group_history.is_empty() {
+        bump_persistent(&env, &group_history_key);
+    }
+    group_history.push_back(payment);
+    env.storage()
+        .persistent()
+        .set(&group_history_key, &group_history);
+    bump_persistent(&env, &group_history_key);
+}
+
+pub fn get_user_payment_history(env: Env, user: Address) -> Vec<PaymentHistory> {
+    let user_history_key = DataKey::UserPaymentHistory(user);
+    let result: Option<Vec<PaymentHistory>> = env.storage().persistent().get(&user_history_key);
+    if result.is_some() {
+        bump_persistent(&env, &user_history_key);
+    }
+    result.unwrap_or(Vec::new(&env))
+}
+
+pub fn get_group_payment_history(env: Env, id: BytesN<32>) -> Vec<PaymentHistory> {
+    let group_history_key = DataKey::GroupPaymentHistory(id);
+    let result: Option<Vec<PaymentHistory>> = env.storage().persistent().get(&group_history_key);
+    if result.is_some() {
+        bump_persistent(&env, &group_history_key);
+    }
+    result.unwrap_or(Vec::new(&env))
+}
+
+// ============================================================================
+// Distribution History
+// ============================================================================
+
+#[allow(clippy::too_many_arguments)]
+fn record_distribution(
+    env: Env,
+    group_id: BytesN<32>,
+    sender: Address,
+    token_amounts: Vec<TokenAmount>,
+    member_amounts: Vec<MemberAmount>,
+    distribution_number: u32,
+    memo: Option<String>,
+    invoice_id: Option<BytesN<32>>,
+    swaps: Vec<SwapReceipt>,
+) {
+    let timestamp = env.ledger().timestamp();
+    let primary = token_amounts.get(0).unwrap();
+
+    let distribution = DistributionHistory {
+        group_id: group_id.clone(),
+        sender: sender.clone(),
+        total_amount: primary.amount,
+        token: primary.token,
+        member_amounts: member_amounts.clone(),
+        timestamp,
+        distribution_number,
+        memo,
+        invoice_id,
+        token_amounts,
+        swaps,
+    };
+
+    // Add to group's distribution history
+    let group_history_key = DataKey::GroupDistributionHistory(group_id.clone());
+    let mut group_history: Vec<DistributionHistory> = env
+        .storage()
+        .persistent()
+        .get(&group_history_key)
+        .unwrap_or(Vec::new(&env));
+    group_history.push_back(distribution.clone());
+    env.storage()
+        .persistent()
+        .set(&group_history_key, &group_history);
+
+    // Add to each member's distribution history
+    for member_amount in member_amounts.iter() {
+        let member_history_key = DataKey::MemberDistributions(member_amount.address.clone());
+        let mut member_history: Vec<DistributionRecord> = env
+            .storage()
+            .persistent()
+            .get(&member_history_key)
+            .unwrap_or(Vec::new(&env));
+        let record = DistributionRecord {
+            group_id: group_id.clone(),
+            amount: member_amount.amount,
+            token: member_amount.token,
+            timestamp,
+        };
+        member_history.push_back(record);
+        env.storage()
+            .persistent()
+            .set(&member_history_key, &member_history);
+    }
+}
+
+pub fn get_group_distributions(env: Env, id: BytesN<32>) -> Vec<DistributionHistory> {
+    let group_history_key = DataKey::GroupDistributionHistory(id);
+    env.storage()
+        .persistent()
+        .get(&group_history_key)
+        .unwrap_or(Vec::new(&env))
+}
+
+/// Returns the most recent distribution of a group carrying the given memo.
+pub fn get_distribution_by_reference(
+    env: Env,
+    id: BytesN<32>,
+    memo: String,
+) -> Result<DistributionHistory, Error> {
+    let history = get_group_distributions(env, id);
+    for idx in (0..history.len()).rev() {
+        let distribution = history.get(idx).unwrap();
+        if distribution.memo.as_ref() == Some(&memo) {
+            return Ok(distribution);
+        }
+    }
+    Err(Error::NotFound)
+}
+
+pub fn get_member_distributions(env: Env, member: Address) -> Vec<DistributionRecord> {
+    let member_history_key = DataKey::MemberDistributions(member);
+    env.storage()
+        .persistent()
+        .get(&member_history_key)
+        .unwrap_or(Vec::new(&env))
+}
+
+// ============================================================================
+// Usage Tracking
+// ============================================================================
+
+pub fn get_remaining_usages(env: Env, id: BytesN<32>) -> Result<u32, Error> {
+    let key = DataKey::AutoShare(id);
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+    Ok(details.usage_count)
+}
+
+pub fn get_total_usages_paid(env: Env, id: BytesN<32>) -> Result<u32, Error> {
+    let key = DataKey::AutoShare(id);
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+    Ok(details.total_usages_paid)
+}
+
+#[cfg(test)]
+#[allow(dead_code)]
+pub fn reduce_usage(env: Env, id: BytesN<32>) -> Result<(), Error> {
+    let key = DataKey::AutoShare(id);
+    let mut details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    if details.usage_count == 0 {
+        return Err(Error::NoUsagesRemaining);
+    }
+
+    details.usage_count -= 1;
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+// ============================================================================
+// Group Activation Management
+// ============================================================================
+
+pub fn update_members(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    new_members: Vec<GroupMember>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let key = DataKey::AutoShare(id.clone());
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageMembers)?;
+
+    require_creator_control(&env, &id)?;
+
+    change_split(env, id, details, new_members, caller)
+}
+
+/// Replaces a group's split, or queues the change when the group has a split
+/// timelock. Used by `update_members` and by executed proposals.
+fn change_split(
+    env: Env,
+    id: BytesN<32>,
+    details: AutoShareDetails,
+    new_members: Vec<GroupMember>,
+    updater: Address,
+) -> Result<(), Error> {
+    let delay = get_group_settings(env.clone(), id.clone()).split_timelock;
+    if delay > 0 {
+        return queue_split_change(env, id, details, new_members, updater, delay);
+    }
+    apply_member_update(env, id, details, new_members, updater)
+}
+
+/// Checks a replacement split the way `update_members` does.
+fn validate_member_update(env: &Env, new_members: &Vec<GroupMember>) -> Result<(), Error> {
+    // Check if new members count exceeds MAX_MEMBERS
+    if new_members.len() > MAX_MEMBERS {
+        return Err(Error::MaxMembersExceeded);
+    }
+    for member in new_members.iter() {
+        require_not_denylisted(env, &member.address)?;
+        if let Some(payout_address) = &member.payout_address {
+            require_not_denylisted(env, payout_address)?;
+        }
+    }
+    validate_members(new_members)
+}
+
+/// Checks that a group can take a replacement split, before anything is written.
+fn check_member_update(
+    env: &Env,
+    details: &AutoShareDetails,
+    new_members: &Vec<GroupMember>,
+) -> Result<(), Error> {
+    if !details.is_active {
+        return Err(Error::GroupInactive);
+    }
+
+    require_not_frozen(env, &details.id)?;
+
+    validate_member_update(env, new_members)
+}
+
+/// Splits a replacement split into the members it keeps or adds directly, with
+/// the payout preferences members set themselves carried over, and the addresses
+/// that are not members yet. Those are invited rather than added; the creator
+/// authorizes the change, so they join directly.
+fn resolve_member_update(
+    env: &Env,
+    details: &AutoShareDetails,
+    new_members: Vec<GroupMember>,
+) -> (Vec<GroupMember>, Vec<GroupMember>) {
+    let mut accepted: Vec<GroupMember> = Vec::new(env);
+    let mut invitees: Vec<GroupMember> = Vec::new(env);
+    for mut member in new_members.iter() {
+        let old_member = details.members.iter().find(|m| m.address == member.address);
+        if member.preferred_token.is_none() && member.payout_address.is_none() {
+            if let Some(old_member) = &old_member {
+                member.preferred_token = old_member.preferred_token.clone();
+                member.payout_address = old_member.payout_address.clone();
+            }
+        }
+        if old_member.is_some() || member.address == details.creator {
+            accepted.push_back(member);
+        } else {
+            invitees.push_back(member);
+        }
+    }
+    (accepted, invitees)
+}
+
+/// Replaces a group's split at once. Fails without writing anything if the
+/// group cannot take it.
+fn apply_member_update(
+    env: Env,
+    id: BytesN<32>,
+    mut details: AutoShareDetails,
+    new_members: Vec<GroupMember>,
+    updater: Address,
+) -> Result<(), Error> {
+    let key = DataKey::AutoShare(id.clone());
+
+    check_member_update(&env, &details, &new_members)?;
+
+    // Determine old members for index updating
+    let old_members = details.members.clone();
+
+    let (accepted, invitees) = resolve_member_update(&env, &details, new_members);
+    let old_invitations = read_invitations(&env, &id);
+    let mut invitations: Vec<Invitation> = Vec::new(&env);
+    for member in invitees.iter() {
+        let previous = old_invitations
+            .iter()
+            .find(|invitation| invitation.invitee == member.address);
+        if previous.is_none() {
+            MemberInvited {
+                group_id: id.clone(),
+                invitee: member.address.clone(),
+                percentage: member.percentage,
+            }
+            .publish(&env);
+        }
+        invitations.push_back(Invitation {
+            invitee: member.address,
+            percentage: member.percentage,
+            invited_at: previous
+                .map(|invitation| invitation.invited_at)
+                .unwrap_or(env.ledger().timestamp()),
+        });
+    }
+    let new_members = accepted;
+    write_invitations(&env, &id, &invitations);
+
+    // Update members in details
+    details.members = new_members.clone();
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+
+    // Update MemberGroups index for removed and added members
+    for old_member in old_members.iter() {
+        let mut found_in_new = false;
+        for new_member in new_members.iter() {
+            if old_member.address == new_member.address {
+                found_in_new = true;
+                break;
+            }
+        }
+        if !found_in_new {
+            // Member was removed, remove group from their index
+            let member_groups_key = DataKey::MemberGroups(old_member.address.clone());
+            let member_groups: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&member_groups_key)
+                .unwrap_or(Vec::new(&env));
+
+            let mut updated_member_groups: Vec<BytesN<32>> = Vec::new(&env);
+            let mut group_removed = false;
+            for group_id in member_groups.iter() {
+                if group_id != id {
+                    updated_member_groups.push_back(group_id);
+                } else {
+                    group_removed = true;
+                }
+            }
+            if group_removed {
+                env.storage()
+                    .persistent()
+                    .set(&member_groups_key, &updated_member_groups);
+                bump_persistent(&env, &member_groups_key);
+            }
+        }
+    }
+
+    for new_member in new_members.iter() {
+        let mut found_in_old = false;
+        for old_member in old_members.iter() {
+            if new_member.address == old_member.address {
+                found_in_old = true;
+                break;
+            }
+        }
+        if !found_in_old {
+            // Member was added, add group to their index
+            let member_groups_key = DataKey::MemberGroups(new_member.address.clone());
+            let mut member_groups: Vec<BytesN<32>> = env
+                .storage()
+                .persistent()
+                .get(&member_groups_key)
+                .unwrap_or(Vec::new(&env));
+
+            member_groups.push_back(id.clone());
+            env.storage()
+                .persistent()
+                .set(&member_groups_key, &member_groups);
+            bump_persistent(&env, &member_groups_key);
+        }
+    }
+
+    AutoshareUpdated {
+        id: id.clone(),
+        updater,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+pub fn deactivate_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let key = DataKey::AutoShare(id.clone());
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    require_group_permission(&env, &details, &caller, ManagerPermission::Deactivate)?;
+
+    require_creator_control(&env, &id)?;
+    apply_activation(env, id, details, false, caller)
+}
+
+pub fn activate_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    caller.require_auth();
 
-pub fn get_remaining_usages(env: Env, id: BytesN<32>) -> Result<u32, Error> {
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let key = DataKey::AutoShare(id.clone());
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+
+    require_group_permission(&env, &details, &caller, ManagerPermission::Deactivate)?;
+
+    require_creator_control(&env, &id)?;
+    apply_activation(env, id, details, true, caller)
+}
+
+/// Activates or deactivates a group. Used by `activate_group`, `deactivate_group`
+/// and by executed proposals.
+fn apply_activation(
+    env: Env,
+    id: BytesN<32>,
+    mut details: AutoShareDetails,
+    active: bool,
+    updater: Address,
+) -> Result<(), Error> {
+    if details.is_active == active {
+        return Err(if active {
+            Error::GroupAlreadyActive
+        } else {
+            Error::GroupAlreadyInactive
+        });
+    }
+
+    let key = DataKey::AutoShare(id.clone());
+    details.is_active = active;
+    env.storage().persistent().set(&key, &details);
+    bump_persistent(&env, &key);
+
+    if active {
+        GroupActivated {
+            id,
+            creator: updater,
+        }
+        .publish(&env);
+    } else {
+        GroupDeactivated {
+            id,
+            creator: updater,
+        }
+        .publish(&env);
+    }
+    Ok(())
+}
+
+pub fn update_group_name(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    new_name: String,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let key = DataKey::AutoShare(id.clone());
+    let details: AutoShareDetails = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    require_creator_control(&env, &id)?;
+    apply_name_update(env, id, details, new_name, caller)
+}
+
+/// Renames a group. Used by `update_group_name` and by executed proposals.
+fn apply_name_update(
+    env: Env,
+    id: BytesN<32>,
+    mut details: AutoShareDetails,
+    new_name: String,
+    updater: Address,
+) -> Result<(), Error> {
+    if !details.is_active {
+        return Err(Error::GroupInactive);
+    }
+
+    if !is_valid_name(&new_name) {
+        return Err(Error::EmptyName);
+    }
+
+    details.name = new_name;
+    env.storage()
+        .persistent()
+        .set(&DataKey::AutoShare(id.clone()), &details);
+
+    GroupNameUpdated { id, updater }.publish(&env);
+    Ok(())
+}
+
+pub fn is_group_active(env: Env, id: BytesN<32>) -> Result<bool, Error> {
     let key = DataKey::AutoShare(id);
     let details: AutoShareDetails = env
         .storage()
@@ -916,302 +3040,1012 @@ pub fn get_remaining_usages(env: Env, id: BytesN<32>) -> Result<u32, Error> {
         .get(&key)
         .ok_or(Error::NotFound)?;
     bump_persistent(&env, &key);
-    Ok(details.usage_count)
+    Ok(details.is_active)
+}
+
+// ============================================================================
+// Group Settings
+// ============================================================================
+
+pub fn get_group_settings(env: Env, id: BytesN<32>) -> GroupSettings {
+    let key = DataKey::GroupSettings(id);
+    match env.storage().persistent().get(&key) {
+        Some(settings) => {
+            bump_persistent(&env, &key);
+            settings
+        }
+        None => GroupSettings {
+            rounding_policy: RoundingPolicy::LastMember,
+            min_payout: 0,
+            leave_policy: LeavePolicy::RequireRebalance,
+            split_timelock: 0,
+            guardian: None,
+        },
+    }
+}
+
+fn save_group_settings(env: &Env, id: &BytesN<32>, settings: &GroupSettings) {
+    let key = DataKey::GroupSettings(id.clone());
+    env.storage().persistent().set(&key, settings);
+    bump_persistent(env, &key);
+}
+
+/// Sets how rounding dust is handled for the group. Only the creator can call.
+pub fn set_rounding_policy(
+    env: Env,
+    id: BytesN<32>,
+    policy: RoundingPolicy,
+    caller: Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut settings = get_group_settings(env.clone(), id.clone());
+    settings.rounding_policy = policy;
+    save_group_settings(&env, &id, &settings);
+    Ok(())
+}
+
+/// Sets what happens to a member's share when they leave. Only the creator can call.
+pub fn set_leave_policy(
+    env: Env,
+    id: BytesN<32>,
+    policy: LeavePolicy,
+    caller: Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut settings = get_group_settings(env.clone(), id.clone());
+    settings.leave_policy = policy;
+    save_group_settings(&env, &id, &settings);
+    Ok(())
+}
+
+/// Sets how long split changes made with `update_members` wait before they apply.
+/// The delay can only be raised, so a compromised key cannot switch it off.
+pub fn set_split_timelock(
+    env: Env,
+    id: BytesN<32>,
+    delay: u64,
+    caller: Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut settings = get_group_settings(env.clone(), id.clone());
+    if delay < settings.split_timelock {
+        return Err(Error::InvalidInput);
+    }
+    // Under a timelock, invitations only come from changes that waited it out,
+    // so accepting one completes a split members could already see coming
+    if settings.split_timelock == 0 && delay > 0 && !read_invitations(&env, &id).is_empty() {
+        return Err(Error::InvitationsPending);
+    }
+    settings.split_timelock = delay;
+    save_group_settings(&env, &id, &settings);
+    Ok(())
+}
+
+/// Returns the rounding dust carried forward for the group's next distribution of `token`.
+pub fn get_dust_credit(env: Env, id: BytesN<32>, token: Address) -> i128 {
+    let key = DataKey::DustCredit(id, token);
+    let credit: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if credit > 0 {
+        bump_persistent(&env, &key);
+    }
+    credit
+}
+
+/// Sets the smallest share paid out directly; smaller shares are held for the
+/// member until they add up. Only the creator can call.
+pub fn set_min_payout(
+    env: Env,
+    id: BytesN<32>,
+    min_payout: i128,
+    caller: Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    if min_payout < 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut settings = get_group_settings(env.clone(), id.clone());
+    settings.min_payout = min_payout;
+    save_group_settings(&env, &id, &settings);
+    Ok(())
+}
+
+/// Returns the amount held for `member` because their shares were below the
+/// group's minimum payout.
+pub fn get_pending_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128 {
+    let key = DataKey::PendingPayout(id, member, token);
+    let pending: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if pending > 0 {
+        bump_persistent(&env, &key);
+    }
+    pending
+}
+
+/// Pays out a member's pending balance regardless of the minimum payout, to
+/// their payout address and preferred token as a distribution would.
+pub fn claim_pending_payout(
+    env: Env,
+    id: BytesN<32>,
+    member: Address,
+    token: Address,
+) -> Result<i128, Error> {
+    member.require_auth();
+
+    require_operation_active(&env, PauseCategory::Withdrawals)?;
+
+    require_not_denylisted(&env, &member)?;
+
+    require_not_frozen(&env, &id)?;
+
+    let key = DataKey::PendingPayout(id.clone(), member.clone(), token.clone());
+    let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if amount <= 0 {
+        return Err(Error::InsufficientBalance);
+    }
+
+    env.storage().persistent().remove(&key);
+    adjust_reserved(&env, &token, -amount);
+    release_to_member(&env, &id, &member, &token, amount)?;
+
+    PendingPayoutReleased {
+        group_id: id,
+        member,
+        token,
+        amount,
+    }
+    .publish(&env);
+    Ok(amount)
+}
+
+/// Pays a held balance like a distribution share, to the member's payout address
+/// and in their preferred token. A member of a deleted group is paid directly.
+fn release_to_member(
+    env: &Env,
+    id: &BytesN<32>,
+    member: &Address,
+    token: &Address,
+    amount: i128,
+) -> Result<(), Error> {
+    match env
+        .storage()
+        .persistent()
+        .get::<DataKey, AutoShareDetails>(&DataKey::AutoShare(id.clone()))
+    {
+        Some(details) => {
+            if is_recipient_denylisted(env, &details, member) {
+                return Err(Error::AddressDenylisted);
+            }
+            let mut conversions: Vec<SwapReceipt> = Vec::new(env);
+            pay_member(env, &details, token, member, amount, &mut conversions)?;
+        }
+        None => token::TokenClient::new(env, token).transfer(
+            &env.current_contract_address(),
+            member,
+            &amount,
+        ),
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Group Freeze
+// ============================================================================
+
+/// Sets the address allowed to freeze the group in an emergency, alongside the
+/// admin. `None` removes the guardian. Creator only.
+pub fn set_group_guardian(
+    env: Env,
+    id: BytesN<32>,
+    guardian: Option<Address>,
+    caller: Address,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    // A frozen group keeps its guardian until the freeze is lifted or expires
+    require_not_frozen(&env, &id)?;
+
+    let mut settings = get_group_settings(env.clone(), id.clone());
+    settings.guardian = guardian;
+    save_group_settings(&env, &id, &settings);
+    Ok(())
+}
+
+/// Halts a group's distributions, contributions, payout claims and membership,
+/// payout, ownership and guardian changes for `duration` seconds. Callable by the group's guardian or the admin; freezing
+/// an already frozen group replaces the reason and expiry.
+pub fn freeze_group(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    reason: u32,
+    duration: u64,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    get_autoshare(env.clone(), id.clone())?;
+    require_guardian(&env, &id, &caller)?;
+
+    if duration == 0 {
+        return Err(Error::InvalidInput);
+    }
+    let expires_at = env
+        .ledger()
+        .timestamp()
+        .checked_add(duration)
+        .ok_or(Error::InvalidInput)?;
+
+    let freeze = GroupFreeze {
+        reason,
+        frozen_by: caller.clone(),
+        expires_at,
+    };
+    let key = DataKey::GroupFreeze(id.clone());
+    env.storage().persistent().set(&key, &freeze);
+    bump_persistent(&env, &key);
+
+    GroupFrozen {
+        group_id: id,
+        frozen_by: caller,
+        reason,
+        expires_at: freeze.expires_at,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+/// Lifts a freeze before it expires. Callable by the group's guardian or the admin.
+pub fn unfreeze_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_guardian(&env, &id, &caller)?;
+
+    let key = DataKey::GroupFreeze(id.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::NotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    GroupUnfrozen {
+        group_id: id,
+        unfrozen_by: caller,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+/// Returns the group's freeze while it is in effect.
+pub fn get_group_freeze(env: Env, id: BytesN<32>) -> Option<GroupFreeze> {
+    let key = DataKey::GroupFreeze(id);
+    let freeze: GroupFreeze = env.storage().persistent().get(&key)?;
+    if env.ledger().timestamp() >= freeze.expires_at {
+        return None;
+    }
+    bump_persistent(&env, &key);
+    Some(freeze)
+}
+
+pub fn is_group_frozen(env: Env, id: BytesN<32>) -> bool {
+    get_group_freeze(env, id).is_some()
+}
+
+fn require_not_frozen(env: &Env, id: &BytesN<32>) -> Result<(), Error> {
+    if is_group_frozen(env.clone(), id.clone()) {
+        return Err(Error::GroupFrozen);
+    }
+    Ok(())
+}
+
+fn require_guardian(env: &Env, id: &BytesN<32>, caller: &Address) -> Result<(), Error> {
+    let settings = get_group_settings(env.clone(), id.clone());
+    if settings.guardian.as_ref() == Some(caller) {
+        return Ok(());
+    }
+    require_admin(env, caller)
+}
+
+// ============================================================================
+// Split Timelock
+// ============================================================================
+
+/// Stores a validated split change to apply once the group's timelock expires.
+/// Only one change can be queued at a time.
+fn queue_split_change(
+    env: Env,
+    id: BytesN<32>,
+    details: AutoShareDetails,
+    new_members: Vec<GroupMember>,
+    queued_by: Address,
+    delay: u64,
+) -> Result<(), Error> {
+    check_member_update(&env, &details, &new_members)?;
+
+    let key = DataKey::PendingSplitChange(id.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(Error::AlreadyExists);
+    }
+
+    let change = PendingSplitChange {
+        members: new_members.clone(),
+        queued_by,
+        effective_at: env.ledger().timestamp() + delay,
+    };
+    env.storage().persistent().set(&key, &change);
+    bump_persistent(&env, &key);
+
+    SplitChangeQueued {
+        group_id: id,
+        effective_at: change.effective_at,
+        members: new_members,
+    }
+    .publish(&env);
+    Ok(())
 }
 
-pub fn get_total_usages_paid(env: Env, id: BytesN<32>) -> Result<u32, Error> {
-    let key = DataKey::AutoShare(id);
-    let details: AutoShareDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
-    Ok(details.total_usages_paid)
+pub fn get_pending_split_change(env: Env, id: BytesN<32>) -> Option<PendingSplitChange> {
+    let key = DataKey::PendingSplitChange(id);
+    let change = env.storage().persistent().get(&key);
+    if change.is_some() {
+        bump_persistent(&env, &key);
+    }
+    change
 }
 
-#[cfg(test)]
-#[allow(dead_code)]
-pub fn reduce_usage(env: Env, id: BytesN<32>) -> Result<(), Error> {
-    let key = DataKey::AutoShare(id);
-    let mut details: AutoShareDetails = env
-        .storage()
+/// Drops a queued split change. Callable by the creator and member managers.
+pub fn cancel_split_change(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageMembers)?;
+
+    let key = DataKey::PendingSplitChange(id.clone());
+    if !env.storage().persistent().has(&key) {
+        return Err(Error::NotFound);
+    }
+    env.storage().persistent().remove(&key);
+
+    SplitChangeCancelled {
+        group_id: id,
+        cancelled_by: caller,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+/// Applies a queued split change whose delay has passed. Anyone can call; due
+/// changes are also applied before the group's next distribution.
+pub fn apply_split_change(env: Env, id: BytesN<32>) -> Result<(), Error> {
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let change = get_pending_split_change(env.clone(), id.clone()).ok_or(Error::NotFound)?;
+    if env.ledger().timestamp() < change.effective_at {
+        return Err(Error::TimelockActive);
+    }
+    apply_due_split_change(&env, &id)
+}
+
+/// Returns the group's queued split change once its delay has passed.
+fn due_split_change(env: &Env, id: &BytesN<32>) -> Option<PendingSplitChange> {
+    let key = DataKey::PendingSplitChange(id.clone());
+    let change: PendingSplitChange = env.storage().persistent().get(&key)?;
+    if env.ledger().timestamp() < change.effective_at {
+        return None;
+    }
+    Some(change)
+}
+
+/// Applies the group's queued split change if its delay has passed. A change
+/// that can no longer apply, for instance because it names an address denylisted
+/// since, fails without writing anything and stays queued until cancelled.
+fn apply_due_split_change(env: &Env, id: &BytesN<32>) -> Result<(), Error> {
+    let change = match due_split_change(env, id) {
+        Some(change) => change,
+        None => return Ok(()),
+    };
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    apply_member_update(
+        env.clone(),
+        id.clone(),
+        details,
+        change.members,
+        change.queued_by,
+    )?;
+    env.storage()
         .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
+        .remove(&DataKey::PendingSplitChange(id.clone()));
+    Ok(())
+}
 
-    if details.usage_count == 0 {
-        return Err(Error::NoUsagesRemaining);
+/// Returns the group as its next distribution would see it, with a due split
+/// change applied, without writing anything. Used by `preview_distribution`.
+fn group_with_due_split_change(env: &Env, id: &BytesN<32>) -> Result<AutoShareDetails, Error> {
+    if let Some(change) = due_split_change(env, id) {
+        let mut details = get_autoshare(env.clone(), id.clone())?;
+        if check_member_update(env, &details, &change.members).is_ok() {
+            let (accepted, invitees) = resolve_member_update(env, &details, change.members);
+            if !invitees.is_empty() {
+                return Err(Error::InvitationsPending);
+            }
+            details.members = accepted;
+            return Ok(details);
+        }
     }
+    load_distributable_group(env, id)
+}
 
-    details.usage_count -= 1;
-    env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+/// Fails when the group's split can only change through a queued `update_members`.
+fn require_no_split_timelock(env: &Env, id: &BytesN<32>) -> Result<(), Error> {
+    if get_group_settings(env.clone(), id.clone()).split_timelock > 0 {
+        return Err(Error::TimelockActive);
+    }
     Ok(())
 }
 
 // ============================================================================
-// Group Activation Management
+// Ownership & Managers
 // ============================================================================
 
-pub fn update_members(
+/// Offers ownership of a group to `new_owner`, who must accept it with
+/// `accept_group_ownership`. A later offer replaces an earlier one.
+pub fn transfer_group_ownership(
     env: Env,
     id: BytesN<32>,
     caller: Address,
-    new_members: Vec<GroupMember>,
+    new_owner: Address,
 ) -> Result<(), Error> {
     caller.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
+    }
+
+    require_not_frozen(&env, &id)?;
+
+    if new_owner == caller {
+        return Err(Error::InvalidInput);
+    }
+
+    let key = DataKey::PendingGroupOwner(id);
+    env.storage().persistent().set(&key, &new_owner);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+/// Completes an ownership transfer. The new owner stops being a co-manager.
+pub fn accept_group_ownership(env: Env, id: BytesN<32>, new_owner: Address) -> Result<(), Error> {
+    new_owner.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    require_not_denylisted(&env, &new_owner)?;
+
+    let pending_key = DataKey::PendingGroupOwner(id.clone());
+    let pending: Address = env
+        .storage()
+        .persistent()
+        .get(&pending_key)
+        .ok_or(Error::NotFound)?;
+    if pending != new_owner {
+        return Err(Error::Unauthorized);
     }
 
+    require_not_frozen(&env, &id)?;
+
     let key = DataKey::AutoShare(id.clone());
     let mut details: AutoShareDetails = env
         .storage()
         .persistent()
         .get(&key)
         .ok_or(Error::NotFound)?;
+    let previous_owner = details.creator.clone();
+    details.creator = new_owner.clone();
+    env.storage().persistent().set(&key, &details);
     bump_persistent(&env, &key);
+    env.storage().persistent().remove(&pending_key);
+
+    let mut managers = read_managers(&env, &id);
+    if let Some(idx) = managers.iter().position(|m| m.address == new_owner) {
+        managers.remove(idx as u32);
+        write_managers(&env, &id, &managers);
+    }
+
+    GroupOwnershipTransferred {
+        group_id: id,
+        previous_owner,
+        new_owner,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+pub fn get_pending_group_owner(env: Env, id: BytesN<32>) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PendingGroupOwner(id))
+}
+
+/// Grants `manager` the given permissions on a group, replacing any they had.
+/// An empty list removes the manager. Creator only.
+pub fn set_group_manager(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    manager: Address,
+    permissions: Vec<ManagerPermission>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
+    let details = get_autoshare(env.clone(), id.clone())?;
     if details.creator != caller {
         return Err(Error::Unauthorized);
     }
 
-    if !details.is_active {
-        return Err(Error::GroupInactive);
+    require_not_frozen(&env, &id)?;
+
+    if manager == caller {
+        return Err(Error::InvalidInput);
     }
 
-    // Validate new members
-    if new_members.is_empty() {
-        return Err(Error::EmptyMembers);
+    let mut managers = read_managers(&env, &id);
+    if let Some(idx) = managers.iter().position(|m| m.address == manager) {
+        managers.remove(idx as u32);
     }
+    if !permissions.is_empty() {
+        managers.push_back(GroupManager {
+            address: manager,
+            permissions,
+        });
+    }
+    write_managers(&env, &id, &managers);
 
-    // Check if new members count exceeds MAX_MEMBERS
-    if new_members.len() > MAX_MEMBERS {
-        return Err(Error::MaxMembersExceeded);
+    AutoshareUpdated {
+        id,
+        updater: caller,
     }
+    .publish(&env);
+    Ok(())
+}
 
-    let mut total_percentage: u32 = 0;
-    let mut seen_addresses = Vec::new(&env);
+pub fn get_group_managers(env: Env, id: BytesN<32>) -> Vec<GroupManager> {
+    read_managers(&env, &id)
+}
 
-    for member in new_members.iter() {
-        if member.percentage == 0 {
-            return Err(Error::InvalidInput);
-        }
-        total_percentage += member.percentage;
+/// Passes for the group creator and for co-managers holding `permission`.
+fn require_group_permission(
+    env: &Env,
+    details: &AutoShareDetails,
+    caller: &Address,
+    permission: ManagerPermission,
+) -> Result<(), Error> {
+    if details.creator == *caller {
+        return Ok(());
+    }
+    let allowed = read_managers(env, &details.id)
+        .iter()
+        .any(|m| m.address == *caller && m.permissions.contains(&permission));
+    if allowed {
+        Ok(())
+    } else {
+        Err(Error::Unauthorized)
+    }
+}
 
-        for seen in seen_addresses.iter() {
-            if seen == member.address {
-                return Err(Error::DuplicateMember);
-            }
+fn read_managers(env: &Env, id: &BytesN<32>) -> Vec<GroupManager> {
+    let key = DataKey::GroupManagers(id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(managers) => {
+            bump_persistent(env, &key);
+            managers
         }
-        seen_addresses.push_back(member.address.clone());
+        None => Vec::new(env),
     }
+}
 
-    if total_percentage != 100 {
-        return Err(Error::InvalidTotalPercentage);
+fn write_managers(env: &Env, id: &BytesN<32>, managers: &Vec<GroupManager>) {
+    let key = DataKey::GroupManagers(id.clone());
+    if managers.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, managers);
+        bump_persistent(env, &key);
     }
+}
 
-    // Determine old members for index updating
-    let old_members = details.members.clone();
+// ============================================================================
+// Governance
+// ============================================================================
 
-    // Update members in details
-    details.members = new_members.clone();
-    env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+/// Hands control of a group's members, name and activation to its members.
+/// Afterwards those changes can only be made through proposals. Creator only;
+/// governance cannot be turned off again.
+pub fn enable_governance(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    config: GovernanceConfig,
+) -> Result<(), Error> {
+    caller.require_auth();
 
-    // Update MemberGroups index for removed and added members
-    for old_member in old_members.iter() {
-        let mut found_in_new = false;
-        for new_member in new_members.iter() {
-            if old_member.address == new_member.address {
-                found_in_new = true;
-                break;
-            }
-        }
-        if !found_in_new {
-            // Member was removed, remove group from their index
-            let member_groups_key = DataKey::MemberGroups(old_member.address.clone());
-            let member_groups: Vec<BytesN<32>> = env
-                .storage()
-                .persistent()
-                .get(&member_groups_key)
-                .unwrap_or(Vec::new(&env));
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-            let mut updated_member_groups: Vec<BytesN<32>> = Vec::new(&env);
-            let mut group_removed = false;
-            for group_id in member_groups.iter() {
-                if group_id != id {
-                    updated_member_groups.push_back(group_id);
-                } else {
-                    group_removed = true;
-                }
-            }
-            if group_removed {
-                env.storage()
-                    .persistent()
-                    .set(&member_groups_key, &updated_member_groups);
-                bump_persistent(&env, &member_groups_key);
-            }
-        }
+    let details = get_autoshare(env.clone(), id.clone())?;
+    if details.creator != caller {
+        return Err(Error::Unauthorized);
     }
 
-    for new_member in new_members.iter() {
-        let mut found_in_old = false;
-        for old_member in old_members.iter() {
-            if new_member.address == old_member.address {
-                found_in_old = true;
-                break;
-            }
-        }
-        if !found_in_old {
-            // Member was added, add group to their index
-            let member_groups_key = DataKey::MemberGroups(new_member.address.clone());
-            let mut member_groups: Vec<BytesN<32>> = env
-                .storage()
-                .persistent()
-                .get(&member_groups_key)
-                .unwrap_or(Vec::new(&env));
+    if config.quorum == 0
+        || config.quorum > 100
+        || config.threshold == 0
+        || config.threshold > 100
+        || config.voting_period == 0
+        || config.execution_window == 0
+    {
+        return Err(Error::InvalidInput);
+    }
 
-            member_groups.push_back(id.clone());
-            env.storage()
-                .persistent()
-                .set(&member_groups_key, &member_groups);
-            bump_persistent(&env, &member_groups_key);
-        }
+    let key = DataKey::GroupGovernance(id.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(Error::AlreadyExists);
     }
+    env.storage().persistent().set(&key, &config);
+    bump_persistent(&env, &key);
 
     AutoshareUpdated {
-        id: id.clone(),
+        id,
         updater: caller,
     }
     .publish(&env);
     Ok(())
 }
 
-pub fn deactivate_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
-    caller.require_auth();
+pub fn get_governance(env: Env, id: BytesN<32>) -> Option<GovernanceConfig> {
+    let key = DataKey::GroupGovernance(id);
+    let config = env.storage().persistent().get(&key);
+    if config.is_some() {
+        bump_persistent(&env, &key);
+    }
+    config
+}
+
+/// Opens a vote on a change to a governed group. Members and the creator can propose.
+pub fn propose(
+    env: Env,
+    id: BytesN<32>,
+    proposer: Address,
+    action: ProposalAction,
+) -> Result<u32, Error> {
+    proposer.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), id.clone())?;
+    let config = get_governance(env.clone(), id.clone()).ok_or(Error::NotFound)?;
+
+    if details.creator != proposer && member_percentage(&details, &proposer).is_none() {
+        return Err(Error::Unauthorized);
+    }
+
+    let mut weights = Map::new(&env);
+    for member in details.members.iter() {
+        weights.set(member.address, member.percentage);
+    }
+
+    // Proposals that can no longer be voted on or executed are dropped
+    let now = env.ledger().timestamp();
+    let book = read_proposals(&env, &id);
+    let mut proposals: Vec<Proposal> = Vec::new(&env);
+    for proposal in book.proposals.iter() {
+        if is_proposal_open(&proposal, &config, now) {
+            proposals.push_back(proposal);
+        }
+    }
+    if proposals.len() >= MAX_OPEN_PROPOSALS {
+        return Err(Error::ProposalLimitReached);
+    }
+
+    let proposal_id = book.next_id;
+    proposals.push_back(Proposal {
+        id: proposal_id,
+        proposer: proposer.clone(),
+        action,
+        voting_ends: now + config.voting_period,
+        weights,
+        votes_for: 0,
+        votes_against: 0,
+        voters: Vec::new(&env),
+        executed: false,
+        superseded: false,
+    });
+    write_proposals(
+        &env,
+        &id,
+        &ProposalBook {
+            next_id: proposal_id + 1,
+            proposals,
+        },
+    );
+
+    ProposalCreated {
+        group_id: id,
+        proposer,
+        proposal_id,
+    }
+    .publish(&env);
+    Ok(proposal_id)
+}
+
+/// Votes on an open proposal, weighted by the voter's percentage when the
+/// proposal was created. Split changes made while the vote is open don't move it.
+pub fn vote(
+    env: Env,
+    id: BytesN<32>,
+    proposal_id: u32,
+    voter: Address,
+    support: bool,
+) -> Result<(), Error> {
+    voter.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    get_autoshare(env.clone(), id.clone())?;
 
-    let key = DataKey::AutoShare(id.clone());
-    let mut details: AutoShareDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
+    let mut book = read_proposals(&env, &id);
+    let (idx, mut proposal) = find_proposal(&book, proposal_id)?;
+    let weight = proposal
+        .weights
+        .get(voter.clone())
+        .ok_or(Error::MemberNotFound)?;
 
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
+    if proposal.executed || proposal.superseded || env.ledger().timestamp() >= proposal.voting_ends
+    {
+        return Err(Error::VotingClosed);
     }
-
-    if !details.is_active {
-        return Err(Error::GroupAlreadyInactive);
+    if proposal.voters.contains(&voter) {
+        return Err(Error::AlreadyExists);
     }
 
-    details.is_active = false;
-    env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+    if support {
+        proposal.votes_for += weight;
+    } else {
+        proposal.votes_against += weight;
+    }
+    proposal.voters.push_back(voter.clone());
+    book.proposals.set(idx, proposal);
+    write_proposals(&env, &id, &book);
 
-    GroupDeactivated {
-        id: id.clone(),
-        creator: caller,
+    VoteCast {
+        group_id: id,
+        voter,
+        proposal_id,
+        support,
+        weight,
     }
     .publish(&env);
     Ok(())
 }
 
-pub fn activate_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
-    caller.require_auth();
+/// Applies a proposal once its voting period has ended with quorum reached and
+/// the threshold met. Anyone can trigger execution.
+pub fn execute_proposal(env: Env, id: BytesN<32>, proposal_id: u32) -> Result<(), Error> {
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    let details = get_autoshare(env.clone(), id.clone())?;
+    let config = get_governance(env.clone(), id.clone()).ok_or(Error::NotFound)?;
 
-    let key = DataKey::AutoShare(id.clone());
-    let mut details: AutoShareDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
+    let mut book = read_proposals(&env, &id);
+    let (idx, mut proposal) = find_proposal(&book, proposal_id)?;
 
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
+    if proposal.executed || proposal.superseded {
+        return Err(Error::AlreadyProcessed);
     }
 
-    if details.is_active {
-        return Err(Error::GroupAlreadyActive);
+    // A passed proposal lapses once its execution window is over
+    let now = env.ledger().timestamp();
+    let cast = proposal.votes_for + proposal.votes_against;
+    if now < proposal.voting_ends
+        || now >= proposal.voting_ends + config.execution_window
+        || cast < config.quorum
+        || proposal.votes_for * 100 < config.threshold * cast
+    {
+        return Err(Error::ProposalNotPassed);
     }
 
-    details.is_active = true;
-    env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+    match proposal.action.clone() {
+        ProposalAction::UpdateMembers(members) => change_split(
+            env.clone(),
+            id.clone(),
+            details,
+            members,
+            proposal.proposer.clone(),
+        )?,
+        ProposalAction::UpdateName(name) => apply_name_update(
+            env.clone(),
+            id.clone(),
+            details,
+            name,
+            proposal.proposer.clone(),
+        )?,
+        ProposalAction::SetActive(active) => apply_activation(
+            env.clone(),
+            id.clone(),
+            details,
+            active,
+            proposal.proposer.clone(),
+        )?,
+    }
 
-    GroupActivated {
-        id: id.clone(),
-        creator: caller,
+    // Open proposals of the same kind were drafted against the state this one changed
+    let kind = core::mem::discriminant(&proposal.action);
+    for (other_idx, mut other) in book.proposals.iter().enumerate() {
+        if !other.executed && !other.superseded && core::mem::discriminant(&other.action) == kind {
+            other.superseded = true;
+            book.proposals.set(other_idx as u32, other);
+        }
+    }
+    proposal.executed = true;
+    book.proposals.set(idx, proposal);
+    write_proposals(&env, &id, &book);
+
+    ProposalExecuted {
+        group_id: id,
+        proposal_id,
     }
     .publish(&env);
     Ok(())
 }
 
-pub fn update_group_name(
-    env: Env,
-    id: BytesN<32>,
-    caller: Address,
-    new_name: String,
-) -> Result<(), Error> {
-    caller.require_auth();
-
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+pub fn get_proposals(env: Env, id: BytesN<32>) -> Vec<Proposal> {
+    read_proposals(&env, &id).proposals
+}
 
-    let key = DataKey::AutoShare(id.clone());
-    let mut details: AutoShareDetails = env
+/// Fails when a group's changes must go through proposals instead.
+fn require_creator_control(env: &Env, id: &BytesN<32>) -> Result<(), Error> {
+    if env
         .storage()
         .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
+        .has(&DataKey::GroupGovernance(id.clone()))
+    {
+        return Err(Error::GovernanceRequired);
     }
+    Ok(())
+}
 
-    if !details.is_active {
-        return Err(Error::GroupInactive);
-    }
+fn member_percentage(details: &AutoShareDetails, address: &Address) -> Option<u32> {
+    details
+        .members
+        .iter()
+        .find(|member| member.address == *address)
+        .map(|member| member.percentage)
+}
 
-    if !is_valid_name(&new_name) {
-        return Err(Error::EmptyName);
+/// Fails while `member` has voted on a proposal that is still open, since the
+/// vote is recorded against the address it was cast from.
+fn require_no_open_vote(env: &Env, id: &BytesN<32>, member: &Address) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    for proposal in read_proposals(env, id).proposals.iter() {
+        if !proposal.executed
+            && !proposal.superseded
+            && now < proposal.voting_ends
+            && proposal.voters.contains(member)
+        {
+            return Err(Error::VoteOpen);
+        }
     }
+    Ok(())
+}
 
-    details.name = new_name;
-    env.storage().persistent().set(&key, &details);
+/// Whether a proposal can still be voted on or executed.
+fn is_proposal_open(proposal: &Proposal, config: &GovernanceConfig, now: u64) -> bool {
+    !proposal.executed
+        && !proposal.superseded
+        && now < proposal.voting_ends + config.execution_window
+}
 
-    GroupNameUpdated {
-        id: id.clone(),
-        updater: caller,
+/// Returns the position of a proposal in the group's book, and the proposal.
+fn find_proposal(book: &ProposalBook, proposal_id: u32) -> Result<(u32, Proposal), Error> {
+    for (idx, proposal) in book.proposals.iter().enumerate() {
+        if proposal.id == proposal_id {
+            return Ok((idx as u32, proposal));
+        }
+    }
+    Err(Error::NotFound)
+}
+
+/// Moves `member`'s snapshotted weight on proposals still open for voting to
+/// `new_address`, so a member who changes address before voting keeps their vote.
+fn move_vote_weights(
+    env: &Env,
+    id: &BytesN<32>,
+    member: &Address,
+    new_address: &Address,
+) -> Result<(), Error> {
+    let now = env.ledger().timestamp();
+    let mut book = read_proposals(env, id);
+    let mut moved = false;
+    for (idx, mut proposal) in book.proposals.iter().enumerate() {
+        if proposal.executed || proposal.superseded || now >= proposal.voting_ends {
+            continue;
+        }
+        if let Some(weight) = proposal.weights.get(member.clone()) {
+            if proposal.weights.contains_key(new_address.clone()) {
+                return Err(Error::DuplicateMember);
+            }
+            proposal.weights.remove(member.clone());
+            proposal.weights.set(new_address.clone(), weight);
+            book.proposals.set(idx as u32, proposal);
+            moved = true;
+        }
+    }
+    if moved {
+        write_proposals(env, id, &book);
     }
-    .publish(&env);
     Ok(())
 }
 
-pub fn is_group_active(env: Env, id: BytesN<32>) -> Result<bool, Error> {
-    let key = DataKey::AutoShare(id);
-    let details: AutoShareDetails = env
-        .storage()
-        .persistent()
-        .get(&key)
-        .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
-    Ok(details.is_active)
+fn read_proposals(env: &Env, id: &BytesN<32>) -> ProposalBook {
+    let key = DataKey::GroupProposals(id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(book) => {
+            bump_persistent(env, &key);
+            book
+        }
+        None => ProposalBook {
+            next_id: 0,
+            proposals: Vec::new(env),
+        },
+    }
+}
+
+fn write_proposals(env: &Env, id: &BytesN<32>, book: &ProposalBook) {
+    let key = DataKey::GroupProposals(id.clone());
+    env.storage().persistent().set(&key, book);
+    bump_persistent(env, &key);
 }
 
 // ============================================================================
@@ -1230,9 +4064,7 @@ pub fn is_group_active(env: Env, id: BytesN<32>) -> Result<bool, Error> {
 pub fn delete_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Error> {
     caller.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
     // Step 1: Verify group exists
     let key = DataKey::AutoShare(id.clone());
@@ -1259,6 +4091,14 @@ pub fn delete_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Err
         return Err(Error::GroupNotDeactivated);
     }
 
+    // Deposited funds must be distributed before the group can go away
+    let tokens = get_supported_tokens(env.clone());
+    for token in tokens.iter() {
+        if get_group_balance(env.clone(), id.clone(), token) > 0 {
+            return Err(Error::GroupHasFunds);
+        }
+    }
+
     // Step 4: Check group has 0 remaining usages (or warn about forfeiture)
     // We allow deletion even with remaining usages, but this is a design choice
     // In production, you might want to enforce zero usages or handle refunds
@@ -1328,161 +4168,1111 @@ pub fn delete_group(env: Env, id: BytesN<32>, caller: Address) -> Result<(), Err
         }
     }
 
-    // Step 9: Emit deletion event
+    // Step 9: Clear per-group configuration so a recreated id starts clean
+    write_invitations(&env, &id, &Vec::new(&env));
+    for group_key in [
+        DataKey::PendingGroupOwner(id.clone()),
+        DataKey::GroupManagers(id.clone()),
+        DataKey::GroupSettings(id.clone()),
+        DataKey::GroupGovernance(id.clone()),
+        DataKey::GroupProposals(id.clone()),
+        DataKey::GroupFreeze(id.clone()),
+        DataKey::PendingSplitChange(id.clone()),
+    ] {
+        env.storage().persistent().remove(&group_key);
+    }
+
+    // Rounding dust carried forward is too small to split, so it goes to the creator
+    for token in tokens.iter() {
+        let dust = get_dust_credit(env.clone(), id.clone(), token.clone());
+        if dust > 0 {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::DustCredit(id.clone(), token.clone()));
+            adjust_reserved(&env, &token, -dust);
+            token::TokenClient::new(&env, &token).transfer(
+                &env.current_contract_address(),
+                &details.creator,
+                &dust,
+            );
+        }
+    }
+
+    // Step 10: Emit deletion event
     GroupDeleted {
         deleter: caller,
         id: id.clone(),
     }
     .publish(&env);
 
-    Ok(())
+    Ok(())
+}
+
+// ============================================================================
+// Contract Balance & Withdrawal
+// ============================================================================
+
+pub fn get_contract_balance(env: Env, token: Address) -> i128 {
+    let client = token::TokenClient::new(&env, &token);
+    client.balance(&env.current_contract_address())
+}
+
+/// Returns how much of the contract's `token` balance is owed to users: group
+/// deposits, sponsor pools, referral rewards, pending payouts and dust credit.
+pub fn get_reserved_balance(env: Env, token: Address) -> i128 {
+    let key = DataKey::ReservedBalance(token);
+    let reserved: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if reserved > 0 {
+        bump_persistent(&env, &key);
+    }
+    reserved
+}
+
+/// Adds `change` to the amount of `token` held for users. Negative changes release it.
+fn adjust_reserved(env: &Env, token: &Address, change: i128) {
+    if change == 0 {
+        return;
+    }
+    let key = DataKey::ReservedBalance(token.clone());
+    let reserved = (get_reserved_balance(env.clone(), token.clone()) + change).max(0);
+    if reserved > 0 {
+        env.storage().persistent().set(&key, &reserved);
+        bump_persistent(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+pub fn withdraw(
+    env: Env,
+    admin: Address,
+    token: Address,
+    amount: i128,
+    recipient: Address,
+) -> Result<(), Error> {
+    admin.require_auth();
+    require_admin(&env, &admin)?;
+
+    // The global pause leaves admin withdrawals available for incident recovery
+    if is_operation_paused(&env, &PauseCategory::Withdrawals) {
+        return Err(Error::ContractPaused);
+    }
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    // Only fees and other unowed funds can be withdrawn
+    let available = get_contract_balance(env.clone(), token.clone())
+        - get_reserved_balance(env.clone(), token.clone());
+    if available < amount {
+        return Err(Error::InsufficientContractBalance);
+    }
+
+    let client = token::TokenClient::new(&env, &token);
+    client.transfer(&env.current_contract_address(), &recipient, &amount);
+
+    Withdrawal {
+        token,
+        amount,
+        recipient,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+#[allow(clippy::needless_borrows_for_generic_args)]
+pub fn distribute(
+    env: Env,
+    id: BytesN<32>,
+    token: Address,
+    amount: i128,
+    sender: Address,
+    memo: Option<String>,
+    idempotency_key: Option<BytesN<32>>,
+) -> Result<(), Error> {
+    sender.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &sender)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    validate_memo(&memo)?;
+
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    if let Some(key) = &idempotency_key {
+        if is_idempotency_key_used(env.clone(), id.clone(), key.clone()) {
+            return Err(Error::AlreadyProcessed);
+        }
+    }
+
+    let details = prepare_distribution(&env, &id)?;
+
+    let client = token::TokenClient::new(&env, &token);
+    client.transfer(&sender, &env.current_contract_address(), &amount);
+    settle_distribution(&env, details, &token, amount, &sender, memo, None)?;
+
+    if let Some(key) = idempotency_key {
+        let storage_key = DataKey::IdempotencyKey(id, key);
+        env.storage()
+            .persistent()
+            .set(&storage_key, &env.ledger().timestamp());
+        bump_persistent(&env, &storage_key);
+    }
+
+    Ok(())
+}
+
+/// Distributes several tokens to the group in one call. Every token is split
+/// by the member percentages and the whole payout consumes a single usage.
+#[allow(clippy::needless_borrows_for_generic_args)]
+pub fn distribute_multi(
+    env: Env,
+    id: BytesN<32>,
+    amounts: Vec<TokenAmount>,
+    sender: Address,
+) -> Result<(), Error> {
+    sender.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &sender)?;
+
+    if amounts.is_empty() || amounts.len() > MAX_DISTRIBUTION_TOKENS {
+        return Err(Error::InvalidInput);
+    }
+
+    let mut seen: Vec<Address> = Vec::new(&env);
+    for entry in amounts.iter() {
+        if entry.amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !is_token_supported(env.clone(), entry.token.clone()) {
+            return Err(Error::UnsupportedToken);
+        }
+        if seen.contains(&entry.token) {
+            return Err(Error::InvalidInput);
+        }
+        seen.push_back(entry.token);
+    }
+
+    let details = prepare_distribution(&env, &id)?;
+
+    for entry in amounts.iter() {
+        let client = token::TokenClient::new(&env, &entry.token);
+        client.transfer(&sender, &env.current_contract_address(), &entry.amount);
+    }
+    settle_multi_distribution(&env, details, amounts, &sender, None, None, Vec::new(&env))?;
+
+    Ok(())
+}
+
+/// Swaps the payment into `token_out` through the configured router and splits
+/// what was received. Fails with `SlippageExceeded` if the contract receives less
+/// than `min_amount_out`.
+pub fn distribute_with_swap(
+    env: Env,
+    id: BytesN<32>,
+    token_in: Address,
+    amount_in: i128,
+    token_out: Address,
+    min_amount_out: i128,
+    sender: Address,
+) -> Result<i128, Error> {
+    sender.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &sender)?;
+
+    if amount_in <= 0 || min_amount_out <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    if token_in == token_out {
+        return Err(Error::InvalidInput);
+    }
+
+    if !is_token_supported(env.clone(), token_out.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    let router = get_swap_router(env.clone()).ok_or(Error::SwapRouterNotSet)?;
+    let details = prepare_distribution(&env, &id)?;
+
+    token::TokenClient::new(&env, &token_in).transfer(
+        &sender,
+        env.current_contract_address(),
+        &amount_in,
+    );
+    let amount_out = swap_exact_in(
+        &env,
+        &router,
+        &token_in,
+        amount_in,
+        &token_out,
+        min_amount_out,
+    )?;
+
+    let mut token_amounts: Vec<TokenAmount> = Vec::new(&env);
+    token_amounts.push_back(TokenAmount {
+        token: token_out.clone(),
+        amount: amount_out,
+    });
+    let mut swaps: Vec<SwapReceipt> = Vec::new(&env);
+    swaps.push_back(SwapReceipt {
+        router,
+        token_in,
+        amount_in,
+        token_out,
+        amount_out,
+    });
+    settle_multi_distribution(&env, details, token_amounts, &sender, None, None, swaps)?;
+
+    Ok(amount_out)
+}
+
+/// Swaps `amount_in` held by the contract into `token_out`. The router pulls the
+/// input through a single-use allowance, so a failed swap moves nothing and
+/// returns `SwapFailed`. The received amount is measured from the contract's
+/// balance rather than trusted from the router.
+fn swap_exact_in(
+    env: &Env,
+    router: &Address,
+    token_in: &Address,
+    amount_in: i128,
+    token_out: &Address,
+    min_amount_out: i128,
+) -> Result<i128, Error> {
+    let contract = env.current_contract_address();
+    let in_client = token::TokenClient::new(env, token_in);
+    let out_client = token::TokenClient::new(env, token_out);
+    let balance_before = out_client.balance(&contract);
+
+    let expiration_ledger = env.ledger().sequence();
+    in_client.approve(&contract, router, &amount_in, &expiration_ledger);
+    let swapped = SwapRouterClient::new(env, router).try_swap_exact_in(
+        token_in,
+        token_out,
+        &amount_in,
+        &min_amount_out,
+        &contract,
+    );
+    if !matches!(swapped, Ok(Ok(_))) {
+        in_client.approve(&contract, router, &0, &expiration_ledger);
+        return Err(Error::SwapFailed);
+    }
+
+    let amount_out = out_client.balance(&contract) - balance_before;
+    if amount_out < min_amount_out {
+        return Err(Error::SlippageExceeded);
+    }
+    Ok(amount_out)
 }
 
-// ============================================================================
-// Contract Balance & Withdrawal
-// ============================================================================
-
-pub fn get_contract_balance(env: Env, token: Address) -> i128 {
-    let client = token::TokenClient::new(&env, &token);
-    client.balance(&env.current_contract_address())
+/// Returns true if the key was consumed by a distribution of this group within
+/// the retention window. Older keys may be reused.
+pub fn is_idempotency_key_used(env: Env, id: BytesN<32>, key: BytesN<32>) -> bool {
+    let storage_key = DataKey::IdempotencyKey(id, key);
+    let used_at: Option<u64> = env.storage().persistent().get(&storage_key);
+    match used_at {
+        Some(used_at) => env.ledger().timestamp() < used_at + IDEMPOTENCY_RETENTION,
+        None => false,
+    }
 }
 
-pub fn withdraw(
+/// Credits `amount` of `token` to the group's balance so it can be split later
+/// with `distribute_balance`.
+#[allow(clippy::needless_borrows_for_generic_args)]
+pub fn deposit(
     env: Env,
-    admin: Address,
+    id: BytesN<32>,
     token: Address,
     amount: i128,
-    recipient: Address,
+    depositor: Address,
 ) -> Result<(), Error> {
-    admin.require_auth();
-    require_admin(&env, &admin)?;
+    depositor.require_auth();
+
+    require_operation_active(&env, PauseCategory::Contributions)?;
+
+    require_not_denylisted(&env, &depositor)?;
 
     if amount <= 0 {
         return Err(Error::InvalidAmount);
     }
 
-    let contract_balance = get_contract_balance(env.clone(), token.clone());
-    if contract_balance < amount {
-        return Err(Error::InsufficientContractBalance);
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    let group_key = DataKey::AutoShare(id.clone());
+    if !env.storage().persistent().has(&group_key) {
+        return Err(Error::NotFound);
     }
+    bump_persistent(&env, &group_key);
 
     let client = token::TokenClient::new(&env, &token);
-    client.transfer(&env.current_contract_address(), &recipient, &amount);
+    client.transfer(&depositor, &env.current_contract_address(), &amount);
 
-    Withdrawal {
+    let balance_key = DataKey::GroupBalance(id.clone(), token.clone());
+    let balance: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
+    env.storage()
+        .persistent()
+        .set(&balance_key, &(balance + amount));
+    bump_persistent(&env, &balance_key);
+    adjust_reserved(&env, &token, amount);
+
+    GroupDeposit {
+        id,
         token,
+        depositor,
         amount,
-        recipient,
     }
     .publish(&env);
     Ok(())
 }
 
-#[allow(clippy::needless_borrows_for_generic_args)]
-pub fn distribute(
+/// Splits the group's entire deposited balance of `token` among its members.
+/// Anyone may trigger settlement; it consumes one usage like `distribute`.
+pub fn distribute_balance(
     env: Env,
     id: BytesN<32>,
     token: Address,
-    amount: i128,
-    sender: Address,
-) -> Result<(), Error> {
-    sender.require_auth();
+    caller: Address,
+) -> Result<i128, Error> {
+    caller.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::Distributions)?;
 
+    let balance_key = DataKey::GroupBalance(id.clone(), token.clone());
+    let amount: i128 = env.storage().persistent().get(&balance_key).unwrap_or(0);
     if amount <= 0 {
-        return Err(Error::InvalidAmount);
+        return Err(Error::InsufficientBalance);
     }
 
-    if !is_token_supported(env.clone(), token.clone()) {
-        return Err(Error::UnsupportedToken);
+    let details = prepare_distribution(&env, &id)?;
+
+    env.storage().persistent().remove(&balance_key);
+    adjust_reserved(&env, &token, -amount);
+    settle_distribution(&env, details, &token, amount, &caller, None, None)?;
+
+    Ok(amount)
+}
+
+pub fn get_group_balance(env: Env, id: BytesN<32>, token: Address) -> i128 {
+    let key = DataKey::GroupBalance(id, token);
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    if balance > 0 {
+        bump_persistent(&env, &key);
     }
+    balance
+}
 
+fn load_distributable_group(env: &Env, id: &BytesN<32>) -> Result<AutoShareDetails, Error> {
     let key = DataKey::AutoShare(id.clone());
-    let mut details: AutoShareDetails = env
+    let details: AutoShareDetails = env
         .storage()
         .persistent()
         .get(&key)
         .ok_or(Error::NotFound)?;
-    bump_persistent(&env, &key);
+    bump_persistent(env, &key);
 
     if !details.is_active {
         return Err(Error::GroupInactive);
     }
 
+    require_not_frozen(env, id)?;
+    require_complete_split(env, &details)?;
+
+    Ok(details)
+}
+
+/// Checks that the members' percentages form a valid split of 100%, which
+/// unanswered invitations or a member leaving can leave short.
+fn require_complete_split(env: &Env, details: &AutoShareDetails) -> Result<(), Error> {
+    if let Err(err) = validate_members(&details.members) {
+        if !read_invitations(env, &details.id).is_empty() {
+            return Err(Error::InvitationsPending);
+        }
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Loads a group and checks that it can take a distribution, buying a
+/// sponsored usage if it has run out. A due split change is applied first; one
+/// that can no longer apply stays queued and the current split is used.
+fn prepare_distribution(env: &Env, id: &BytesN<32>) -> Result<AutoShareDetails, Error> {
+    let _ = apply_due_split_change(env, id);
+    let mut details = load_distributable_group(env, id)?;
+
     if details.usage_count == 0 {
-        return Err(Error::NoUsagesRemaining);
+        sponsor_usage(env, &mut details)?;
     }
 
-    validate_members(&details.members)?;
+    Ok(details)
+}
+
+/// Splits `amount`, already held by the contract, among the group members,
+/// records the distribution and consumes one usage.
+fn settle_distribution(
+    env: &Env,
+    details: AutoShareDetails,
+    token: &Address,
+    amount: i128,
+    sender: &Address,
+    memo: Option<String>,
+    invoice_id: Option<BytesN<32>>,
+) -> Result<Vec<MemberAmount>, Error> {
+    let mut token_amounts: Vec<TokenAmount> = Vec::new(env);
+    token_amounts.push_back(TokenAmount {
+        token: token.clone(),
+        amount,
+    });
+    settle_multi_distribution(
+        env,
+        details,
+        token_amounts,
+        sender,
+        memo,
+        invoice_id,
+        Vec::new(env),
+    )
+}
+
+/// Splits each token amount among the group members as one distribution that
+/// consumes a single usage.
+fn settle_multi_distribution(
+    env: &Env,
+    mut details: AutoShareDetails,
+    token_amounts: Vec<TokenAmount>,
+    sender: &Address,
+    memo: Option<String>,
+    invoice_id: Option<BytesN<32>>,
+    mut swaps: Vec<SwapReceipt>,
+) -> Result<Vec<MemberAmount>, Error> {
+    let id = details.id.clone();
+    let mut member_amounts: Vec<MemberAmount> = Vec::new(env);
+    for entry in token_amounts.iter() {
+        let (token_member_amounts, conversions) =
+            perform_distribution(env, &details, &entry.token, entry.amount)?;
+        swaps.append(&conversions);
+        // Emit new distribution event for fund flow tracking
+        emit_distribution(
+            env,
+            &id,
+            sender,
+            &entry.token,
+            entry.amount,
+            token_member_amounts.len(),
+            &memo,
+        );
+        member_amounts.append(&token_member_amounts);
+    }
 
-    let client = token::TokenClient::new(&env, &token);
-    client.transfer(&sender, &env.current_contract_address(), &amount);
-    let member_amounts = perform_distribution(&env, &id, &token, amount, &details.members);
     let distribution_number = details.total_usages_paid - details.usage_count;
     record_distribution(
         env.clone(),
         id.clone(),
         sender.clone(),
-        amount,
-        token.clone(),
+        token_amounts,
         member_amounts.clone(),
         distribution_number,
-    );
-    // Emit new distribution event for fund flow tracking
-    emit_distribution(
-        &env,
-        &id,
-        &sender,
-        &token,
-        amount,
-        member_amounts.len() as u32,
+        memo,
+        invoice_id,
+        swaps,
     );
 
     details.usage_count -= 1;
+    let key = DataKey::AutoShare(id);
     env.storage().persistent().set(&key, &details);
-    bump_persistent(&env, &key);
+    bump_persistent(env, &key);
+
+    Ok(member_amounts)
+}
+
+/// Runs several distributions in one call, pulling the total for each token from
+/// the sender once. Each item consumes one usage of its group.
+/// When `atomic` is true any failing item aborts the whole batch; otherwise items
+/// that fail their checks are skipped, their funds returned to the sender, and the
+/// error reported. A payout failing partway through an item, such as a swap
+/// returning too little, always aborts the batch.
+pub fn distribute_batch(
+    env: Env,
+    items: Vec<BatchDistribution>,
+    sender: Address,
+    atomic: bool,
+) -> Result<Vec<BatchDistributionResult>, Error> {
+    sender.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &sender)?;
+
+    if items.is_empty() || items.len() > MAX_BATCH_SIZE {
+        return Err(Error::InvalidInput);
+    }
+
+    // Stateless checks first so that invalid items are never pulled
+    let mut checks: Vec<u32> = Vec::new(&env);
+    let mut totals: Vec<TokenAmount> = Vec::new(&env);
+    for item in items.iter() {
+        let check = if item.amount <= 0 {
+            Err(Error::InvalidAmount)
+        } else if !is_token_supported(env.clone(), item.token.clone()) {
+            Err(Error::UnsupportedToken)
+        } else {
+            Ok(())
+        };
+        match check {
+            Ok(()) => {
+                add_token_amount(&mut totals, &item.token, item.amount);
+                checks.push_back(0);
+            }
+            Err(error) if atomic => return Err(error),
+            Err(error) => checks.push_back(error as u32),
+        }
+    }
+
+    for total in totals.iter() {
+        let client = token::TokenClient::new(&env, &total.token);
+        client.transfer(&sender, env.current_contract_address(), &total.amount);
+    }
+
+    let mut results: Vec<BatchDistributionResult> = Vec::new(&env);
+    let mut refunds: Vec<TokenAmount> = Vec::new(&env);
+    for (idx, item) in items.iter().enumerate() {
+        let mut error_code = checks.get(idx as u32).unwrap();
+        // Outside atomic mode each item is checked read-only first and skipped,
+        // with its funds returned, if it cannot be paid
+        if error_code == 0 && !atomic {
+            if let Err(error) = preview_distribution(
+                env.clone(),
+                item.group_id.clone(),
+                item.token.clone(),
+                item.amount,
+            ) {
+                add_token_amount(&mut refunds, &item.token, item.amount);
+                error_code = error as u32;
+            }
+        }
+        // Once an item starts paying out its writes cannot be undone on their
+        // own, so a failure from here on aborts the whole batch
+        if error_code == 0 {
+            let details = prepare_distribution(&env, &item.group_id)?;
+            settle_distribution(&env, details, &item.token, item.amount, &sender, None, None)?;
+        }
+        results.push_back(BatchDistributionResult {
+            group_id: item.group_id,
+            success: error_code == 0,
+            error_code,
+        });
+    }
+
+    for refund in refunds.iter() {
+        let client = token::TokenClient::new(&env, &refund.token);
+        client.transfer(&env.current_contract_address(), &sender, &refund.amount);
+    }
+
+    Ok(results)
+}
+
+/// Computes each member's share of `amount` under the group's rounding policy.
+/// Every share is floored first; the policy decides who receives the dust.
+/// Returns the payouts and the dust left to carry forward, which together
+/// always add up to `amount`. Recipients whose share is zero are left out.
+/// Fails unless the members' percentages add up to exactly 100.
+fn compute_shares(
+    env: &Env,
+    details: &AutoShareDetails,
+    token: &Address,
+    amount: i128,
+    policy: &RoundingPolicy,
+) -> Result<(Vec<MemberAmount>, i128), Error> {
+    let members = &details.members;
+    let total_percentage: u32 = members.iter().map(|m| m.percentage).sum();
+    if total_percentage != 100 {
+        return Err(Error::InvalidTotalPercentage);
+    }
+    let mut shares: Vec<i128> = Vec::new(env);
+    let mut distributed: i128 = 0;
+    for member in members.iter() {
+        let share = (amount * (member.percentage as i128)) / 100;
+        shares.push_back(share);
+        distributed += share;
+    }
+    let mut dust = amount - distributed;
+
+    let mut dust_recipient: Option<Address> = None;
+    if dust > 0 && !members.is_empty() {
+        match policy {
+            RoundingPolicy::LastMember => {
+                let last = members.len() - 1;
+                shares.set(last, shares.get(last).unwrap() + dust);
+                dust = 0;
+            }
+            RoundingPolicy::LargestShare => {
+                let mut largest: u32 = 0;
+                for (idx, member) in members.iter().enumerate() {
+                    if member.percentage > members.get(largest).unwrap().percentage {
+                        largest = idx as u32;
+                    }
+                }
+                shares.set(largest, shares.get(largest).unwrap() + dust);
+                dust = 0;
+            }
+            RoundingPolicy::LargestRemainder => {
+                // Dust is always smaller than the member count, so each member
+                // receives at most one extra unit.
+                let mut picked: Vec<bool> = Vec::new(env);
+                for _ in members.iter() {
+                    picked.push_back(false);
+                }
+                while dust > 0 {
+                    let mut best: Option<(u32, i128)> = None;
+                    for (idx, member) in members.iter().enumerate() {
+                        let idx = idx as u32;
+                        if picked.get(idx).unwrap() {
+                            continue;
+                        }
+                        let remainder = (amount * (member.percentage as i128)) % 100;
+                        if best.is_none_or(|(_, best_remainder)| remainder > best_remainder) {
+                            best = Some((idx, remainder));
+                        }
+                    }
+                    let (idx, _) = best.ok_or(Error::InvalidTotalPercentage)?;
+                    picked.set(idx, true);
+                    shares.set(idx, shares.get(idx).unwrap() + 1);
+                    dust -= 1;
+                }
+            }
+            RoundingPolicy::Creator => dust_recipient = Some(details.creator.clone()),
+            RoundingPolicy::DustRecipient(recipient) => dust_recipient = Some(recipient.clone()),
+            RoundingPolicy::CarryForward => {}
+        }
+    }
+
+    let mut member_amounts: Vec<MemberAmount> = Vec::new(env);
+    for (idx, member) in members.iter().enumerate() {
+        let mut share = shares.get(idx as u32).unwrap();
+        if dust_recipient.as_ref() == Some(&member.address) {
+            share += dust;
+            dust = 0;
+        }
+        if share > 0 {
+            member_amounts.push_back(MemberAmount {
+                address: member.address.clone(),
+                token: token.clone(),
+                amount: share,
+            });
+        }
+    }
+    if let Some(recipient) = dust_recipient {
+        if dust > 0 {
+            member_amounts.push_back(MemberAmount {
+                address: recipient,
+                token: token.clone(),
+                amount: dust,
+            });
+            dust = 0;
+        }
+    }
+
+    Ok((member_amounts, dust))
+}
+
+/// Splits `amount` plus any dust carried forward for the token, pays the members
+/// and stores the new dust if the group carries it forward. Returns the shares and
+/// the swaps made to convert payouts into members' preferred tokens.
+fn perform_distribution(
+    env: &Env,
+    details: &AutoShareDetails,
+    token: &Address,
+    amount: i128,
+) -> Result<(Vec<MemberAmount>, Vec<SwapReceipt>), Error> {
+    let id = &details.id;
+    let settings = get_group_settings(env.clone(), id.clone());
+    let policy = settings.rounding_policy;
+    let credit_key = DataKey::DustCredit(id.clone(), token.clone());
+    let credit = get_dust_credit(env.clone(), id.clone(), token.clone());
+    let (member_amounts, dust) =
+        compute_payable_shares(env, details, token, amount + credit, &policy)?;
+
+    let mut conversions: Vec<SwapReceipt> = Vec::new(env);
+    // Change in what the contract holds for members once this distribution settles
+    let mut held = dust - credit;
+    for member_amount in member_amounts.iter() {
+        // Shares below the minimum payout are held until the member's pending
+        // balance reaches it
+        let pending_key =
+            DataKey::PendingPayout(id.clone(), member_amount.address.clone(), token.clone());
+        let pending: i128 = env.storage().persistent().get(&pending_key).unwrap_or(0);
+        let payout = pending + member_amount.amount;
+        if is_recipient_denylisted(env, details, &member_amount.address) {
+            let escrow_key =
+                DataKey::EscrowedPayout(id.clone(), member_amount.address.clone(), token.clone());
+            let escrowed: i128 = env.storage().persistent().get(&escrow_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&escrow_key, &(escrowed + member_amount.amount));
+            bump_persistent(env, &escrow_key);
+            held += member_amount.amount;
+            BlockedShareEscrowed {
+                group_id: id.clone(),
+                member: member_amount.address.clone(),
+                token: token.clone(),
+                amount: member_amount.amount,
+            }
+            .publish(env);
+        } else if payout >= settings.min_payout {
+            pay_member(
+                env,
+                details,
+                token,
+                &member_amount.address,
+                payout,
+                &mut conversions,
+            )?;
+            if pending > 0 {
+                env.storage().persistent().remove(&pending_key);
+                held -= pending;
+            }
+        } else {
+            hold_pending_payout(env, &pending_key, id, token, payout);
+            held += member_amount.amount;
+        }
+
+        // Update running total for member group earnings
+        let earnings_key = DataKey::MemberGroupEarnings(member_amount.address.clone(), id.clone());
+        let current_earnings: i128 = env.storage().persistent().get(&earnings_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&earnings_key, &(current_earnings + member_amount.amount));
+        bump_persistent(env, &earnings_key);
+    }
+
+    if dust > 0 {
+        env.storage().persistent().set(&credit_key, &dust);
+        bump_persistent(env, &credit_key);
+    } else if credit > 0 {
+        env.storage().persistent().remove(&credit_key);
+    }
+    adjust_reserved(env, token, held);
+    Ok((member_amounts, conversions))
+}
+
+/// Stores a member's pending balance and records `token` among the tokens the
+/// group holds pending payouts in.
+fn hold_pending_payout(
+    env: &Env,
+    pending_key: &DataKey,
+    id: &BytesN<32>,
+    token: &Address,
+    amount: i128,
+) {
+    env.storage().persistent().set(pending_key, &amount);
+    bump_persistent(env, pending_key);
+
+    let tokens_key = DataKey::GroupPendingTokens(id.clone());
+    let mut tokens = read_pending_tokens(env, id);
+    if !tokens.contains(token) {
+        tokens.push_back(token.clone());
+        env.storage().persistent().set(&tokens_key, &tokens);
+        bump_persistent(env, &tokens_key);
+    }
+}
+
+fn read_pending_tokens(env: &Env, id: &BytesN<32>) -> Vec<Address> {
+    let key = DataKey::GroupPendingTokens(id.clone());
+    match env.storage().persistent().get(&key) {
+        Some(tokens) => {
+            bump_persistent(env, &key);
+            tokens
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Computes the shares under the blocked share policy. Rejects the distribution
+/// if a denylisted recipient would be paid, or leaves their shares in the result
+/// to be escrowed. Under redistribution only a blocked dust recipient remains.
+fn compute_payable_shares(
+    env: &Env,
+    details: &AutoShareDetails,
+    token: &Address,
+    amount: i128,
+    policy: &RoundingPolicy,
+) -> Result<(Vec<MemberAmount>, i128), Error> {
+    let blocked_policy = get_blocked_share_policy(env);
+    if blocked_policy == BlockedSharePolicy::Redistribute {
+        let mut eligible = details.clone();
+        eligible.members = without_denylisted_members(env, details)?;
+        return compute_shares(env, &eligible, token, amount, policy);
+    }
+
+    let shares = compute_shares(env, details, token, amount, policy)?;
+    if blocked_policy == BlockedSharePolicy::Reject
+        && shares
+            .0
+            .iter()
+            .any(|share| is_recipient_denylisted(env, details, &share.address))
+    {
+        return Err(Error::AddressDenylisted);
+    }
+    Ok(shares)
+}
 
+/// Returns the members who may be paid, with the denylisted members' percentages
+/// split among them.
+fn without_denylisted_members(
+    env: &Env,
+    details: &AutoShareDetails,
+) -> Result<Vec<GroupMember>, Error> {
+    let mut eligible: Vec<GroupMember> = Vec::new(env);
+    for member in details.members.iter() {
+        if !is_recipient_denylisted(env, details, &member.address) {
+            eligible.push_back(member);
+        }
+    }
+
+    if eligible.is_empty() {
+        return Err(Error::AddressDenylisted);
+    }
+    if eligible.len() < details.members.len() {
+        redistribute_percentages(&mut eligible, 100);
+    }
+    Ok(eligible)
+}
+
+/// Sends a payout to the recipient's payout address, converted into their preferred
+/// token through the swap router when one is configured. Otherwise, or when the
+/// preferred token is no longer supported, the payout is made in `token`.
+fn pay_member(
+    env: &Env,
+    details: &AutoShareDetails,
+    token: &Address,
+    recipient: &Address,
+    amount: i128,
+    conversions: &mut Vec<SwapReceipt>,
+) -> Result<(), Error> {
+    let contract = env.current_contract_address();
+    let (payout_address, preferred_token) = payout_target(details, recipient);
+
+    if let Some(preferred) = preferred_token {
+        if let Some(router) = conversion_router(env, token, &preferred) {
+            if let Some(amount_out) = convert_payout(env, &router, token, amount, &preferred)? {
+                token::TokenClient::new(env, &preferred).transfer(
+                    &contract,
+                    &payout_address,
+                    &amount_out,
+                );
+                conversions.push_back(SwapReceipt {
+                    router,
+                    token_in: token.clone(),
+                    amount_in: amount,
+                    token_out: preferred,
+                    amount_out,
+                });
+                return Ok(());
+            }
+        }
+    }
+
+    token::TokenClient::new(env, token).transfer(&contract, &payout_address, &amount);
     Ok(())
 }
 
-fn perform_distribution(
-    env: &Env,
-    id: &BytesN<32>,
-    token: &Address,
-    amount: i128,
-    members: &Vec<GroupMember>,
-) -> Vec<MemberAmount> {
-    let client = token::TokenClient::new(env, token);
-    let mut distributed: i128 = 0;
-    let members_len = members.len() as usize;
-    let mut member_amounts: Vec<MemberAmount> = Vec::new(env);
-    for (idx, member) in members.iter().enumerate() {
-        let share = if idx + 1 < members_len {
-            (amount * (member.percentage as i128)) / 100
-        } else {
-            amount - distributed
-        };
-        if share > 0 {
-            client.transfer(&env.current_contract_address(), &member.address, &share);
-            distributed += share;
-            member_amounts.push_back(MemberAmount {
-                address: member.address.clone(),
-                amount: share,
+/// Swaps a payout into the member's preferred token, accepting at most
+/// `MAX_PAYOUT_SLIPPAGE_BPS` below the oracle quote. Returns `None`, leaving the
+/// payout in `token`, when the pair cannot be priced or the router cannot fill the
+/// swap. Fails only if the router returns less than the minimum it was given.
+fn convert_payout(
+    env: &Env,
+    router: &Address,
+    token: &Address,
+    amount: i128,
+    preferred: &Address,
+) -> Result<Option<i128>, Error> {
+    let min_amount_out = match payout_quote(env, token, amount, preferred) {
+        Some((_, min_amount_out)) => min_amount_out,
+        None => return Ok(None),
+    };
+
+    match swap_exact_in(env, router, token, amount, preferred, min_amount_out) {
+        Ok(amount_out) => Ok(Some(amount_out)),
+        Err(Error::SwapFailed) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns where a recipient is paid and the token they prefer, if any.
+fn payout_target(details: &AutoShareDetails, recipient: &Address) -> (Address, Option<Address>) {
+    match details.members.iter().find(|m| m.address == *recipient) {
+        Some(member) => (
+            member.payout_address.unwrap_or(member.address),
+            member.preferred_token,
+        ),
+        None => (recipient.clone(), None),
+    }
+}
+
+/// Returns the router a payout in `token` would be converted through, when the
+/// preferred token differs, is still supported and a router is configured.
+fn conversion_router(env: &Env, token: &Address, preferred: &Address) -> Option<Address> {
+    if preferred == token || !is_token_supported(env.clone(), preferred.clone()) {
+        return None;
+    }
+    get_swap_router(env.clone())
+}
+
+/// Returns the oracle's expected output for a payout conversion and the least
+/// the swap may return, or `None` when the pair cannot be priced.
+fn payout_quote(
+    env: &Env,
+    token: &Address,
+    amount: i128,
+    preferred: &Address,
+) -> Option<(i128, i128)> {
+    let expected = oracle_quote(env, token, amount, preferred).ok()?;
+    let min_amount_out = expected * (10_000 - MAX_PAYOUT_SLIPPAGE_BPS) / 10_000;
+    if min_amount_out <= 0 {
+        return None;
+    }
+    Some((expected, min_amount_out))
+}
+
+/// The transfer `pay_member` would make, with a conversion estimated at the
+/// oracle's quote.
+fn preview_payout(
+    env: &Env,
+    details: &AutoShareDetails,
+    token: &Address,
+    recipient: &Address,
+    amount: i128,
+) -> MemberAmount {
+    let (payout_address, preferred_token) = payout_target(details, recipient);
+    if let Some(preferred) = preferred_token {
+        if conversion_router(env, token, &preferred).is_some() {
+            if let Some((expected, _)) = payout_quote(env, token, amount, &preferred) {
+                return MemberAmount {
+                    address: payout_address,
+                    token: preferred,
+                    amount: expected,
+                };
+            }
+        }
+    }
+    MemberAmount {
+        address: payout_address,
+        token: token.clone(),
+        amount,
+    }
+}
+
+/// Simulates `distribute` without moving funds or writing state. Fails with the
+/// same error a real distribution would.
+pub fn preview_distribution(
+    env: Env,
+    id: BytesN<32>,
+    token: Address,
+    amount: i128,
+) -> Result<DistributionPreview, Error> {
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    let details = group_with_due_split_change(&env, &id)?;
+
+    let mut sponsored_usage = false;
+    let mut usage_fee: i128 = 0;
+    if details.usage_count == 0 {
+        let price = get_usage_quote(env.clone(), 1).unit_price;
+        select_sponsor_pool(&env, &details, price).ok_or(Error::NoUsagesRemaining)?;
+        sponsored_usage = true;
+        usage_fee = price;
+    }
+    let remaining_usages_after = if sponsored_usage {
+        0
+    } else {
+        details.usage_count - 1
+    };
+
+    let settings = get_group_settings(env.clone(), id.clone());
+    let credit = get_dust_credit(env.clone(), id.clone(), token.clone());
+    let (member_amounts, carried_dust) = compute_payable_shares(
+        &env,
+        &details,
+        &token,
+        amount + credit,
+        &settings.rounding_policy,
+    )?;
+
+    // Mirrors perform_distribution: a blocked recipient's share joins their escrow,
+    // others join any pending balance, which is paid once it reaches the minimum
+    let mut payouts: Vec<MemberAmount> = Vec::new(&env);
+    let mut held_amounts: Vec<MemberAmount> = Vec::new(&env);
+    for share in member_amounts.iter() {
+        let pending = get_pending_payout(
+            env.clone(),
+            id.clone(),
+            share.address.clone(),
+            token.clone(),
+        );
+        let payout = pending + share.amount;
+        if is_recipient_denylisted(&env, &details, &share.address) {
+            let escrowed = get_escrowed_payout(
+                env.clone(),
+                id.clone(),
+                share.address.clone(),
+                token.clone(),
+            );
+            held_amounts.push_back(MemberAmount {
+                address: share.address,
+                token: token.clone(),
+                amount: escrowed + share.amount,
             });
-
-            // Update running total for member group earnings
-            let earnings_key = DataKey::MemberGroupEarnings(member.address.clone(), id.clone());
-            let current_earnings: i128 = env.storage().persistent().get(&earnings_key).unwrap_or(0);
-            env.storage()
-                .persistent()
-                .set(&earnings_key, &(current_earnings + share));
-            bump_persistent(env, &earnings_key);
+        } else if payout < settings.min_payout {
+            held_amounts.push_back(MemberAmount {
+                address: share.address,
+                token: token.clone(),
+                amount: payout,
+            });
+        } else {
+            payouts.push_back(preview_payout(
+                &env,
+                &details,
+                &token,
+                &share.address,
+                payout,
+            ));
         }
     }
-    member_amounts
+
+    Ok(DistributionPreview {
+        group_id: id,
+        token: token.clone(),
+        amount,
+        member_amounts,
+        payouts,
+        held_amounts,
+        sponsored_usage,
+        usage_fee,
+        remaining_usages_after,
+        carried_dust,
+    })
 }
 
 pub fn get_member_earnings(env: Env, member: Address, group_id: BytesN<32>) -> i128 {
@@ -1505,6 +5295,8 @@ pub fn get_fundraising_status(env: Env, id: BytesN<32>) -> FundraisingConfig {
             target_amount: 0,
             total_raised: 0,
             is_active: false,
+            target_value: 0,
+            total_raised_value: 0,
         }
     }
 }
@@ -1559,13 +5351,35 @@ pub fn start_fundraising(
     id: BytesN<32>,
     caller: Address,
     target_amount: i128,
+) -> Result<(), Error> {
+    open_fundraising(env, id, caller, target_amount, 0)
+}
+
+/// Starts a campaign whose target is set in the price oracle's reference currency.
+/// Each contribution is valued at the oracle's last price when it is made.
+pub fn start_valued_fundraising(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    target_value: i128,
+) -> Result<(), Error> {
+    if get_price_oracle(env.clone()).is_none() {
+        return Err(Error::OracleNotSet);
+    }
+    open_fundraising(env, id, caller, 0, target_value)
+}
+
+fn open_fundraising(
+    env: Env,
+    id: BytesN<32>,
+    caller: Address,
+    target_amount: i128,
+    target_value: i128,
 ) -> Result<(), Error> {
     caller.require_auth();
 
     // Check if contract is paused
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
 
     // Verify group exists
     let key = DataKey::AutoShare(id.clone());
@@ -1576,10 +5390,13 @@ pub fn start_fundraising(
         .ok_or(Error::NotFound)?;
     bump_persistent(&env, &key);
 
-    // Verify caller is the group creator
-    if details.creator != caller {
-        return Err(Error::Unauthorized);
-    }
+    // Verify caller is the group creator or a fundraising manager
+    require_group_permission(
+        &env,
+        &details,
+        &caller,
+        ManagerPermission::ManageFundraising,
+    )?;
 
     // Verify group is active
     if !details.is_active {
@@ -1598,8 +5415,8 @@ pub fn start_fundraising(
         bump_persistent(&env, &fundraising_key);
     }
 
-    // Validate target_amount > 0
-    if target_amount <= 0 {
+    // Validate the target > 0
+    if target_amount.max(target_value) <= 0 {
         return Err(Error::InvalidAmount);
     }
 
@@ -1608,6 +5425,8 @@ pub fn start_fundraising(
         target_amount,
         total_raised: 0,
         is_active: true,
+        target_value,
+        total_raised_value: 0,
     };
 
     env.storage()
@@ -1631,21 +5450,27 @@ pub fn contribute(
     token: Address,
     amount: i128,
     contributor: Address,
+    memo: Option<String>,
 ) -> Result<(), Error> {
     contributor.require_auth();
 
-    if get_paused_status(&env) {
-        return Err(Error::ContractPaused);
-    }
+    require_operation_active(&env, PauseCategory::Contributions)?;
+
+    require_not_denylisted(&env, &contributor)?;
 
     if amount <= 0 {
         return Err(Error::InvalidAmount);
     }
 
+    validate_memo(&memo)?;
+
     if !is_token_supported(env.clone(), token.clone()) {
         return Err(Error::UnsupportedToken);
     }
 
+    // Contributions after a queued split change's delay use the new split
+    let _ = apply_due_split_change(&env, &id);
+
     // Verify group exists and is active
     let group_key = DataKey::AutoShare(id.clone());
     let group_details: AutoShareDetails = env
@@ -1659,6 +5484,8 @@ pub fn contribute(
         return Err(Error::GroupInactive);
     }
 
+    require_not_frozen(&env, &id)?;
+
     // Verify fundraising is active
     let fundraising_key = DataKey::GroupFundraising(id.clone());
     let mut fundraising_config: FundraisingConfig = env
@@ -1672,16 +5499,31 @@ pub fn contribute(
     }
     bump_persistent(&env, &fundraising_key);
 
+    require_complete_split(&env, &group_details)?;
+
+    // Campaigns with a reference currency target are tracked by value
+    let value = if fundraising_config.target_value > 0 {
+        value_in_reference(&env, &token, amount)?
+    } else {
+        0
+    };
+
     // Transfer amount from contributor to the contract
     let token_client = token::Client::new(&env, &token);
     token_client.transfer(&contributor, env.current_contract_address(), &amount);
 
     // Distribute funds to group members
-    perform_distribution(&env, &id, &token, amount, &group_details.members);
+    perform_distribution(&env, &group_details, &token, amount)?;
 
     // Update fundraising total
     fundraising_config.total_raised += amount;
-    if fundraising_config.total_raised >= fundraising_config.target_amount {
+    fundraising_config.total_raised_value += value;
+    let target_reached = if fundraising_config.target_value > 0 {
+        fundraising_config.total_raised_value >= fundraising_config.target_value
+    } else {
+        fundraising_config.total_raised >= fundraising_config.target_amount
+    };
+    if target_reached {
         fundraising_config.is_active = false;
     }
     env.storage()
@@ -1696,6 +5538,7 @@ pub fn contribute(
         token: token.clone(),
         amount,
         timestamp: env.ledger().timestamp(),
+        memo: memo.clone(),
     };
 
     let group_contributions_key = DataKey::GroupContributions(id.clone());
@@ -1737,7 +5580,388 @@ pub fn contribute(
     env.storage().persistent().set(&stats_key, &stats);
     bump_persistent(&env, &stats_key);
     // Emit new contribution event for fundraising tracking
-    emit_contribution(&env, &id, &contributor, &token, amount);
+    emit_contribution(&env, &id, &contributor, &token, amount, &memo);
+
+    Ok(())
+}
+
+// ============================================================================
+// Pull Payment Mandates
+// ============================================================================
+
+/// Authorizes the group to pull up to `max_amount_per_period` of `token` from the
+/// payer every `period_length` seconds until `expires_at`. The payer must also
+/// approve this contract as a spender on the token.
+#[allow(clippy::too_many_arguments)]
+pub fn create_mandate(
+    env: Env,
+    id: BytesN<32>,
+    payer: Address,
+    token: Address,
+    max_amount_per_period: i128,
+    period_length: u64,
+    expires_at: u64,
+    collector: Option<Address>,
+) -> Result<(), Error> {
+    payer.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    if max_amount_per_period <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let now = env.ledger().timestamp();
+    if period_length == 0 || expires_at <= now {
+        return Err(Error::InvalidInput);
+    }
+
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    let group_key = DataKey::AutoShare(id.clone());
+    if !env.storage().persistent().has(&group_key) {
+        return Err(Error::NotFound);
+    }
+    bump_persistent(&env, &group_key);
+
+    let mandate = Mandate {
+        group_id: id.clone(),
+        payer: payer.clone(),
+        token,
+        max_amount_per_period,
+        period_length,
+        expires_at,
+        collector,
+        period_start: now,
+        collected_in_period: 0,
+        total_collected: 0,
+        collection_count: 0,
+        is_active: true,
+    };
+    let key = DataKey::Mandate(id, payer);
+    env.storage().persistent().set(&key, &mandate);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+/// Stops any further collections. The mandate is kept for its usage record.
+pub fn revoke_mandate(env: Env, id: BytesN<32>, payer: Address) -> Result<(), Error> {
+    payer.require_auth();
+
+    let mut mandate = get_mandate(env.clone(), id.clone(), payer.clone())?;
+    mandate.is_active = false;
+
+    let key = DataKey::Mandate(id, payer);
+    env.storage().persistent().set(&key, &mandate);
+    bump_persistent(&env, &key);
+    Ok(())
+}
+
+pub fn get_mandate(env: Env, id: BytesN<32>, payer: Address) -> Result<Mandate, Error> {
+    let key = DataKey::Mandate(id, payer);
+    let mandate = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(&env, &key);
+    Ok(mandate)
+}
+
+/// Pulls `amount` from the payer under their mandate and splits it among the
+/// group members. Only the group creator or the mandate's collector can call.
+pub fn collect(
+    env: Env,
+    id: BytesN<32>,
+    payer: Address,
+    amount: i128,
+    collector: Address,
+) -> Result<(), Error> {
+    collector.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &payer)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let mut mandate = get_mandate(env.clone(), id.clone(), payer.clone())?;
+    if !mandate.is_active {
+        return Err(Error::Unauthorized);
+    }
+
+    let details = prepare_distribution(&env, &id)?;
+    if collector != details.creator && mandate.collector != Some(collector.clone()) {
+        return Err(Error::Unauthorized);
+    }
+
+    let now = env.ledger().timestamp();
+    if now >= mandate.expires_at {
+        return Err(Error::MandateExpired);
+    }
+
+    // Roll forward to the period containing `now`
+    let elapsed_periods = (now - mandate.period_start) / mandate.period_length;
+    if elapsed_periods > 0 {
+        mandate.period_start += elapsed_periods * mandate.period_length;
+        mandate.collected_in_period = 0;
+    }
+
+    if mandate.collected_in_period + amount > mandate.max_amount_per_period {
+        return Err(Error::MandateLimitExceeded);
+    }
+
+    let contract = env.current_contract_address();
+    let client = token::TokenClient::new(&env, &mandate.token);
+    client.transfer_from(&contract, &payer, &contract, &amount);
+
+    mandate.collected_in_period += amount;
+    mandate.total_collected += amount;
+    mandate.collection_count += 1;
+    let key = DataKey::Mandate(id.clone(), payer.clone());
+    env.storage().persistent().set(&key, &mandate);
+    bump_persistent(&env, &key);
+
+    settle_distribution(&env, details, &mandate.token, amount, &payer, None, None)?;
+
+    MandateCollected {
+        group_id: id,
+        payer,
+        collector,
+        amount,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+// ============================================================================
+// Invoices
+// ============================================================================
+
+fn get_invoice_record(env: &Env, invoice_id: &BytesN<32>) -> Result<Invoice, Error> {
+    let key = DataKey::Invoice(invoice_id.clone());
+    let invoice = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NotFound)?;
+    bump_persistent(env, &key);
+    Ok(invoice)
+}
+
+fn save_invoice(env: &Env, invoice: &Invoice) {
+    let key = DataKey::Invoice(invoice.id.clone());
+    env.storage().persistent().set(&key, invoice);
+    bump_persistent(env, &key);
+}
+
+fn is_invoice_open(invoice: &Invoice) -> bool {
+    matches!(
+        invoice.status,
+        InvoiceStatus::Open | InvoiceStatus::PartiallyPaid
+    )
+}
+
+/// Issues a payment request against a group. The creator or a manager with the
+/// ManageInvoices permission can call.
+#[allow(clippy::too_many_arguments)]
+pub fn create_invoice(
+    env: Env,
+    invoice_id: BytesN<32>,
+    group_id: BytesN<32>,
+    caller: Address,
+    token: Address,
+    amount: i128,
+    payer: Option<Address>,
+    due_date: u64,
+    reference: Option<String>,
+) -> Result<(), Error> {
+    caller.require_auth();
+
+    require_operation_active(&env, PauseCategory::GroupManagement)?;
+
+    let details = get_autoshare(env.clone(), group_id.clone())?;
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageInvoices)?;
+
+    if amount <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    if !is_token_supported(env.clone(), token.clone()) {
+        return Err(Error::UnsupportedToken);
+    }
+
+    validate_memo(&reference)?;
+
+    let now = env.ledger().timestamp();
+    if due_date <= now {
+        return Err(Error::InvalidInput);
+    }
+
+    let key = DataKey::Invoice(invoice_id.clone());
+    if env.storage().persistent().has(&key) {
+        return Err(Error::AlreadyExists);
+    }
+
+    let invoice = Invoice {
+        id: invoice_id.clone(),
+        group_id: group_id.clone(),
+        token: token.clone(),
+        amount,
+        amount_paid: 0,
+        payer,
+        due_date,
+        reference,
+        status: InvoiceStatus::Open,
+        created_at: now,
+    };
+    save_invoice(&env, &invoice);
+
+    let group_invoices_key = DataKey::GroupInvoices(group_id.clone());
+    let mut group_invoices: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&group_invoices_key)
+        .unwrap_or(Vec::new(&env));
+    group_invoices.push_back(invoice_id.clone());
+    env.storage()
+        .persistent()
+        .set(&group_invoices_key, &group_invoices);
+    bump_persistent(&env, &group_invoices_key);
+
+    InvoiceCreated {
+        invoice_id,
+        group_id,
+        token,
+        amount,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+/// Pays all or part of an open invoice. The payment is split among the group
+/// members like a regular distribution and consumes one usage.
+#[allow(clippy::needless_borrows_for_generic_args)]
+pub fn pay_invoice(
+    env: Env,
+    invoice_id: BytesN<32>,
+    payer: Address,
+    amount: i128,
+) -> Result<(), Error> {
+    payer.require_auth();
+
+    require_operation_active(&env, PauseCategory::Distributions)?;
+
+    require_not_denylisted(&env, &payer)?;
+
+    let mut invoice = get_invoice_record(&env, &invoice_id)?;
+    if !is_invoice_open(&invoice) {
+        return Err(Error::InvoiceNotOpen);
+    }
+
+    if let Some(expected) = &invoice.payer {
+        if *expected != payer {
+            return Err(Error::Unauthorized);
+        }
+    }
+
+    let remaining = invoice.amount - invoice.amount_paid;
+    if amount <= 0 || amount > remaining {
+        return Err(Error::InvalidAmount);
+    }
+
+    let details = prepare_distribution(&env, &invoice.group_id)?;
+
+    let client = token::TokenClient::new(&env, &invoice.token);
+    client.transfer(&payer, &env.current_contract_address(), &amount);
+    settle_distribution(
+        &env,
+        details,
+        &invoice.token,
+        amount,
+        &payer,
+        invoice.reference.clone(),
+        Some(invoice_id.clone()),
+    )?;
+
+    invoice.amount_paid += amount;
+    invoice.status = if invoice.amount_paid == invoice.amount {
+        InvoiceStatus::Paid
+    } else {
+        InvoiceStatus::PartiallyPaid
+    };
+    save_invoice(&env, &invoice);
+
+    InvoicePaid {
+        invoice_id,
+        payer,
+        amount,
+        remaining: invoice.amount - invoice.amount_paid,
+    }
+    .publish(&env);
+    Ok(())
+}
+
+/// Cancels an open invoice. Payments already made are not refunded. The creator
+/// or a manager with the ManageInvoices permission can call.
+pub fn cancel_invoice(env: Env, invoice_id: BytesN<32>, caller: Address) -> Result<(), Error> {
+    caller.require_auth();
+
+    let mut invoice = get_invoice_record(&env, &invoice_id)?;
+    let details = get_autoshare(env.clone(), invoice.group_id.clone())?;
+    require_group_permission(&env, &details, &caller, ManagerPermission::ManageInvoices)?;
+
+    if !is_invoice_open(&invoice) {
+        return Err(Error::InvoiceNotOpen);
+    }
 
+    invoice.status = InvoiceStatus::Cancelled;
+    save_invoice(&env, &invoice);
+
+    InvoiceCancelled {
+        invoice_id,
+        group_id: invoice.group_id,
+    }
+    .publish(&env);
     Ok(())
 }
+
+pub fn get_invoice(env: Env, invoice_id: BytesN<32>) -> Result<Invoice, Error> {
+    get_invoice_record(&env, &invoice_id)
+}
+
+pub fn get_group_invoices(env: Env, group_id: BytesN<32>) -> Vec<Invoice> {
+    let key = DataKey::GroupInvoices(group_id);
+    let invoice_ids: Vec<BytesN<32>> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(&env));
+    if !invoice_ids.is_empty() {
+        bump_persistent(&env, &key);
+    }
+
+    let mut invoices: Vec<Invoice> = Vec::new(&env);
+    for invoice_id in invoice_ids.iter() {
+        if let Ok(invoice) = get_invoice_record(&env, &invoice_id) {
+            invoices.push_back(invoice);
+        }
+    }
+    invoices
+}
+
+/// Returns the group's invoices that are still unpaid past their due date.
+pub fn get_overdue_invoices(env: Env, group_id: BytesN<32>) -> Vec<Invoice> {
+    let now = env.ledger().timestamp();
+    let mut overdue: Vec<Invoice> = Vec::new(&env);
+    for invoice in get_group_invoices(env.clone(), group_id).iter() {
+        if is_invoice_open(&invoice) && invoice.due_date < now {
+            overdue.push_back(invoice);
+        }
+    }
+    overdue
+}
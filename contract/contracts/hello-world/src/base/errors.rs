@@ -31,4 +31,27 @@ pub enum Error {
     MaxMembersExceeded = 25,
     FundraisingAlreadyActive = 32,
     FundraisingNotActive = 33,
+    InvalidPromoCode = 34,
+    PromoCodeExpired = 35,
+    PromoCodeExhausted = 36,
+    MandateExpired = 37,
+    MandateLimitExceeded = 38,
+    InvoiceNotOpen = 39,
+    AlreadyProcessed = 40,
+    SlippageExceeded = 41,
+    SwapRouterNotSet = 42,
+    OracleNotSet = 43,
+    PriceUnavailable = 44,
+    StalePrice = 45,
+    InvitationsPending = 46,
+    GovernanceRequired = 47,
+    VotingClosed = 48,
+    ProposalNotPassed = 49,
+    TimelockActive = 50,
+    GroupFrozen = 51,
+    AddressDenylisted = 52,
+    SwapFailed = 53,
+    VoteOpen = 54,
+    GroupHasFunds = 55,
+    ProposalLimitReached = 56,
 }
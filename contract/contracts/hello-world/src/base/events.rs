@@ -6,6 +6,7 @@ pub fn emit_distribution(
     token: &Address,
     amount: i128,
     member_count: u32,
+    memo: &Option<String>,
 ) {
     Distribution {
         id: group_id.clone(),
@@ -13,6 +14,7 @@ pub fn emit_distribution(
         sender: sender.clone(),
         amount,
         member_count,
+        memo: memo.clone(),
     }
     .publish(env);
 }
@@ -24,16 +26,19 @@ pub fn emit_contribution(
     contributor: &Address,
     token: &Address,
     amount: i128,
+    memo: &Option<String>,
 ) {
     Contribution {
         group_id: group_id.clone(),
         contributor: contributor.clone(),
         token: token.clone(),
         amount,
+        memo: memo.clone(),
     }
     .publish(env);
 }
-use soroban_sdk::{contractevent, Address, BytesN};
+use crate::base::types::{GroupMember, PauseCategory};
+use soroban_sdk::{contractevent, Address, BytesN, String, Vec};
 
 #[contractevent(data_format = "single-value")]
 #[derive(Clone)]
@@ -51,6 +56,20 @@ pub struct ContractPaused {}
 #[derive(Clone)]
 pub struct ContractUnpaused {}
 
+#[contractevent]
+#[derive(Clone)]
+pub struct OperationPaused {
+    #[topic]
+    pub category: PauseCategory,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct OperationUnpaused {
+    #[topic]
+    pub category: PauseCategory,
+}
+
 #[contractevent(data_format = "single-value")]
 #[derive(Clone)]
 pub struct AutoshareUpdated {
@@ -112,6 +131,214 @@ pub struct Distribution {
     pub sender: Address,
     pub amount: i128,
     pub member_count: u32,
+    pub memo: Option<String>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MemberInvited {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub invitee: Address,
+    pub percentage: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct InvitationAccepted {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub member: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct InvitationDeclined {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub invitee: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct GroupFrozen {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub frozen_by: Address,
+    pub reason: u32,
+    pub expires_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct GroupUnfrozen {
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub unfrozen_by: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct SplitChangeQueued {
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub effective_at: u64,
+    pub members: Vec<GroupMember>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct SplitChangeCancelled {
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub cancelled_by: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct GroupOwnershipTransferred {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub new_owner: Address,
+    pub previous_owner: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ProposalCreated {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub proposer: Address,
+    pub proposal_id: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct VoteCast {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub voter: Address,
+    pub proposal_id: u32,
+    pub support: bool,
+    pub weight: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ProposalExecuted {
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub proposal_id: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MemberLeft {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub member: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct PendingPayoutReleased {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub member: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct GroupDeposit {
+    #[topic]
+    pub id: BytesN<32>,
+    #[topic]
+    pub token: Address,
+    pub depositor: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MandateCollected {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub payer: Address,
+    pub collector: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct InvoiceCreated {
+    #[topic]
+    pub invoice_id: BytesN<32>,
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct InvoicePaid {
+    #[topic]
+    pub invoice_id: BytesN<32>,
+    #[topic]
+    pub payer: Address,
+    pub amount: i128,
+    pub remaining: i128,
+}
+
+#[contractevent(data_format = "single-value")]
+#[derive(Clone)]
+pub struct InvoiceCancelled {
+    #[topic]
+    pub invoice_id: BytesN<32>,
+    pub group_id: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct UsageSponsored {
+    #[topic]
+    pub pool_id: BytesN<32>,
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ReferralRewarded {
+    #[topic]
+    pub referrer: Address,
+    #[topic]
+    pub group_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct PromoCodeRedeemed {
+    #[topic]
+    pub code_hash: BytesN<32>,
+    #[topic]
+    pub user: Address,
+    pub group_id: BytesN<32>,
 }
 
 #[contractevent(data_format = "single-value")]
@@ -139,4 +366,31 @@ pub struct Contribution {
     #[topic]
     pub token: Address,
     pub amount: i128,
+    pub memo: Option<String>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AddressDenylisted {
+    #[topic]
+    pub address: Address,
+    pub reason: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AddressRemovedFromDenylist {
+    #[topic]
+    pub address: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct BlockedShareEscrowed {
+    #[topic]
+    pub group_id: BytesN<32>,
+    #[topic]
+    pub member: Address,
+    pub token: Address,
+    pub amount: i128,
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, String, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -17,6 +17,21 @@ pub struct AutoShareDetails {
 pub struct GroupMember {
     pub address: Address,
     pub percentage: u32,
+    /// Supported token the member wants to be paid in. Shares in other tokens are
+    /// converted through the swap router when one is configured and the price
+    /// oracle can quote the pair; otherwise they are paid as received.
+    pub preferred_token: Option<Address>,
+    /// Address that receives the member's payouts instead of `address`.
+    pub payout_address: Option<Address>,
+}
+
+/// A share offered to an address that only takes effect once the invitee accepts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invitation {
+    pub invitee: Address,
+    pub percentage: u32,
+    pub invited_at: u64,
 }
 
 #[contracttype]
@@ -26,16 +41,94 @@ pub struct PaymentHistory {
     pub group_id: BytesN<32>,
     pub usages_purchased: u32,
     pub amount_paid: i128,
+    pub unit_price: i128,
+    pub promo_code: Option<BytesN<32>>,
+    pub fee_waived: bool,
     pub timestamp: u64,
 }
 
+/// An admin-issued promo code, stored by the SHA-256 hash of the code.
+/// The first `free_usages` usages of a purchase are free and the rest are
+/// charged `discount_percentage` percent less.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PromoCode {
+    pub code_hash: BytesN<32>,
+    pub discount_percentage: u32,
+    pub free_usages: u32,
+    pub max_redemptions: u32,
+    pub redemptions: u32,
+    pub expires_at: u64,
+    pub is_active: bool,
+}
+
+/// A volume discount tier. Purchases of at least `min_usages` usages are
+/// charged `discount_percentage` percent less per usage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceTier {
+    pub min_usages: u32,
+    pub discount_percentage: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UsageQuote {
+    pub usage_count: u32,
+    pub unit_price: i128,
+    pub total_cost: i128,
+}
+
+/// A pre-funded pool that pays usage fees for groups that run out of usages.
+/// A group is covered if it is listed directly or if its creator is listed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SponsorPool {
+    pub id: BytesN<32>,
+    pub sponsor: Address,
+    pub token: Address,
+    pub balance: i128,
+    pub per_group_limit: u32,
+    pub sponsored_groups: Vec<BytesN<32>>,
+    pub sponsored_creators: Vec<Address>,
+    pub total_spent: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SponsorSpending {
+    pub group_id: BytesN<32>,
+    pub usages_sponsored: u32,
+    pub amount_spent: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenAmount {
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Rewards earned by a referrer. `total_earned` is cumulative per token,
+/// `claimable` is what has not been claimed yet.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralStats {
+    pub referred_groups: Vec<BytesN<32>>,
+    pub total_earned: Vec<TokenAmount>,
+    pub claimable: Vec<TokenAmount>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MemberAmount {
     pub address: Address,
+    pub token: Address,
     pub amount: i128,
 }
 
+/// `token` and `total_amount` describe the first token paid out; `token_amounts`
+/// lists every token for distributions made with `distribute_multi`.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DistributionHistory {
@@ -46,6 +139,236 @@ pub struct DistributionHistory {
     pub member_amounts: Vec<MemberAmount>,
     pub timestamp: u64,
     pub distribution_number: u32,
+    pub memo: Option<String>,
+    pub invoice_id: Option<BytesN<32>>,
+    pub token_amounts: Vec<TokenAmount>,
+    pub swaps: Vec<SwapReceipt>,
+}
+
+/// A swap a payment went through before it was split. `amount_out` is the amount
+/// the contract actually received and distributed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SwapReceipt {
+    pub router: Address,
+    pub token_in: Address,
+    pub amount_in: i128,
+    pub token_out: Address,
+    pub amount_out: i128,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchDistribution {
+    pub group_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Outcome of one batch item. `error_code` is the contract `Error` value, or 0 on success.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchDistributionResult {
+    pub group_id: BytesN<32>,
+    pub success: bool,
+    pub error_code: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum InvoiceStatus {
+    Open,
+    PartiallyPaid,
+    Paid,
+    Cancelled,
+}
+
+/// A payment request issued by a group creator. `payer` restricts who may pay it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Invoice {
+    pub id: BytesN<32>,
+    pub group_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+    pub amount_paid: i128,
+    pub payer: Option<Address>,
+    pub due_date: u64,
+    pub reference: Option<String>,
+    pub status: InvoiceStatus,
+    pub created_at: u64,
+}
+
+/// How the rounding dust left after flooring every share is handled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RoundingPolicy {
+    /// The last member receives the remainder.
+    LastMember,
+    /// The member with the largest percentage receives the remainder.
+    LargestShare,
+    /// The group creator receives the remainder.
+    Creator,
+    /// A designated address receives the remainder.
+    DustRecipient(Address),
+    /// The remainder is handed out one unit at a time to the members with the
+    /// largest fractional shares.
+    LargestRemainder,
+    /// The remainder is kept as a group credit and added to the next distribution
+    /// of the same token.
+    CarryForward,
+}
+
+/// What happens to a member's percentage when they leave a group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LeavePolicy {
+    /// The split stays short of 100% and distributions fail until the creator
+    /// rebalances it.
+    RequireRebalance,
+    /// The share is divided among the remaining members in proportion to theirs.
+    Redistribute,
+}
+
+/// Actions a group creator can delegate to a co-manager.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ManagerPermission {
+    /// Add, remove and rebalance members.
+    ManageMembers,
+    /// Start fundraising campaigns.
+    ManageFundraising,
+    /// Buy additional usages with `topup_subscription` while the group is inactive.
+    TopUp,
+    /// Deactivate and reactivate the group.
+    Deactivate,
+    /// Issue and cancel invoices.
+    ManageInvoices,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupManager {
+    pub address: Address,
+    pub permissions: Vec<ManagerPermission>,
+}
+
+/// Voting rules for a group whose changes are decided by its members.
+/// `quorum` and `threshold` are percentages: a proposal needs votes carrying at
+/// least `quorum`% of the split, and `threshold`% of the weight cast in favour.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GovernanceConfig {
+    pub quorum: u32,
+    pub threshold: u32,
+    /// Seconds a proposal stays open for voting.
+    pub voting_period: u64,
+    /// Seconds after voting ends during which a passed proposal can be executed.
+    pub execution_window: u64,
+}
+
+/// A change to a governed group that only applies once members vote it through.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProposalAction {
+    UpdateMembers(Vec<GroupMember>),
+    UpdateName(String),
+    SetActive(bool),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub voting_ends: u64,
+    /// Each member's percentage when the proposal was created. Only these
+    /// addresses can vote, with these weights.
+    pub weights: Map<Address, u32>,
+    /// Sum of the voters' snapshotted percentages.
+    pub votes_for: u32,
+    pub votes_against: u32,
+    pub voters: Vec<Address>,
+    pub executed: bool,
+    /// Set when another proposal of the same kind was executed first.
+    pub superseded: bool,
+}
+
+/// Per-group policies that can be changed after creation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupSettings {
+    pub rounding_policy: RoundingPolicy,
+    /// Shares below this amount are held as a pending balance until they reach it.
+    /// Zero pays every share immediately.
+    pub min_payout: i128,
+    pub leave_policy: LeavePolicy,
+    /// Seconds a split change waits before it applies. Zero applies changes at once.
+    pub split_timelock: u64,
+    /// Address that can freeze the group in an emergency, besides the admin.
+    pub guardian: Option<Address>,
+}
+
+/// An emergency stop on a single group, lifted automatically at `expires_at`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GroupFreeze {
+    pub reason: u32,
+    pub frozen_by: Address,
+    pub expires_at: u64,
+}
+
+/// A split change queued by `update_members` on a timelocked group.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingSplitChange {
+    pub members: Vec<GroupMember>,
+    pub queued_by: Address,
+    pub effective_at: u64,
+}
+
+/// The outcome `distribute` would have for a group right now.
+/// `usage_fee` is charged to a sponsor pool when the group has no usages left;
+/// it is zero when a prepaid usage is consumed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionPreview {
+    pub group_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+    /// Each member's share of this distribution.
+    pub member_amounts: Vec<MemberAmount>,
+    /// The transfers made now, to each payout address. A share converted into a
+    /// preferred token is shown at the oracle's quote; the swap may return up to
+    /// the payout slippage limit less, or fall back to `token`.
+    pub payouts: Vec<MemberAmount>,
+    /// Balances, including the new share, left pending for members below the
+    /// minimum payout or escrowed for members blocked by the denylist.
+    pub held_amounts: Vec<MemberAmount>,
+    pub sponsored_usage: bool,
+    pub usage_fee: i128,
+    pub remaining_usages_after: u32,
+    pub carried_dust: i128,
+}
+
+/// A payer's standing authorization for a group to pull payments from them.
+/// `collected_in_period` resets whenever a new period starts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Mandate {
+    pub group_id: BytesN<32>,
+    pub payer: Address,
+    pub token: Address,
+    pub max_amount_per_period: i128,
+    pub period_length: u64,
+    pub expires_at: u64,
+    pub collector: Option<Address>,
+    pub period_start: u64,
+    pub collected_in_period: i128,
+    pub total_collected: i128,
+    pub collection_count: u32,
+    pub is_active: bool,
 }
 
 #[contracttype]
@@ -72,6 +395,19 @@ pub struct FundraisingConfig {
     pub target_amount: i128,
     pub total_raised: i128,
     pub is_active: bool,
+    /// Target in the price oracle's reference currency, with 7 decimals. Zero for
+    /// campaigns targeting a raw token amount.
+    pub target_value: i128,
+    /// Contributions valued in the reference currency at the time they were made.
+    pub total_raised_value: i128,
+}
+
+/// Price oracle used to value contributions, and how old its prices may be.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    pub oracle: Address,
+    pub max_price_age: u64,
 }
 
 #[contracttype]
@@ -82,6 +418,7 @@ pub struct FundraisingContribution {
     pub token: Address,
     pub amount: i128,
     pub timestamp: u64,
+    pub memo: Option<String>,
 }
 
 #[contracttype]
@@ -90,3 +427,46 @@ pub struct GroupStats {
     pub total_raised: i128,
     pub contribution_count: u32,
 }
+
+/// Groups of operations the admin can pause independently of the global pause.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PauseCategory {
+    /// Distributions to members, including mandate collections and invoice payments.
+    Distributions,
+    /// Fundraising contributions and group deposits.
+    Contributions,
+    /// Admin withdrawals, sponsor refunds and claims of owed payouts or rewards.
+    Withdrawals,
+    /// Group creation and usage top-ups.
+    Subscriptions,
+    /// Membership, settings, ownership and governance changes.
+    GroupManagement,
+    /// Creating, funding and configuring sponsor pools and choosing what they cover.
+    /// Spending from a pool is paused with distributions and refunds with withdrawals.
+    Sponsorship,
+}
+
+/// Why and when the admin blocked an address from the contract's fund flows.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DenylistEntry {
+    pub address: Address,
+    pub reason: u32,
+    pub added_by: Address,
+    pub added_at: u64,
+}
+
+/// What happens to a denylisted member's share of a distribution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BlockedSharePolicy {
+    /// The whole distribution fails.
+    Reject,
+    /// The share is held as a pending payout the member can only claim once
+    /// removed from the denylist.
+    Escrow,
+    /// The share is split among the remaining members in proportion to their
+    /// percentages.
+    Redistribute,
+}
@@ -1,8 +1,13 @@
-use soroban_sdk::{Address, BytesN, Env, String, Vec};
+use soroban_sdk::{Address, Bytes, BytesN, Env, Map, String, Vec};
 
 use crate::base::types::{
-    AutoShareDetails, DistributionHistory, DistributionRecord, FundraisingConfig,
-    FundraisingContribution, GroupMember, PaymentHistory,
+    AutoShareDetails, BatchDistribution, BatchDistributionResult, BlockedSharePolicy,
+    DenylistEntry, DistributionHistory, DistributionPreview, DistributionRecord, FundraisingConfig,
+    FundraisingContribution, GovernanceConfig, GroupFreeze, GroupManager, GroupMember,
+    GroupSettings, Invitation, Invoice, LeavePolicy, ManagerPermission, Mandate, OracleConfig,
+    PauseCategory, PaymentHistory, PendingSplitChange, PriceTier, PromoCode, Proposal,
+    ProposalAction, ReferralStats, RoundingPolicy, SponsorPool, SponsorSpending, TokenAmount,
+    UsageQuote,
 };
 
 /// AutoShareTrait defines the interface for the AutoShare contract.
@@ -26,23 +31,73 @@ pub trait AutoShareTrait {
     /// Returns the current pause status.
     fn get_paused_status(env: Env) -> bool;
 
+    /// Pauses one category of operations while the rest stay available. Only admin can call.
+    fn pause_operation(env: Env, admin: Address, category: PauseCategory);
+
+    /// Unpauses one category of operations. Only admin can call.
+    fn unpause_operation(env: Env, admin: Address, category: PauseCategory);
+
+    /// Returns whether each operation category is paused, either on its own or by the
+    /// global pause.
+    fn get_pause_map(env: Env) -> Map<PauseCategory, bool>;
+
+    /// Blocks an address from creating groups, joining them, contributing and
+    /// receiving distributions. Only admin can call.
+    fn add_to_denylist(env: Env, admin: Address, address: Address, reason: u32);
+
+    /// Lifts the block on an address. Only admin can call.
+    fn remove_from_denylist(env: Env, admin: Address, address: Address);
+
+    /// Returns why and when an address was denylisted, if it is.
+    fn get_denylist_entry(env: Env, address: Address) -> Option<DenylistEntry>;
+
+    /// Returns true if the address is denylisted.
+    fn is_denylisted(env: Env, address: Address) -> bool;
+
+    /// Sets what happens to a denylisted member's share of a distribution. Only admin can call.
+    fn set_blocked_share_policy(env: Env, admin: Address, policy: BlockedSharePolicy);
+
+    /// Returns what happens to a denylisted member's share of a distribution.
+    fn get_blocked_share_policy(env: Env) -> BlockedSharePolicy;
+
+    /// Returns the shares escrowed for a denylisted member of a group.
+    fn get_escrowed_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128;
+
+    /// Pays out a member's escrowed shares once they are off the denylist. Only admin
+    /// can call. Returns the amount paid.
+    fn release_escrowed_payout(
+        env: Env,
+        admin: Address,
+        id: BytesN<32>,
+        member: Address,
+        token: Address,
+    ) -> i128;
+
     /// Returns the current admin address.
     fn get_admin(env: Env) -> Address;
 
     /// Transfers admin rights to a new address. Only current admin can call.
     fn transfer_admin(env: Env, current_admin: Address, new_admin: Address);
 
-    /// Withdraws tokens from the contract. Only admin can call.
+    /// Withdraws tokens from the contract. Only admin can call, and only the part of
+    /// the balance not reserved for users can be withdrawn.
     fn withdraw(env: Env, admin: Address, token: Address, amount: i128, recipient: Address);
 
     /// Returns the contract's balance for a specified token.
     fn get_contract_balance(env: Env, token: Address) -> i128;
 
+    /// Returns how much of the contract's balance is held for users: group deposits,
+    /// sponsor pools, referral rewards, pending payouts and dust credit.
+    fn get_reserved_balance(env: Env, token: Address) -> i128;
+
     // ============================================================================
     // AutoShare Group Management
     // ============================================================================
 
     /// Creates a new AutoShare plan with payment.
+    /// An optional referrer is credited a share of the usage fee, and an optional
+    /// promo code discounts the purchase.
+    #[allow(clippy::too_many_arguments)]
     fn create(
         env: Env,
         id: BytesN<32>,
@@ -50,10 +105,14 @@ pub trait AutoShareTrait {
         creator: Address,
         usage_count: u32,
         payment_token: Address,
+        referrer: Option<Address>,
+        promo_code: Option<Bytes>,
     );
 
     /// Update members of an existing AutoShare plan.
-    /// Only creator can update. Validates percentages.
+    /// Only the creator or a member manager can update. Validates percentages.
+    /// Addresses that are not members yet are invited and must accept first.
+    /// On a timelocked group the change is queued instead of applied.
     fn update_members(env: Env, id: BytesN<32>, caller: Address, new_members: Vec<GroupMember>);
 
     /// Retrieves an existing AutoShare plan.
@@ -74,8 +133,8 @@ pub trait AutoShareTrait {
     /// Returns all members of a group.
     fn get_group_members(env: Env, id: BytesN<32>) -> Vec<GroupMember>;
 
-    /// Adds a member to a group with specified percentage.
-    /// Only the group creator (caller) may add members.
+    /// Invites an address to a group with the specified percentage.
+    /// Only the creator or a member manager may invite; the share applies once accepted.
     fn add_group_member(
         env: Env,
         id: BytesN<32>,
@@ -84,14 +143,44 @@ pub trait AutoShareTrait {
         percentage: u32,
     );
 
-    /// Removes a single member from a group. Only the creator can call; group must be active.
+    /// Removes a single member from a group. Only the creator or a member manager can call; group must be active.
     /// After removal, remaining percentages may not sum to 100; call update_members to set a valid split.
+    /// Also withdraws a pending invitation for the address.
     fn remove_group_member(env: Env, id: BytesN<32>, caller: Address, member_address: Address);
 
-    /// Deactivates a group. Only the creator can deactivate.
+    /// Accepts an invitation to a group. Called by the invitee.
+    fn accept_invitation(env: Env, id: BytesN<32>, invitee: Address);
+
+    /// Declines an invitation to a group. Called by the invitee.
+    fn decline_invitation(env: Env, id: BytesN<32>, invitee: Address);
+
+    /// Returns the invitations of a group that have not been answered yet.
+    fn get_group_invitations(env: Env, id: BytesN<32>) -> Vec<Invitation>;
+
+    /// Sets the token a member is paid in and the address their payouts go to.
+    /// Called by the member themselves; `None` clears a preference.
+    fn set_payout_preferences(
+        env: Env,
+        id: BytesN<32>,
+        member: Address,
+        preferred_token: Option<Address>,
+        payout_address: Option<Address>,
+    );
+
+    /// Removes the caller from a group. Their share is redistributed or left for the
+    /// creator to rebalance, depending on the group's leave policy.
+    fn leave_group(env: Env, id: BytesN<32>, member: Address);
+
+    /// Moves a member's memberships, open invitations, earnings, pending payouts and
+    /// distribution history to a new address, which also takes over the member's weight
+    /// on open proposals. Both addresses must authorize. Fails while the member has a
+    /// vote on an open proposal.
+    fn change_payout_address(env: Env, member: Address, new_address: Address);
+
+    /// Deactivates a group. Only the creator or a manager with `Deactivate` can call.
     fn deactivate_group(env: Env, id: BytesN<32>, caller: Address);
 
-    /// Activates a group. Only the creator can activate.
+    /// Activates a group. Only the creator or a manager with `Deactivate` can call.
     fn activate_group(env: Env, id: BytesN<32>, caller: Address);
 
     /// Updates the name of a group. Only the creator can update.
@@ -102,8 +191,128 @@ pub trait AutoShareTrait {
 
     /// Permanently deletes a group. Only creator or admin can delete.
     /// Group must be deactivated first and have 0 remaining usages.
+    /// Deposited balances must be distributed first; carried-forward dust goes to the creator.
     fn delete_group(env: Env, id: BytesN<32>, caller: Address);
 
+    // ============================================================================
+    // Group Settings
+    // ============================================================================
+
+    /// Returns the group's configurable policies.
+    fn get_group_settings(env: Env, id: BytesN<32>) -> GroupSettings;
+
+    /// Sets how rounding dust is handled for a group. Only the creator can call.
+    fn set_rounding_policy(env: Env, id: BytesN<32>, policy: RoundingPolicy, caller: Address);
+
+    /// Sets what happens to a member's share when they leave. Only the creator can call.
+    fn set_leave_policy(env: Env, id: BytesN<32>, policy: LeavePolicy, caller: Address);
+
+    /// Sets how long split changes from `update_members` and executed proposals
+    /// wait before applying. Only the creator can call, and the delay can only be
+    /// raised. A timelock cannot be enabled while invitations are pending.
+    fn set_split_timelock(env: Env, id: BytesN<32>, delay: u64, caller: Address);
+
+    /// Returns the rounding dust carried forward for a group's next distribution of a token.
+    fn get_dust_credit(env: Env, id: BytesN<32>, token: Address) -> i128;
+
+    /// Sets the minimum payout for a group; smaller shares are held as pending
+    /// balances. Only the creator can call.
+    fn set_min_payout(env: Env, id: BytesN<32>, min_payout: i128, caller: Address);
+
+    /// Returns a member's pending balance for a group and token.
+    fn get_pending_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128;
+
+    /// Pays out a member's pending balance on demand, to their payout address and
+    /// preferred token. Returns the amount paid.
+    fn claim_pending_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128;
+
+    // ============================================================================
+    // Group Freeze
+    // ============================================================================
+
+    /// Sets the guardian that can freeze a group alongside the admin. Only the creator can call.
+    fn set_group_guardian(env: Env, id: BytesN<32>, guardian: Option<Address>, caller: Address);
+
+    /// Freezes a group's distributions, contributions, payout claims and membership,
+    /// payout, ownership and guardian changes for `duration` seconds. Only the
+    /// group's guardian or the admin can call.
+    fn freeze_group(env: Env, id: BytesN<32>, caller: Address, reason: u32, duration: u64);
+
+    /// Lifts a group's freeze early. Only the group's guardian or the admin can call.
+    fn unfreeze_group(env: Env, id: BytesN<32>, caller: Address);
+
+    /// Returns a group's freeze while it is in effect.
+    fn get_group_freeze(env: Env, id: BytesN<32>) -> Option<GroupFreeze>;
+
+    /// Checks if a group is currently frozen.
+    fn is_group_frozen(env: Env, id: BytesN<32>) -> bool;
+
+    // ============================================================================
+    // Split Timelock
+    // ============================================================================
+
+    /// Returns the split change waiting for a group's timelock to expire, if any.
+    fn get_pending_split_change(env: Env, id: BytesN<32>) -> Option<PendingSplitChange>;
+
+    /// Cancels a queued split change. Only the creator or a member manager can call.
+    fn cancel_split_change(env: Env, id: BytesN<32>, caller: Address);
+
+    /// Applies a queued split change once its delay has passed. Anyone can call.
+    /// Distributions apply a due change themselves and keep the current split if
+    /// it can no longer apply.
+    fn apply_split_change(env: Env, id: BytesN<32>);
+
+    // ============================================================================
+    // Ownership & Managers
+    // ============================================================================
+
+    /// Offers ownership of a group to a new owner. Only the creator can call;
+    /// the transfer completes when the new owner accepts.
+    fn transfer_group_ownership(env: Env, id: BytesN<32>, caller: Address, new_owner: Address);
+
+    /// Accepts a pending ownership transfer. Called by the new owner.
+    fn accept_group_ownership(env: Env, id: BytesN<32>, new_owner: Address);
+
+    /// Returns the address a group's ownership has been offered to, if any.
+    fn get_pending_group_owner(env: Env, id: BytesN<32>) -> Option<Address>;
+
+    /// Sets a co-manager's permissions; an empty list removes them. Only the creator can call.
+    fn set_group_manager(
+        env: Env,
+        id: BytesN<32>,
+        caller: Address,
+        manager: Address,
+        permissions: Vec<ManagerPermission>,
+    );
+
+    /// Returns a group's co-managers and their permissions.
+    fn get_group_managers(env: Env, id: BytesN<32>) -> Vec<GroupManager>;
+
+    // ============================================================================
+    // Governance
+    // ============================================================================
+
+    /// Puts a group's members, name and activation under member vote. Only the
+    /// creator can call, and governance cannot be turned off afterwards.
+    fn enable_governance(env: Env, id: BytesN<32>, caller: Address, config: GovernanceConfig);
+
+    /// Returns a group's voting rules, if it is governed.
+    fn get_governance(env: Env, id: BytesN<32>) -> Option<GovernanceConfig>;
+
+    /// Proposes a change to a governed group. Returns the proposal id. A group can have
+    /// at most ten proposals open at once.
+    fn propose(env: Env, id: BytesN<32>, proposer: Address, action: ProposalAction) -> u32;
+
+    /// Votes on a proposal, weighted by the voter's percentage when it was proposed.
+    fn vote(env: Env, id: BytesN<32>, proposal_id: u32, voter: Address, support: bool);
+
+    /// Applies a proposal that passed once its voting period is over. It must be
+    /// executed within the execution window, and supersedes open proposals of the same kind.
+    fn execute_proposal(env: Env, id: BytesN<32>, proposal_id: u32);
+
+    /// Returns a group's proposals. Closed ones are dropped when the next one is made.
+    fn get_proposals(env: Env, id: BytesN<32>) -> Vec<Proposal>;
+
     // ============================================================================
     // Token Management
     // ============================================================================
@@ -121,7 +330,64 @@ pub trait AutoShareTrait {
     fn is_token_supported(env: Env, token: Address) -> bool;
 
     /// Distributes a payment among group members based on their percentages.
-    fn distribute(env: Env, id: BytesN<32>, token: Address, amount: i128, sender: Address);
+    /// An optional memo (up to 64 characters) is stored as an external reference, and an
+    /// optional idempotency key rejects replays of the same submission.
+    fn distribute(
+        env: Env,
+        id: BytesN<32>,
+        token: Address,
+        amount: i128,
+        sender: Address,
+        memo: Option<String>,
+        idempotency_key: Option<BytesN<32>>,
+    );
+
+    /// Returns the exact split, usage fee and usage impact of a distribution without
+    /// moving funds, along with the payouts it would make and the amounts it would hold.
+    fn preview_distribution(
+        env: Env,
+        id: BytesN<32>,
+        token: Address,
+        amount: i128,
+    ) -> DistributionPreview;
+
+    /// Distributes several tokens to a group in one call, consuming a single usage.
+    fn distribute_multi(env: Env, id: BytesN<32>, amounts: Vec<TokenAmount>, sender: Address);
+
+    /// Swaps a payment into `token_out` through the configured router and distributes
+    /// the received amount. Reverts if less than `min_amount_out` is received.
+    fn distribute_with_swap(
+        env: Env,
+        id: BytesN<32>,
+        token_in: Address,
+        amount_in: i128,
+        token_out: Address,
+        min_amount_out: i128,
+        sender: Address,
+    ) -> i128;
+
+    /// Returns true if an idempotency key was already used for a group's distribution
+    /// within the retention window.
+    fn is_idempotency_key_used(env: Env, id: BytesN<32>, key: BytesN<32>) -> bool;
+
+    /// Deposits tokens into a group's balance for later distribution.
+    fn deposit(env: Env, id: BytesN<32>, token: Address, amount: i128, depositor: Address);
+
+    /// Distributes the group's full deposited balance of a token. Anyone can call.
+    fn distribute_balance(env: Env, id: BytesN<32>, token: Address, caller: Address) -> i128;
+
+    /// Returns the deposited balance of a token held for a group.
+    fn get_group_balance(env: Env, id: BytesN<32>, token: Address) -> i128;
+
+    /// Distributes payments to several groups in one call, consuming one usage per group.
+    /// With `atomic` set, any failure aborts the batch; otherwise failed checks are reported per
+    /// item, while a payout failing partway through an item aborts the batch.
+    fn distribute_batch(
+        env: Env,
+        items: Vec<BatchDistribution>,
+        sender: Address,
+        atomic: bool,
+    ) -> Vec<BatchDistributionResult>;
 
     // ============================================================================
     // Payment Configuration
@@ -133,19 +399,139 @@ pub trait AutoShareTrait {
     /// Returns the current usage fee.
     fn get_usage_fee(env: Env) -> u32;
 
+    /// Sets the swap router used by `distribute_with_swap` (admin only).
+    fn set_swap_router(env: Env, router: Address, admin: Address);
+
+    /// Returns the configured swap router, if any.
+    fn get_swap_router(env: Env) -> Option<Address>;
+
+    /// Sets the SEP-40 price oracle used to value contributions and the maximum
+    /// age of its prices in seconds (admin only).
+    fn set_price_oracle(env: Env, oracle: Address, max_price_age: u64, admin: Address);
+
+    /// Returns the configured price oracle, if any.
+    fn get_price_oracle(env: Env) -> Option<OracleConfig>;
+
+    /// Replaces the volume discount tiers applied to usage purchases (admin only).
+    fn set_price_tiers(env: Env, tiers: Vec<PriceTier>, admin: Address);
+
+    /// Returns the configured volume discount tiers.
+    fn get_price_tiers(env: Env) -> Vec<PriceTier>;
+
+    /// Returns the unit price and total cost of buying `usage_count` usages.
+    fn get_usage_quote(env: Env, usage_count: u32) -> UsageQuote;
+
     // ============================================================================
     // Subscription Management
     // ============================================================================
 
     /// Tops up a group's subscription with additional usages.
+    /// An optional referrer is credited a share of the usage fee, and an optional
+    /// promo code discounts the purchase. Anyone can pay for an active group;
+    /// an inactive group only accepts the creator or a manager with the `TopUp`
+    /// permission.
     fn topup_subscription(
         env: Env,
         id: BytesN<32>,
         additional_usages: u32,
         payment_token: Address,
         payer: Address,
+        referrer: Option<Address>,
+        promo_code: Option<Bytes>,
     );
 
+    // ============================================================================
+    // Promo Codes & Fee Waivers
+    // ============================================================================
+
+    /// Creates a promo code identified by the SHA-256 hash of the code (admin only).
+    fn create_promo_code(
+        env: Env,
+        code_hash: BytesN<32>,
+        discount_percentage: u32,
+        free_usages: u32,
+        max_redemptions: u32,
+        expires_at: u64,
+        admin: Address,
+    );
+
+    /// Stops a promo code from being redeemed (admin only).
+    fn deactivate_promo_code(env: Env, code_hash: BytesN<32>, admin: Address);
+
+    /// Returns a promo code by the hash of its code.
+    fn get_promo_code(env: Env, code_hash: BytesN<32>) -> PromoCode;
+
+    /// Exempts an address from paying usage fees (admin only).
+    fn add_fee_waiver(env: Env, address: Address, admin: Address);
+
+    /// Removes an address from the fee-waiver allowlist (admin only).
+    fn remove_fee_waiver(env: Env, address: Address, admin: Address);
+
+    /// Checks if an address is exempt from usage fees.
+    fn is_fee_waived(env: Env, address: Address) -> bool;
+
+    // ============================================================================
+    // Referrals
+    // ============================================================================
+
+    /// Sets the percentage of usage fees credited to referrers (admin only).
+    fn set_referral_rate(env: Env, rate: u32, admin: Address);
+
+    /// Returns the percentage of usage fees credited to referrers.
+    fn get_referral_rate(env: Env) -> u32;
+
+    /// Returns the groups referred and rewards earned by a referrer.
+    fn get_referral_stats(env: Env, referrer: Address) -> ReferralStats;
+
+    /// Pays out a referrer's claimable rewards in `token`. Returns the amount paid.
+    fn claim_referral_rewards(env: Env, referrer: Address, token: Address) -> i128;
+
+    // ============================================================================
+    // Sponsorship
+    // ============================================================================
+
+    /// Creates a sponsor pool that pays usage fees for the groups it covers.
+    /// When a covered group has no usages left, `distribute` buys one from the pool.
+    fn create_sponsor_pool(
+        env: Env,
+        pool_id: BytesN<32>,
+        sponsor: Address,
+        token: Address,
+        per_group_limit: u32,
+    );
+
+    /// Adds funds to a sponsor pool.
+    fn fund_sponsor_pool(env: Env, pool_id: BytesN<32>, funder: Address, amount: i128);
+
+    /// Returns unspent pool funds to the sponsor. Only the sponsor can call.
+    fn withdraw_sponsor_funds(env: Env, pool_id: BytesN<32>, sponsor: Address, amount: i128);
+
+    /// Sets the maximum number of usages the pool covers per group. Only the sponsor can call.
+    fn set_sponsor_pool_limit(
+        env: Env,
+        pool_id: BytesN<32>,
+        sponsor: Address,
+        per_group_limit: u32,
+    );
+
+    /// Adds a group to the pool's coverage. Only the sponsor can call.
+    fn sponsor_group(env: Env, pool_id: BytesN<32>, sponsor: Address, group_id: BytesN<32>);
+
+    /// Removes a group from the pool's coverage. Only the sponsor can call.
+    fn unsponsor_group(env: Env, pool_id: BytesN<32>, sponsor: Address, group_id: BytesN<32>);
+
+    /// Covers every group created by `creator`. Only the sponsor can call.
+    fn sponsor_creator(env: Env, pool_id: BytesN<32>, sponsor: Address, creator: Address);
+
+    /// Stops covering groups created by `creator`. Only the sponsor can call.
+    fn unsponsor_creator(env: Env, pool_id: BytesN<32>, sponsor: Address, creator: Address);
+
+    /// Returns a sponsor pool.
+    fn get_sponsor_pool(env: Env, pool_id: BytesN<32>) -> SponsorPool;
+
+    /// Returns how many usages and how much a sponsor pool has spent per group.
+    fn get_sponsor_spending(env: Env, pool_id: BytesN<32>) -> Vec<SponsorSpending>;
+
     // ============================================================================
     // Payment History
     // ============================================================================
@@ -166,6 +552,10 @@ pub trait AutoShareTrait {
     /// Returns all distribution history for a member.
     fn get_member_distributions(env: Env, member: Address) -> Vec<DistributionRecord>;
 
+    /// Returns the most recent distribution of a group carrying the given memo.
+    fn get_distribution_by_reference(env: Env, id: BytesN<32>, memo: String)
+        -> DistributionHistory;
+
     // ============================================================================
     // Usage Tracking
     // ============================================================================
@@ -188,6 +578,78 @@ pub trait AutoShareTrait {
     /// Starts a fundraising campaign for a group.
     fn start_fundraising(env: Env, id: BytesN<32>, caller: Address, target_amount: i128);
 
+    /// Starts a fundraising campaign with a target in the price oracle's reference
+    /// currency (7 decimals). Contributions are valued at the oracle's last price.
+    fn start_valued_fundraising(env: Env, id: BytesN<32>, caller: Address, target_value: i128);
+
     /// Contributes funds to a fundraising campaign.
-    fn contribute(env: Env, id: BytesN<32>, token: Address, amount: i128, contributor: Address);
+    fn contribute(
+        env: Env,
+        id: BytesN<32>,
+        token: Address,
+        amount: i128,
+        contributor: Address,
+        memo: Option<String>,
+    );
+
+    // ============================================================================
+    // Pull Payment Mandates
+    // ============================================================================
+
+    /// Authorizes a group to pull up to a capped amount per period from the payer.
+    /// The payer must also approve this contract on the token.
+    #[allow(clippy::too_many_arguments)]
+    fn create_mandate(
+        env: Env,
+        id: BytesN<32>,
+        payer: Address,
+        token: Address,
+        max_amount_per_period: i128,
+        period_length: u64,
+        expires_at: u64,
+        collector: Option<Address>,
+    );
+
+    /// Revokes a payer's mandate for a group. Only the payer can call.
+    fn revoke_mandate(env: Env, id: BytesN<32>, payer: Address);
+
+    /// Returns a payer's mandate for a group.
+    fn get_mandate(env: Env, id: BytesN<32>, payer: Address) -> Mandate;
+
+    /// Pulls a payment under a mandate and distributes it. Only the group creator
+    /// or the mandate's collector can call.
+    fn collect(env: Env, id: BytesN<32>, payer: Address, amount: i128, collector: Address);
+
+    // ============================================================================
+    // Invoices
+    // ============================================================================
+
+    /// Issues a payment request against a group. The creator or an invoice manager can call.
+    #[allow(clippy::too_many_arguments)]
+    fn create_invoice(
+        env: Env,
+        invoice_id: BytesN<32>,
+        group_id: BytesN<32>,
+        caller: Address,
+        token: Address,
+        amount: i128,
+        payer: Option<Address>,
+        due_date: u64,
+        reference: Option<String>,
+    );
+
+    /// Pays all or part of an open invoice, distributing the payment to the group.
+    fn pay_invoice(env: Env, invoice_id: BytesN<32>, payer: Address, amount: i128);
+
+    /// Cancels an open invoice. The creator or an invoice manager can call.
+    fn cancel_invoice(env: Env, invoice_id: BytesN<32>, caller: Address);
+
+    /// Returns an invoice by id.
+    fn get_invoice(env: Env, invoice_id: BytesN<32>) -> Invoice;
+
+    /// Returns all invoices issued against a group.
+    fn get_group_invoices(env: Env, group_id: BytesN<32>) -> Vec<Invoice>;
+
+    /// Returns a group's unpaid invoices that are past their due date.
+    fn get_overdue_invoices(env: Env, group_id: BytesN<32>) -> Vec<Invoice>;
 }
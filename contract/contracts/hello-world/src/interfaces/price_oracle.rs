@@ -0,0 +1,25 @@
+use soroban_sdk::{contractclient, contracttype, Address, Env, Symbol};
+
+/// Asset identifier used by SEP-40 price oracles.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Asset {
+    Stellar(Address),
+    Other(Symbol),
+}
+
+/// A price quoted in the oracle's base currency with `decimals()` decimals.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PriceData {
+    pub price: i128,
+    pub timestamp: u64,
+}
+
+/// Subset of the SEP-40 price feed interface used to value contributions.
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn decimals(env: Env) -> u32;
+
+    fn lastprice(env: Env, asset: Asset) -> Option<PriceData>;
+}
@@ -0,0 +1,17 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Interface of the DEX router payments are routed through before a split.
+/// The contract approves the router for `amount_in` of `token_in`; the router pulls
+/// it from `to` with `transfer_from`, sends the output to `to` and returns the
+/// amount it sent. Routers should fail rather than send less than `min_amount_out`.
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouter {
+    fn swap_exact_in(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128;
+}
@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Map, String, Vec};
 
 // 1. Declare the foundational modules (Requirement: Modular Structure)
 pub mod base {
@@ -10,6 +10,8 @@ pub mod base {
 
 pub mod interfaces {
     pub mod autoshare;
+    pub mod price_oracle;
+    pub mod swap_router;
 }
 
 // 2. Declare the main logic file where the functions are implemented
@@ -18,6 +20,12 @@ mod autoshare_logic;
 #[cfg(test)]
 pub mod mock_token;
 
+#[cfg(test)]
+pub mod mock_router;
+
+#[cfg(test)]
+pub mod mock_oracle;
+
 #[contract]
 pub struct AutoShareContract;
 
@@ -47,12 +55,87 @@ impl AutoShareContract {
         autoshare_logic::get_paused_status(&env)
     }
 
+    /// Pauses one category of operations while the rest stay available. Only admin can call.
+    pub fn pause_operation(env: Env, admin: Address, category: base::types::PauseCategory) {
+        autoshare_logic::pause_operation(env, admin, category).unwrap();
+    }
+
+    /// Unpauses one category of operations. Only admin can call.
+    pub fn unpause_operation(env: Env, admin: Address, category: base::types::PauseCategory) {
+        autoshare_logic::unpause_operation(env, admin, category).unwrap();
+    }
+
+    /// Returns whether each operation category is paused, either on its own or by the
+    /// global pause.
+    pub fn get_pause_map(env: Env) -> Map<base::types::PauseCategory, bool> {
+        autoshare_logic::get_pause_map(&env)
+    }
+
+    // ============================================================================
+    // Denylist
+    // ============================================================================
+
+    /// Blocks an address from creating groups, joining them, contributing and
+    /// receiving distributions. Only admin can call.
+    pub fn add_to_denylist(env: Env, admin: Address, address: Address, reason: u32) {
+        autoshare_logic::add_to_denylist(env, admin, address, reason).unwrap();
+    }
+
+    /// Lifts the block on an address. Only admin can call.
+    pub fn remove_from_denylist(env: Env, admin: Address, address: Address) {
+        autoshare_logic::remove_from_denylist(env, admin, address).unwrap();
+    }
+
+    /// Returns why and when an address was denylisted, if it is.
+    pub fn get_denylist_entry(env: Env, address: Address) -> Option<base::types::DenylistEntry> {
+        autoshare_logic::get_denylist_entry(&env, &address)
+    }
+
+    /// Returns true if the address is denylisted.
+    pub fn is_denylisted(env: Env, address: Address) -> bool {
+        autoshare_logic::is_denylisted(&env, &address)
+    }
+
+    /// Sets what happens to a denylisted member's share of a distribution. Only admin can call.
+    pub fn set_blocked_share_policy(
+        env: Env,
+        admin: Address,
+        policy: base::types::BlockedSharePolicy,
+    ) {
+        autoshare_logic::set_blocked_share_policy(env, admin, policy).unwrap();
+    }
+
+    /// Returns what happens to a denylisted member's share of a distribution.
+    pub fn get_blocked_share_policy(env: Env) -> base::types::BlockedSharePolicy {
+        autoshare_logic::get_blocked_share_policy(&env)
+    }
+
+    /// Returns the shares escrowed for a denylisted member of a group.
+    pub fn get_escrowed_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128 {
+        autoshare_logic::get_escrowed_payout(env, id, member, token)
+    }
+
+    /// Pays out a member's escrowed shares once they are off the denylist. Only admin
+    /// can call. Returns the amount paid.
+    pub fn release_escrowed_payout(
+        env: Env,
+        admin: Address,
+        id: BytesN<32>,
+        member: Address,
+        token: Address,
+    ) -> i128 {
+        autoshare_logic::release_escrowed_payout(env, admin, id, member, token).unwrap()
+    }
+
     // ============================================================================
     // AutoShare Group Management
     // ============================================================================
 
     /// Creates a new AutoShare plan with payment.
     /// Requirement: create_autoshare should store data, accept payment, and emit an event.
+    /// An optional referrer is credited a share of the usage fee, and an optional
+    /// promo code discounts the purchase.
+    #[allow(clippy::too_many_arguments)]
     pub fn create(
         env: Env,
         id: BytesN<32>,
@@ -60,13 +143,26 @@ impl AutoShareContract {
         creator: Address,
         usage_count: u32,
         payment_token: Address,
+        referrer: Option<Address>,
+        promo_code: Option<Bytes>,
     ) {
-        autoshare_logic::create_autoshare(env, id, name, creator, usage_count, payment_token)
-            .unwrap();
+        autoshare_logic::create_autoshare(
+            env,
+            id,
+            name,
+            creator,
+            usage_count,
+            payment_token,
+            referrer,
+            promo_code,
+        )
+        .unwrap();
     }
 
     /// Update members of an existing AutoShare plan.
-    /// Requirement: Only creator can update. Validates percentages.
+    /// Requirement: Only the creator or a member manager can update. Validates percentages.
+    /// Addresses that are not members yet are invited and must accept first.
+    /// On a timelocked group the change is queued instead of applied.
     pub fn update_members(
         env: Env,
         id: BytesN<32>,
@@ -126,8 +222,8 @@ impl AutoShareContract {
         autoshare_logic::get_group_members(env, id).unwrap()
     }
 
-    /// Adds a member to a group with specified percentage.
-    /// Only the group creator (caller) may add members.
+    /// Invites an address to a group with the specified percentage.
+    /// Only the creator or a member manager may invite; the share applies once accepted.
     pub fn add_group_member(
         env: Env,
         id: BytesN<32>,
@@ -138,18 +234,61 @@ impl AutoShareContract {
         autoshare_logic::add_group_member(env, id, caller, address, percentage).unwrap();
     }
 
-    /// Removes a single member from a group. Only the creator can call; group must be active.
+    /// Removes a single member from a group. Only the creator or a member manager can call; group must be active.
     /// After removal, remaining percentages may not sum to 100; call update_members to set a valid split.
+    /// Also withdraws a pending invitation for the address.
     pub fn remove_group_member(env: Env, id: BytesN<32>, caller: Address, member_address: Address) {
         autoshare_logic::remove_group_member(env, id, caller, member_address).unwrap();
     }
 
-    /// Deactivates a group. Only the creator can deactivate.
+    /// Accepts an invitation to a group. Called by the invitee.
+    pub fn accept_invitation(env: Env, id: BytesN<32>, invitee: Address) {
+        autoshare_logic::accept_invitation(env, id, invitee).unwrap();
+    }
+
+    /// Declines an invitation to a group. Called by the invitee.
+    pub fn decline_invitation(env: Env, id: BytesN<32>, invitee: Address) {
+        autoshare_logic::decline_invitation(env, id, invitee).unwrap();
+    }
+
+    /// Returns the invitations of a group that have not been answered yet.
+    pub fn get_group_invitations(env: Env, id: BytesN<32>) -> Vec<base::types::Invitation> {
+        autoshare_logic::get_group_invitations(env, id).unwrap()
+    }
+
+    /// Sets the token a member is paid in and the address their payouts go to.
+    /// Called by the member themselves; `None` clears a preference.
+    pub fn set_payout_preferences(
+        env: Env,
+        id: BytesN<32>,
+        member: Address,
+        preferred_token: Option<Address>,
+        payout_address: Option<Address>,
+    ) {
+        autoshare_logic::set_payout_preferences(env, id, member, preferred_token, payout_address)
+            .unwrap();
+    }
+
+    /// Removes the caller from a group. Their share is redistributed or left for the
+    /// creator to rebalance, depending on the group's leave policy.
+    pub fn leave_group(env: Env, id: BytesN<32>, member: Address) {
+        autoshare_logic::leave_group(env, id, member).unwrap();
+    }
+
+    /// Moves a member's memberships, open invitations, earnings, pending payouts and
+    /// distribution history to a new address, which also takes over the member's weight
+    /// on open proposals. Both addresses must authorize. Fails while the member has a
+    /// vote on an open proposal.
+    pub fn change_payout_address(env: Env, member: Address, new_address: Address) {
+        autoshare_logic::change_payout_address(env, member, new_address).unwrap();
+    }
+
+    /// Deactivates a group. Only the creator or a manager with `Deactivate` can call.
     pub fn deactivate_group(env: Env, id: BytesN<32>, caller: Address) {
         autoshare_logic::deactivate_group(env, id, caller).unwrap();
     }
 
-    /// Activates a group. Only the creator can activate.
+    /// Activates a group. Only the creator or a manager with `Deactivate` can call.
     pub fn activate_group(env: Env, id: BytesN<32>, caller: Address) {
         autoshare_logic::activate_group(env, id, caller).unwrap();
     }
@@ -166,6 +305,7 @@ impl AutoShareContract {
 
     /// Permanently deletes a group. Only creator or admin can delete.
     /// Group must be deactivated first and have 0 remaining usages.
+    /// Deposited balances must be distributed first; carried-forward dust goes to the creator.
     pub fn delete_group(env: Env, id: BytesN<32>, caller: Address) {
         autoshare_logic::delete_group(env, id, caller).unwrap();
     }
@@ -180,7 +320,8 @@ impl AutoShareContract {
         autoshare_logic::transfer_admin(env, current_admin, new_admin).unwrap();
     }
 
-    /// Withdraws tokens from the contract. Only admin can call.
+    /// Withdraws tokens from the contract. Only admin can call, and only the part of
+    /// the balance not reserved for users can be withdrawn.
     pub fn withdraw(env: Env, admin: Address, token: Address, amount: i128, recipient: Address) {
         autoshare_logic::withdraw(env, admin, token, amount, recipient).unwrap();
     }
@@ -190,6 +331,213 @@ impl AutoShareContract {
         autoshare_logic::get_contract_balance(env, token)
     }
 
+    /// Returns how much of the contract's balance is held for users: group deposits,
+    /// sponsor pools, referral rewards, pending payouts and dust credit.
+    pub fn get_reserved_balance(env: Env, token: Address) -> i128 {
+        autoshare_logic::get_reserved_balance(env, token)
+    }
+
+    // ============================================================================
+    // Group Settings
+    // ============================================================================
+
+    /// Returns the group's configurable policies.
+    pub fn get_group_settings(env: Env, id: BytesN<32>) -> base::types::GroupSettings {
+        autoshare_logic::get_group_settings(env, id)
+    }
+
+    /// Sets how rounding dust is handled for a group. Only the creator can call.
+    pub fn set_rounding_policy(
+        env: Env,
+        id: BytesN<32>,
+        policy: base::types::RoundingPolicy,
+        caller: Address,
+    ) {
+        autoshare_logic::set_rounding_policy(env, id, policy, caller).unwrap();
+    }
+
+    /// Sets what happens to a member's share when they leave. Only the creator can call.
+    pub fn set_leave_policy(
+        env: Env,
+        id: BytesN<32>,
+        policy: base::types::LeavePolicy,
+        caller: Address,
+    ) {
+        autoshare_logic::set_leave_policy(env, id, policy, caller).unwrap();
+    }
+
+    /// Sets how long split changes from `update_members` and executed proposals
+    /// wait before applying. Only the creator can call, and the delay can only be
+    /// raised. A timelock cannot be enabled while invitations are pending.
+    pub fn set_split_timelock(env: Env, id: BytesN<32>, delay: u64, caller: Address) {
+        autoshare_logic::set_split_timelock(env, id, delay, caller).unwrap();
+    }
+
+    /// Returns the rounding dust carried forward for a group's next distribution of a token.
+    pub fn get_dust_credit(env: Env, id: BytesN<32>, token: Address) -> i128 {
+        autoshare_logic::get_dust_credit(env, id, token)
+    }
+
+    /// Sets the minimum payout for a group; smaller shares are held as pending
+    /// balances. Only the creator can call.
+    pub fn set_min_payout(env: Env, id: BytesN<32>, min_payout: i128, caller: Address) {
+        autoshare_logic::set_min_payout(env, id, min_payout, caller).unwrap();
+    }
+
+    /// Returns a member's pending balance for a group and token.
+    pub fn get_pending_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128 {
+        autoshare_logic::get_pending_payout(env, id, member, token)
+    }
+
+    /// Pays out a member's pending balance on demand, to their payout address and
+    /// preferred token. Returns the amount paid.
+    pub fn claim_pending_payout(env: Env, id: BytesN<32>, member: Address, token: Address) -> i128 {
+        autoshare_logic::claim_pending_payout(env, id, member, token).unwrap()
+    }
+
+    // ============================================================================
+    // Group Freeze
+    // ============================================================================
+
+    /// Sets the guardian that can freeze a group alongside the admin. Only the creator can call.
+    pub fn set_group_guardian(
+        env: Env,
+        id: BytesN<32>,
+        guardian: Option<Address>,
+        caller: Address,
+    ) {
+        autoshare_logic::set_group_guardian(env, id, guardian, caller).unwrap();
+    }
+
+    /// Freezes a group's distributions, contributions, payout claims and membership,
+    /// payout, ownership and guardian changes for `duration` seconds. Only the
+    /// group's guardian or the admin can call.
+    pub fn freeze_group(env: Env, id: BytesN<32>, caller: Address, reason: u32, duration: u64) {
+        autoshare_logic::freeze_group(env, id, caller, reason, duration).unwrap();
+    }
+
+    /// Lifts a group's freeze early. Only the group's guardian or the admin can call.
+    pub fn unfreeze_group(env: Env, id: BytesN<32>, caller: Address) {
+        autoshare_logic::unfreeze_group(env, id, caller).unwrap();
+    }
+
+    /// Returns a group's freeze while it is in effect.
+    pub fn get_group_freeze(env: Env, id: BytesN<32>) -> Option<base::types::GroupFreeze> {
+        autoshare_logic::get_group_freeze(env, id)
+    }
+
+    /// Checks if a group is currently frozen.
+    pub fn is_group_frozen(env: Env, id: BytesN<32>) -> bool {
+        autoshare_logic::is_group_frozen(env, id)
+    }
+
+    // ============================================================================
+    // Split Timelock
+    // ============================================================================
+
+    /// Returns the split change waiting for a group's timelock to expire, if any.
+    pub fn get_pending_split_change(
+        env: Env,
+        id: BytesN<32>,
+    ) -> Option<base::types::PendingSplitChange> {
+        autoshare_logic::get_pending_split_change(env, id)
+    }
+
+    /// Cancels a queued split change. Only the creator or a member manager can call.
+    pub fn cancel_split_change(env: Env, id: BytesN<32>, caller: Address) {
+        autoshare_logic::cancel_split_change(env, id, caller).unwrap();
+    }
+
+    /// Applies a queued split change once its delay has passed. Anyone can call.
+    /// Distributions apply a due change themselves and keep the current split if
+    /// it can no longer apply.
+    pub fn apply_split_change(env: Env, id: BytesN<32>) {
+        autoshare_logic::apply_split_change(env, id).unwrap();
+    }
+
+    // ============================================================================
+    // Ownership & Managers
+    // ============================================================================
+
+    /// Offers ownership of a group to a new owner. Only the creator can call;
+    /// the transfer completes when the new owner accepts.
+    pub fn transfer_group_ownership(env: Env, id: BytesN<32>, caller: Address, new_owner: Address) {
+        autoshare_logic::transfer_group_ownership(env, id, caller, new_owner).unwrap();
+    }
+
+    /// Accepts a pending ownership transfer. Called by the new owner.
+    pub fn accept_group_ownership(env: Env, id: BytesN<32>, new_owner: Address) {
+        autoshare_logic::accept_group_ownership(env, id, new_owner).unwrap();
+    }
+
+    /// Returns the address a group's ownership has been offered to, if any.
+    pub fn get_pending_group_owner(env: Env, id: BytesN<32>) -> Option<Address> {
+        autoshare_logic::get_pending_group_owner(env, id)
+    }
+
+    /// Sets a co-manager's permissions; an empty list removes them. Only the creator can call.
+    pub fn set_group_manager(
+        env: Env,
+        id: BytesN<32>,
+        caller: Address,
+        manager: Address,
+        permissions: Vec<base::types::ManagerPermission>,
+    ) {
+        autoshare_logic::set_group_manager(env, id, caller, manager, permissions).unwrap();
+    }
+
+    /// Returns a group's co-managers and their permissions.
+    pub fn get_group_managers(env: Env, id: BytesN<32>) -> Vec<base::types::GroupManager> {
+        autoshare_logic::get_group_managers(env, id)
+    }
+
+    // ============================================================================
+    // Governance
+    // ============================================================================
+
+    /// Puts a group's members, name and activation under member vote. Only the
+    /// creator can call, and governance cannot be turned off afterwards.
+    pub fn enable_governance(
+        env: Env,
+        id: BytesN<32>,
+        caller: Address,
+        config: base::types::GovernanceConfig,
+    ) {
+        autoshare_logic::enable_governance(env, id, caller, config).unwrap();
+    }
+
+    /// Returns a group's voting rules, if it is governed.
+    pub fn get_governance(env: Env, id: BytesN<32>) -> Option<base::types::GovernanceConfig> {
+        autoshare_logic::get_governance(env, id)
+    }
+
+    /// Proposes a change to a governed group. Returns the proposal id. A group can have
+    /// at most ten proposals open at once.
+    pub fn propose(
+        env: Env,
+        id: BytesN<32>,
+        proposer: Address,
+        action: base::types::ProposalAction,
+    ) -> u32 {
+        autoshare_logic::propose(env, id, proposer, action).unwrap()
+    }
+
+    /// Votes on a proposal, weighted by the voter's percentage when it was proposed.
+    pub fn vote(env: Env, id: BytesN<32>, proposal_id: u32, voter: Address, support: bool) {
+        autoshare_logic::vote(env, id, proposal_id, voter, support).unwrap();
+    }
+
+    /// Applies a proposal that passed once its voting period is over. It must be
+    /// executed within the execution window, and supersedes open proposals of the same kind.
+    pub fn execute_proposal(env: Env, id: BytesN<32>, proposal_id: u32) {
+        autoshare_logic::execute_proposal(env, id, proposal_id).unwrap();
+    }
+
+    /// Returns a group's proposals. Closed ones are dropped when the next one is made.
+    pub fn get_proposals(env: Env, id: BytesN<32>) -> Vec<base::types::Proposal> {
+        autoshare_logic::get_proposals(env, id)
+    }
+
     // ============================================================================
     // Token Management
     // ============================================================================
@@ -215,8 +563,95 @@ impl AutoShareContract {
     }
 
     /// Distributes a payment among group members based on their percentages.
-    pub fn distribute(env: Env, id: BytesN<32>, token: Address, amount: i128, sender: Address) {
-        autoshare_logic::distribute(env, id, token, amount, sender).unwrap();
+    /// An optional memo (up to 64 characters) is stored as an external reference, and an
+    /// optional idempotency key rejects replays of the same submission.
+    pub fn distribute(
+        env: Env,
+        id: BytesN<32>,
+        token: Address,
+        amount: i128,
+        sender: Address,
+        memo: Option<String>,
+        idempotency_key: Option<BytesN<32>>,
+    ) {
+        autoshare_logic::distribute(env, id, token, amount, sender, memo, idempotency_key).unwrap();
+    }
+
+    /// Returns the exact split, usage fee and usage impact of a distribution without
+    /// moving funds, along with the payouts it would make and the amounts it would hold.
+    pub fn preview_distribution(
+        env: Env,
+        id: BytesN<32>,
+        token: Address,
+        amount: i128,
+    ) -> base::types::DistributionPreview {
+        autoshare_logic::preview_distribution(env, id, token, amount).unwrap()
+    }
+
+    /// Distributes several tokens to a group in one call, consuming a single usage.
+    pub fn distribute_multi(
+        env: Env,
+        id: BytesN<32>,
+        amounts: Vec<base::types::TokenAmount>,
+        sender: Address,
+    ) {
+        autoshare_logic::distribute_multi(env, id, amounts, sender).unwrap();
+    }
+
+    /// Swaps a payment into `token_out` through the configured router and distributes
+    /// the received amount. Reverts if less than `min_amount_out` is received.
+    pub fn distribute_with_swap(
+        env: Env,
+        id: BytesN<32>,
+        token_in: Address,
+        amount_in: i128,
+        token_out: Address,
+        min_amount_out: i128,
+        sender: Address,
+    ) -> i128 {
+        autoshare_logic::distribute_with_swap(
+            env,
+            id,
+            token_in,
+            amount_in,
+            token_out,
+            min_amount_out,
+            sender,
+        )
+        .unwrap()
+    }
+
+    /// Returns true if an idempotency key was already used for a group's distribution
+    /// within the retention window.
+    pub fn is_idempotency_key_used(env: Env, id: BytesN<32>, key: BytesN<32>) -> bool {
+        autoshare_logic::is_idempotency_key_used(env, id, key)
+    }
+
+    /// Deposits tokens into a group's balance for later distribution.
+    pub fn deposit(env: Env, id: BytesN<32>, token: Address, amount: i128, depositor: Address) {
+        autoshare_logic::deposit(env, id, token, amount, depositor).unwrap();
+    }
+
+    /// Distributes the group's full deposited balance of a token. Anyone can call.
+    pub fn distribute_balance(env: Env, id: BytesN<32>, token: Address, caller: Address) -> i128 {
+        autoshare_logic::distribute_balance(env, id, token, caller).unwrap()
+    }
+
+    /// Returns the deposited balance of a token held for a group.
+    pub fn get_group_balance(env: Env, id: BytesN<32>, token: Address) -> i128 {
+        autoshare_logic::get_group_balance(env, id, token)
+    }
+
+    /// Distributes payments to several groups in one call, consuming one usage per group.
+    /// With `atomic` set, any failure aborts the batch; otherwise failed checks are reported per
+    /// item, while a payout failing partway through an item aborts the batch.
+    pub fn distribute_batch(
+        env: Env,
+        items: Vec<base::types::BatchDistribution>,
+        sender: Address,
+        atomic: bool,
+    ) -> Vec<base::types::BatchDistributionResult> {
+        autoshare_logic::distribute_batch(env, items, sender, atomic).unwrap()
     }
 
     // ============================================================================
@@ -233,22 +668,216 @@ impl AutoShareContract {
         autoshare_logic::get_usage_fee(env)
     }
 
+    /// Sets the swap router used by `distribute_with_swap` (admin only).
+    pub fn set_swap_router(env: Env, router: Address, admin: Address) {
+        autoshare_logic::set_swap_router(env, router, admin).unwrap();
+    }
+
+    /// Returns the configured swap router, if any.
+    pub fn get_swap_router(env: Env) -> Option<Address> {
+        autoshare_logic::get_swap_router(env)
+    }
+
+    /// Sets the SEP-40 price oracle used to value contributions and the maximum
+    /// age of its prices in seconds (admin only).
+    pub fn set_price_oracle(env: Env, oracle: Address, max_price_age: u64, admin: Address) {
+        autoshare_logic::set_price_oracle(env, oracle, max_price_age, admin).unwrap();
+    }
+
+    /// Returns the configured price oracle, if any.
+    pub fn get_price_oracle(env: Env) -> Option<base::types::OracleConfig> {
+        autoshare_logic::get_price_oracle(env)
+    }
+
+    /// Replaces the volume discount tiers applied to usage purchases (admin only).
+    pub fn set_price_tiers(env: Env, tiers: Vec<base::types::PriceTier>, admin: Address) {
+        autoshare_logic::set_price_tiers(env, tiers, admin).unwrap();
+    }
+
+    /// Returns the configured volume discount tiers.
+    pub fn get_price_tiers(env: Env) -> Vec<base::types::PriceTier> {
+        autoshare_logic::get_price_tiers(env)
+    }
+
+    /// Returns the unit price and total cost of buying `usage_count` usages.
+    pub fn get_usage_quote(env: Env, usage_count: u32) -> base::types::UsageQuote {
+        autoshare_logic::get_usage_quote(env, usage_count)
+    }
+
     // ============================================================================
     // Subscription Management
     // ============================================================================
 
     /// Tops up a group's subscription with additional usages.
+    /// An optional referrer is credited a share of the usage fee, and an optional
+    /// promo code discounts the purchase. Anyone can pay for an active group;
+    /// an inactive group only accepts the creator or a manager with the `TopUp`
+    /// permission.
     pub fn topup_subscription(
         env: Env,
         id: BytesN<32>,
         additional_usages: u32,
         payment_token: Address,
         payer: Address,
+        referrer: Option<Address>,
+        promo_code: Option<Bytes>,
+    ) {
+        autoshare_logic::topup_subscription(
+            env,
+            id,
+            additional_usages,
+            payment_token,
+            payer,
+            referrer,
+            promo_code,
+        )
+        .unwrap();
+    }
+
+    // ============================================================================
+    // Promo Codes & Fee Waivers
+    // ============================================================================
+
+    /// Creates a promo code identified by the SHA-256 hash of the code (admin only).
+    pub fn create_promo_code(
+        env: Env,
+        code_hash: BytesN<32>,
+        discount_percentage: u32,
+        free_usages: u32,
+        max_redemptions: u32,
+        expires_at: u64,
+        admin: Address,
+    ) {
+        autoshare_logic::create_promo_code(
+            env,
+            code_hash,
+            discount_percentage,
+            free_usages,
+            max_redemptions,
+            expires_at,
+            admin,
+        )
+        .unwrap();
+    }
+
+    /// Stops a promo code from being redeemed (admin only).
+    pub fn deactivate_promo_code(env: Env, code_hash: BytesN<32>, admin: Address) {
+        autoshare_logic::deactivate_promo_code(env, code_hash, admin).unwrap();
+    }
+
+    /// Returns a promo code by the hash of its code.
+    pub fn get_promo_code(env: Env, code_hash: BytesN<32>) -> base::types::PromoCode {
+        autoshare_logic::get_promo_code(env, code_hash).unwrap()
+    }
+
+    /// Exempts an address from paying usage fees (admin only).
+    pub fn add_fee_waiver(env: Env, address: Address, admin: Address) {
+        autoshare_logic::add_fee_waiver(env, address, admin).unwrap();
+    }
+
+    /// Removes an address from the fee-waiver allowlist (admin only).
+    pub fn remove_fee_waiver(env: Env, address: Address, admin: Address) {
+        autoshare_logic::remove_fee_waiver(env, address, admin).unwrap();
+    }
+
+    /// Checks if an address is exempt from usage fees.
+    pub fn is_fee_waived(env: Env, address: Address) -> bool {
+        autoshare_logic::is_fee_waived(env, address)
+    }
+
+    // ============================================================================
+    // Referrals
+    // ============================================================================
+
+    /// Sets the percentage of usage fees credited to referrers (admin only).
+    pub fn set_referral_rate(env: Env, rate: u32, admin: Address) {
+        autoshare_logic::set_referral_rate(env, rate, admin).unwrap();
+    }
+
+    /// Returns the percentage of usage fees credited to referrers.
+    pub fn get_referral_rate(env: Env) -> u32 {
+        autoshare_logic::get_referral_rate(env)
+    }
+
+    /// Returns the groups referred and rewards earned by a referrer.
+    pub fn get_referral_stats(env: Env, referrer: Address) -> base::types::ReferralStats {
+        autoshare_logic::get_referral_stats(env, referrer)
+    }
+
+    /// Pays out a referrer's claimable rewards in `token`. Returns the amount paid.
+    pub fn claim_referral_rewards(env: Env, referrer: Address, token: Address) -> i128 {
+        autoshare_logic::claim_referral_rewards(env, referrer, token).unwrap()
+    }
+
+    // ============================================================================
+    // Sponsorship
+    // ============================================================================
+
+    /// Creates a sponsor pool that pays usage fees for the groups it covers.
+    pub fn create_sponsor_pool(
+        env: Env,
+        pool_id: BytesN<32>,
+        sponsor: Address,
+        token: Address,
+        per_group_limit: u32,
     ) {
-        autoshare_logic::topup_subscription(env, id, additional_usages, payment_token, payer)
+        autoshare_logic::create_sponsor_pool(env, pool_id, sponsor, token, per_group_limit)
             .unwrap();
     }
 
+    /// Adds funds to a sponsor pool.
+    pub fn fund_sponsor_pool(env: Env, pool_id: BytesN<32>, funder: Address, amount: i128) {
+        autoshare_logic::fund_sponsor_pool(env, pool_id, funder, amount).unwrap();
+    }
+
+    /// Returns unspent pool funds to the sponsor. Only the sponsor can call.
+    pub fn withdraw_sponsor_funds(env: Env, pool_id: BytesN<32>, sponsor: Address, amount: i128) {
+        autoshare_logic::withdraw_sponsor_funds(env, pool_id, sponsor, amount).unwrap();
+    }
+
+    /// Sets the maximum number of usages the pool covers per group. Only the sponsor can call.
+    pub fn set_sponsor_pool_limit(
+        env: Env,
+        pool_id: BytesN<32>,
+        sponsor: Address,
+        per_group_limit: u32,
+    ) {
+        autoshare_logic::set_sponsor_pool_limit(env, pool_id, sponsor, per_group_limit).unwrap();
+    }
+
+    /// Adds a group to the pool's coverage. Only the sponsor can call.
+    pub fn sponsor_group(env: Env, pool_id: BytesN<32>, sponsor: Address, group_id: BytesN<32>) {
+        autoshare_logic::sponsor_group(env, pool_id, sponsor, group_id).unwrap();
+    }
+
+    /// Removes a group from the pool's coverage. Only the sponsor can call.
+    pub fn unsponsor_group(env: Env, pool_id: BytesN<32>, sponsor: Address, group_id: BytesN<32>) {
+        autoshare_logic::unsponsor_group(env, pool_id, sponsor, group_id).unwrap();
+    }
+
+    /// Covers every group created by `creator`. Only the sponsor can call.
+    pub fn sponsor_creator(env: Env, pool_id: BytesN<32>, sponsor: Address, creator: Address) {
+        autoshare_logic::sponsor_creator(env, pool_id, sponsor, creator).unwrap();
+    }
+
+    /// Stops covering groups created by `creator`. Only the sponsor can call.
+    pub fn unsponsor_creator(env: Env, pool_id: BytesN<32>, sponsor: Address, creator: Address) {
+        autoshare_logic::unsponsor_creator(env, pool_id, sponsor, creator).unwrap();
+    }
+
+    /// Returns a sponsor pool.
+    pub fn get_sponsor_pool(env: Env, pool_id: BytesN<32>) -> base::types::SponsorPool {
+        autoshare_logic::get_sponsor_pool(env, pool_id).unwrap()
+    }
+
+    /// Returns how many usages and how much a sponsor pool has spent per group.
+    pub fn get_sponsor_spending(
+        env: Env,
+        pool_id: BytesN<32>,
+    ) -> Vec<base::types::SponsorSpending> {
+        autoshare_logic::get_sponsor_spending(env, pool_id)
+    }
+
     // ============================================================================
     // Payment History
     // ============================================================================
@@ -283,6 +912,15 @@ impl AutoShareContract {
         autoshare_logic::get_member_distributions(env, member)
     }
 
+    /// Returns the most recent distribution of a group carrying the given memo.
+    pub fn get_distribution_by_reference(
+        env: Env,
+        id: BytesN<32>,
+        memo: String,
+    ) -> base::types::DistributionHistory {
+        autoshare_logic::get_distribution_by_reference(env, id, memo).unwrap()
+    }
+
     // ============================================================================
     // Usage Tracking
     // ============================================================================
@@ -328,6 +966,12 @@ impl AutoShareContract {
         autoshare_logic::start_fundraising(env, id, caller, target_amount).unwrap();
     }
 
+    /// Starts a fundraising campaign with a target in the price oracle's reference
+    /// currency (7 decimals). Contributions are valued at the oracle's last price.
+    pub fn start_valued_fundraising(env: Env, id: BytesN<32>, caller: Address, target_value: i128) {
+        autoshare_logic::start_valued_fundraising(env, id, caller, target_value).unwrap();
+    }
+
     /// Contributes funds to a fundraising campaign.
     pub fn contribute(
         env: Env,
@@ -335,8 +979,103 @@ impl AutoShareContract {
         token: Address,
         amount: i128,
         contributor: Address,
+        memo: Option<String>,
+    ) {
+        autoshare_logic::contribute(env, id, token, amount, contributor, memo).unwrap();
+    }
+
+    // ============================================================================
+    // Pull Payment Mandates
+    // ============================================================================
+
+    /// Authorizes a group to pull up to a capped amount per period from the payer.
+    /// The payer must also approve this contract on the token.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_mandate(
+        env: Env,
+        id: BytesN<32>,
+        payer: Address,
+        token: Address,
+        max_amount_per_period: i128,
+        period_length: u64,
+        expires_at: u64,
+        collector: Option<Address>,
     ) {
-        autoshare_logic::contribute(env, id, token, amount, contributor).unwrap();
+        autoshare_logic::create_mandate(
+            env,
+            id,
+            payer,
+            token,
+            max_amount_per_period,
+            period_length,
+            expires_at,
+            collector,
+        )
+        .unwrap();
+    }
+
+    /// Revokes a payer's mandate for a group. Only the payer can call.
+    pub fn revoke_mandate(env: Env, id: BytesN<32>, payer: Address) {
+        autoshare_logic::revoke_mandate(env, id, payer).unwrap();
+    }
+
+    /// Returns a payer's mandate for a group.
+    pub fn get_mandate(env: Env, id: BytesN<32>, payer: Address) -> base::types::Mandate {
+        autoshare_logic::get_mandate(env, id, payer).unwrap()
+    }
+
+    /// Pulls a payment under a mandate and distributes it. Only the group creator
+    /// or the mandate's collector can call.
+    pub fn collect(env: Env, id: BytesN<32>, payer: Address, amount: i128, collector: Address) {
+        autoshare_logic::collect(env, id, payer, amount, collector).unwrap();
+    }
+
+    // ============================================================================
+    // Invoices
+    // ============================================================================
+
+    /// Issues a payment request against a group. The creator or an invoice manager can call.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_invoice(
+        env: Env,
+        invoice_id: BytesN<32>,
+        group_id: BytesN<32>,
+        caller: Address,
+        token: Address,
+        amount: i128,
+        payer: Option<Address>,
+        due_date: u64,
+        reference: Option<String>,
+    ) {
+        autoshare_logic::create_invoice(
+            env, invoice_id, group_id, caller, token, amount, payer, due_date, reference,
+        )
+        .unwrap();
+    }
+
+    /// Pays all or part of an open invoice, distributing the payment to the group.
+    pub fn pay_invoice(env: Env, invoice_id: BytesN<32>, payer: Address, amount: i128) {
+        autoshare_logic::pay_invoice(env, invoice_id, payer, amount).unwrap();
+    }
+
+    /// Cancels an open invoice. The creator or an invoice manager can call.
+    pub fn cancel_invoice(env: Env, invoice_id: BytesN<32>, caller: Address) {
+        autoshare_logic::cancel_invoice(env, invoice_id, caller).unwrap();
+    }
+
+    /// Returns an invoice by id.
+    pub fn get_invoice(env: Env, invoice_id: BytesN<32>) -> base::types::Invoice {
+        autoshare_logic::get_invoice(env, invoice_id).unwrap()
+    }
+
+    /// Returns all invoices issued against a group.
+    pub fn get_group_invoices(env: Env, group_id: BytesN<32>) -> Vec<base::types::Invoice> {
+        autoshare_logic::get_group_invoices(env, group_id)
+    }
+
+    /// Returns a group's unpaid invoices that are past their due date.
+    pub fn get_overdue_invoices(env: Env, group_id: BytesN<32>) -> Vec<base::types::Invoice> {
+        autoshare_logic::get_overdue_invoices(env, group_id)
     }
 }
 
@@ -396,3 +1135,107 @@ mod max_members_test;
 #[cfg(test)]
 #[path = "tests/group_count_property_test.rs"]
 mod group_count_property_test;
+
+#[cfg(test)]
+#[path = "tests/price_tiers_test.rs"]
+mod price_tiers_test;
+
+#[cfg(test)]
+#[path = "tests/sponsorship_test.rs"]
+mod sponsorship_test;
+
+#[cfg(test)]
+#[path = "tests/referral_test.rs"]
+mod referral_test;
+
+#[cfg(test)]
+#[path = "tests/promo_code_test.rs"]
+mod promo_code_test;
+
+#[cfg(test)]
+#[path = "tests/distribute_batch_test.rs"]
+mod distribute_batch_test;
+
+#[cfg(test)]
+#[path = "tests/group_balance_test.rs"]
+mod group_balance_test;
+
+#[cfg(test)]
+#[path = "tests/mandate_test.rs"]
+mod mandate_test;
+
+#[cfg(test)]
+#[path = "tests/memo_test.rs"]
+mod memo_test;
+
+#[cfg(test)]
+#[path = "tests/invoice_test.rs"]
+mod invoice_test;
+
+#[cfg(test)]
+#[path = "tests/idempotency_test.rs"]
+mod idempotency_test;
+
+#[cfg(test)]
+#[path = "tests/distribute_multi_test.rs"]
+mod distribute_multi_test;
+
+#[cfg(test)]
+#[path = "tests/preview_test.rs"]
+mod preview_test;
+
+#[cfg(test)]
+#[path = "tests/rounding_policy_test.rs"]
+mod rounding_policy_test;
+
+#[cfg(test)]
+#[path = "tests/min_payout_test.rs"]
+mod min_payout_test;
+
+#[cfg(test)]
+#[path = "tests/swap_test.rs"]
+mod swap_test;
+
+#[cfg(test)]
+#[path = "tests/payout_preferences_test.rs"]
+mod payout_preferences_test;
+
+#[cfg(test)]
+#[path = "tests/oracle_fundraising_test.rs"]
+mod oracle_fundraising_test;
+
+#[cfg(test)]
+#[path = "tests/member_self_service_test.rs"]
+mod member_self_service_test;
+
+#[cfg(test)]
+#[path = "tests/invitation_test.rs"]
+mod invitation_test;
+
+#[cfg(test)]
+#[path = "tests/governance_test.rs"]
+mod governance_test;
+
+#[cfg(test)]
+#[path = "tests/ownership_test.rs"]
+mod ownership_test;
+
+#[cfg(test)]
+#[path = "tests/split_timelock_test.rs"]
+mod split_timelock_test;
+
+#[cfg(test)]
+#[path = "tests/group_freeze_test.rs"]
+mod group_freeze_test;
+
+#[cfg(test)]
+#[path = "tests/granular_pause_test.rs"]
+mod granular_pause_test;
+
+#[cfg(test)]
+#[path = "tests/denylist_test.rs"]
+mod denylist_test;
+
+#[cfg(test)]
+#[path = "tests/reserved_balance_test.rs"]
+mod reserved_balance_test;
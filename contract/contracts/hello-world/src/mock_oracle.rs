@@ -0,0 +1,33 @@
+use crate::interfaces::price_oracle::{Asset, PriceData};
+use soroban_sdk::{contract, contractimpl, Env, Symbol};
+
+/// SEP-40 price feed for tests. Prices are set directly and quoted with
+/// 14 decimals unless changed.
+#[contract]
+pub struct MockOracle;
+
+#[contractimpl]
+impl MockOracle {
+    pub fn set_price(env: Env, asset: Asset, price: i128, timestamp: u64) {
+        env.storage()
+            .instance()
+            .set(&asset, &PriceData { price, timestamp });
+    }
+
+    pub fn set_decimals(env: Env, decimals: u32) {
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "decimals"), &decimals);
+    }
+
+    pub fn decimals(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "decimals"))
+            .unwrap_or(14)
+    }
+
+    pub fn lastprice(env: Env, asset: Asset) -> Option<PriceData> {
+        env.storage().instance().get(&asset)
+    }
+}
@@ -0,0 +1,63 @@
+use soroban_sdk::{contract, contractimpl, symbol_short, token, Address, Env};
+
+/// Fixed-rate router for tests. It pays out of its own token balance, so tests
+/// mint the output token to it first. Like a real router it fails rather than
+/// return less than `min_amount_out`.
+#[contract]
+pub struct MockRouter;
+
+#[contractimpl]
+impl MockRouter {
+    /// Sets the rate as `numerator / denominator` units of `token_out` per unit of `token_in`.
+    pub fn set_rate(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        numerator: i128,
+        denominator: i128,
+    ) {
+        env.storage()
+            .instance()
+            .set(&(token_in, token_out), &(numerator, denominator));
+    }
+
+    /// Makes the router deliver `shortfall` fewer units than it reports, past
+    /// its own minimum check, like a faulty or malicious router would.
+    pub fn set_shortfall(env: Env, shortfall: i128) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("shortfall"), &shortfall);
+    }
+
+    pub fn swap_exact_in(
+        env: Env,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_amount_out: i128,
+        to: Address,
+    ) -> i128 {
+        let (numerator, denominator): (i128, i128) = env
+            .storage()
+            .instance()
+            .get(&(token_in.clone(), token_out.clone()))
+            .expect("No rate for pair");
+        let router = env.current_contract_address();
+        token::TokenClient::new(&env, &token_in).transfer_from(&router, &to, &router, &amount_in);
+        let amount_out = amount_in * numerator / denominator;
+        if amount_out < min_amount_out {
+            panic!("Insufficient output amount");
+        }
+        let shortfall: i128 = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("shortfall"))
+            .unwrap_or(0);
+        token::TokenClient::new(&env, &token_out).transfer(
+            &env.current_contract_address(),
+            &to,
+            &(amount_out - shortfall),
+        );
+        amount_out
+    }
+}
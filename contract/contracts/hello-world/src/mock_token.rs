@@ -17,6 +17,31 @@ fn bump_persistent<K: soroban_sdk::IntoVal<Env, soroban_sdk::Val>>(env: &Env, ke
     }
 }
 
+fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+    let from_key = (from,);
+    let mut from_balance: i128 = env.storage().persistent().get(&from_key).unwrap_or(0);
+    if from_balance > 0 {
+        bump_persistent(env, &from_key);
+    }
+
+    if from_balance < amount {
+        panic!("Insufficient balance");
+    }
+
+    from_balance -= amount;
+    env.storage().persistent().set(&from_key, &from_balance);
+    bump_persistent(env, &from_key);
+
+    let to_key = (to,);
+    let mut to_balance: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
+    if to_balance > 0 {
+        bump_persistent(env, &to_key);
+    }
+    to_balance += amount;
+    env.storage().persistent().set(&to_key, &to_balance);
+    bump_persistent(env, &to_key);
+}
+
 #[contractimpl]
 impl MockToken {
     pub fn initialize(env: Env, admin: Address, decimal: u32, name: String, symbol: String) {
@@ -69,29 +94,50 @@ impl MockToken {
         if amount <= 0 {
             panic!("Invalid amount");
         }
+        move_balance(&env, &from, &to, amount);
+    }
 
-        let from_key = (&from,);
-        let mut from_balance: i128 = env.storage().persistent().get(&from_key).unwrap_or(0);
-        if from_balance > 0 {
-            bump_persistent(&env, &from_key);
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        _expiration_ledger: u32,
+    ) {
+        from.require_auth();
+        if amount < 0 {
+            panic!("Invalid amount");
         }
 
-        if from_balance < amount {
-            panic!("Insufficient balance");
+        let key = (&from, &spender);
+        env.storage().persistent().set(&key, &amount);
+        bump_persistent(&env, &key);
+    }
+
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let key = (&from, &spender);
+        let allowance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if allowance > 0 {
+            bump_persistent(&env, &key);
         }
+        allowance
+    }
 
-        from_balance -= amount;
-        env.storage().persistent().set(&from_key, &from_balance);
-        bump_persistent(&env, &from_key);
+    pub fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+        if amount <= 0 {
+            panic!("Invalid amount");
+        }
 
-        let to_key = (&to,);
-        let mut to_balance: i128 = env.storage().persistent().get(&to_key).unwrap_or(0);
-        if to_balance > 0 {
-            bump_persistent(&env, &to_key);
+        let key = (&from, &spender);
+        let allowance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if allowance < amount {
+            panic!("Insufficient allowance");
         }
-        to_balance += amount;
-        env.storage().persistent().set(&to_key, &to_balance);
-        bump_persistent(&env, &to_key);
+        env.storage().persistent().set(&key, &(allowance - amount));
+        bump_persistent(&env, &key);
+
+        move_balance(&env, &from, &to, amount);
     }
 
     pub fn balance(env: Env, id: Address) -> i128 {
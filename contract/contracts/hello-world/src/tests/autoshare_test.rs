@@ -1,6 +1,6 @@
 use crate::base::types::GroupMember;
 use crate::mock_token::{MockToken, MockTokenClient};
-use crate::test_utils::{create_test_group, setup_test_env};
+use crate::test_utils::{accept_invitations, create_test_group, setup_test_env};
 use crate::{AutoShareContract, AutoShareContractClient};
 
 /*use soroban_sdk::testutils::Events;*/
@@ -15,8 +15,9 @@ fn create_helper(
 ) {
     let token = test_env.mock_tokens.get(0).unwrap().clone();
     crate::test_utils::mint_tokens(&test_env.env, &token, creator, 10000000);
-    client.create(id, name, creator, &1u32, &token);
+    client.create(id, name, creator, &1u32, &token, &None, &None);
     client.update_members(id, creator, members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, id);
 }
 
 #[test]
@@ -31,10 +32,14 @@ fn test_create_and_get_success() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 60,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 40,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
     let name = String::from_str(&test_env.env, "Test Group");
@@ -74,6 +79,8 @@ fn test_duplicate_id_fails() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
 
@@ -126,6 +133,8 @@ fn test_get_all_groups_multiple() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
 
@@ -182,6 +191,8 @@ fn test_get_groups_by_creator_multiple() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
 
@@ -230,6 +241,8 @@ fn test_create_fails_invalid_percentage() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 50, // Sum = 50 != 100
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -249,6 +262,8 @@ fn test_create_fails_empty_name() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -268,6 +283,8 @@ fn test_create_fails_whitespace_name() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -290,6 +307,8 @@ fn test_create_fails_too_long_name() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -325,10 +344,14 @@ fn test_create_fails_duplicate_member() {
     members.push_back(GroupMember {
         address: member_summary.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member_summary, // Duplicate
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -348,6 +371,8 @@ fn test_update_members_success() {
     initial_members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &initial_members, &test_env);
@@ -362,13 +387,18 @@ fn test_update_members_success() {
     new_members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     new_members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     client.update_members(&id, &creator, &new_members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     // Verify update
     let updated_res = client.get(&id);
@@ -391,6 +421,8 @@ fn test_update_members_unauthorized() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -413,6 +445,8 @@ fn test_update_members_invalid_percentage() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -421,6 +455,8 @@ fn test_update_members_invalid_percentage() {
     bad_members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 90,
+        preferred_token: None,
+        payout_address: None,
     });
 
     client.update_members(&id, &creator, &bad_members);
@@ -441,6 +477,8 @@ fn test_is_group_member() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -460,6 +498,8 @@ fn test_is_group_member_false() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
 
@@ -487,6 +527,8 @@ fn test_is_group_member_true() {
     members.push_back(GroupMember {
         address: member.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     let token = test_env.mock_tokens.get(0).unwrap().clone();
 
@@ -529,14 +571,20 @@ fn test_get_group_members_multiple() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 40,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 30,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member3.clone(),
         percentage: 30,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let token = test_env.mock_tokens.get(0).unwrap().clone();
@@ -574,6 +622,8 @@ fn test_get_group_members_empty() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -612,10 +662,14 @@ fn test_add_group_member_success() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -625,35 +679,49 @@ fn test_add_group_member_success() {
     updated_members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     updated_members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     // Add a placeholder third member with 34% to make 100%
     let placeholder = Address::generate(&test_env.env);
     updated_members.push_back(GroupMember {
         address: placeholder.clone(),
         percentage: 34,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&id, &creator, &updated_members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     // Remove placeholder and add real third member
     let mut final_members_vec = Vec::new(&test_env.env);
     final_members_vec.push_back(GroupMember {
         address: member1.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     final_members_vec.push_back(GroupMember {
         address: member2.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     let member3 = Address::generate(&test_env.env);
     final_members_vec.push_back(GroupMember {
         address: member3.clone(),
         percentage: 34,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&id, &creator, &final_members_vec);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     // Verify all three members exist
     let final_members = client.get_group_members(&id);
@@ -677,6 +745,8 @@ fn test_add_duplicate_member() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -714,6 +784,8 @@ fn test_add_member_invalid_total_percentage() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -738,6 +810,8 @@ fn test_add_multiple_members_sequentially() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -747,6 +821,8 @@ fn test_add_multiple_members_sequentially() {
     updated_members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 25,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Add second member with 25% to make 50%
@@ -754,6 +830,8 @@ fn test_add_multiple_members_sequentially() {
     updated_members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 25,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Add third member with 25% to make 75%
@@ -761,6 +839,8 @@ fn test_add_multiple_members_sequentially() {
     updated_members.push_back(GroupMember {
         address: member3.clone(),
         percentage: 25,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Add fourth member with 25% to make 100%
@@ -768,9 +848,12 @@ fn test_add_multiple_members_sequentially() {
     updated_members.push_back(GroupMember {
         address: member4.clone(),
         percentage: 25,
+        preferred_token: None,
+        payout_address: None,
     });
 
     client.update_members(&id, &creator, &updated_members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     // Verify all four members exist
     let final_members = client.get_group_members(&id);
@@ -800,10 +883,14 @@ fn test_add_member_to_inactive_group() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -813,17 +900,24 @@ fn test_add_member_to_inactive_group() {
     updated_members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     updated_members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     let placeholder = Address::generate(&test_env.env);
     updated_members.push_back(GroupMember {
         address: placeholder.clone(),
         percentage: 34,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&id, &creator, &updated_members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     // Deactivate the group
     client.deactivate_group(&id, &creator);
@@ -834,20 +928,27 @@ fn test_add_member_to_inactive_group() {
     final_members_vec.push_back(GroupMember {
         address: member1.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     final_members_vec.push_back(GroupMember {
         address: member2.clone(),
         percentage: 33,
+        preferred_token: None,
+        payout_address: None,
     });
     let member3 = Address::generate(&test_env.env);
     final_members_vec.push_back(GroupMember {
         address: member3.clone(),
         percentage: 34,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Reactivate to update, then deactivate again
     client.activate_group(&id, &creator);
     client.update_members(&id, &creator, &final_members_vec);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
     client.deactivate_group(&id, &creator);
 
     // Verify member was added and group is inactive
@@ -876,14 +977,20 @@ fn test_remove_group_member_success() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 40,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 35,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member3.clone(),
         percentage: 25,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -915,10 +1022,14 @@ fn test_remove_group_member_unauthorized() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -943,10 +1054,14 @@ fn test_remove_group_member_inactive() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -971,10 +1086,14 @@ fn test_remove_group_member_not_found() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1000,10 +1119,14 @@ fn test_remove_group_member_paused() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1027,10 +1150,14 @@ fn test_remove_group_member_then_update_members() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1045,8 +1172,11 @@ fn test_remove_group_member_then_update_members() {
     single_member.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&id, &creator, &single_member);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &id);
 
     let final_members = client.get_group_members(&id);
     assert_eq!(final_members.len(), 1);
@@ -1071,6 +1201,8 @@ fn test_group_created_as_active() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1092,6 +1224,8 @@ fn test_creator_can_deactivate_group() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1116,6 +1250,8 @@ fn test_creator_can_activate_group() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1144,6 +1280,8 @@ fn test_update_group_name_success() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1170,6 +1308,8 @@ fn test_update_group_name_unauthorized() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1192,6 +1332,8 @@ fn test_update_group_name_inactive_group() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1215,6 +1357,8 @@ fn test_update_group_name_empty_name() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1237,6 +1381,8 @@ fn test_updating_inactive_group_fails() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1249,10 +1395,14 @@ fn test_updating_inactive_group_fails() {
     new_members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     new_members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
 
     client.update_members(&id, &creator, &new_members);
@@ -1272,6 +1422,8 @@ fn test_viewing_inactive_group_works() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1301,6 +1453,8 @@ fn test_non_creator_cannot_deactivate() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1324,6 +1478,8 @@ fn test_non_creator_cannot_activate() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1349,6 +1505,8 @@ fn test_deactivating_already_inactive_group_fails() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1374,6 +1532,8 @@ fn test_activating_already_active_group_fails() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1422,6 +1582,8 @@ fn test_get_all_groups_includes_inactive() {
     members.push_back(GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Create two groups
@@ -1457,6 +1619,8 @@ fn test_is_group_member_works_on_inactive_group() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_helper(&client, &id, &name, &creator, &members, &test_env);
@@ -1957,7 +2121,15 @@ fn test_create_group_with_payment() {
     let name = String::from_str(&test_env.env, "Paid Group");
     let usage_count = 50u32;
 
-    client.create(&id, &name, &creator, &usage_count, &token_address);
+    client.create(
+        &id,
+        &name,
+        &creator,
+        &usage_count,
+        &token_address,
+        &None,
+        &None,
+    );
 
     let details = client.get(&id);
     assert_eq!(details.usage_count, usage_count);
@@ -0,0 +1,267 @@
+use crate::base::types::BlockedSharePolicy;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, create_test_group, member, mint_tokens,
+    setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN};
+
+const REASON_SANCTIONED: u32 = 1;
+
+struct DenylistSetup {
+    id: BytesN<32>,
+    token: Address,
+    member1: Address,
+    member2: Address,
+    sender: Address,
+}
+
+/// A 60/40 group and a funded sender.
+fn setup(test_env: &TestEnv) -> DenylistSetup {
+    let env = &test_env.env;
+    let group = create_group_with_members(test_env, &[60, 40], 5);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &group.token, &sender, 1_000);
+
+    DenylistSetup {
+        id: group.id,
+        token: group.token,
+        member1: group.members.get(0).unwrap(),
+        member2: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+#[test]
+fn test_denylist_entry_records_reason() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let address = Address::generate(env);
+    client.add_to_denylist(&test_env.admin, &address, &REASON_SANCTIONED);
+    assert!(client.is_denylisted(&address));
+    let entry = client.get_denylist_entry(&address).unwrap();
+    assert_eq!(entry.reason, REASON_SANCTIONED);
+    assert_eq!(entry.added_by, test_env.admin);
+    assert_eq!(entry.added_at, env.ledger().timestamp());
+
+    client.remove_from_denylist(&test_env.admin, &address);
+    assert!(!client.is_denylisted(&address));
+    assert_eq!(client.get_denylist_entry(&address), None);
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_distribution_to_blocked_member_rejected_by_default() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    assert_eq!(
+        client.get_blocked_share_policy(),
+        BlockedSharePolicy::Reject
+    );
+    client.add_to_denylist(&test_env.admin, &s.member2, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+}
+
+#[test]
+fn test_blocked_share_escrowed_until_removed() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_blocked_share_policy(&test_env.admin, &BlockedSharePolicy::Escrow);
+    client.add_to_denylist(&test_env.admin, &s.member2, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+
+    assert_balance(env, &s.token, &s.member1, 60);
+    assert_balance(env, &s.token, &s.member2, 0);
+    assert_eq!(client.get_escrowed_payout(&s.id, &s.member2, &s.token), 40);
+    assert_eq!(client.get_pending_payout(&s.id, &s.member2, &s.token), 0);
+    assert!(client
+        .try_claim_pending_payout(&s.id, &s.member2, &s.token)
+        .is_err());
+    assert!(client
+        .try_release_escrowed_payout(&test_env.admin, &s.id, &s.member2, &s.token)
+        .is_err());
+
+    client.remove_from_denylist(&test_env.admin, &s.member2);
+    // Removal alone does not hand the escrow back; the admin releases it
+    assert!(client
+        .try_claim_pending_payout(&s.id, &s.member2, &s.token)
+        .is_err());
+    client.release_escrowed_payout(&test_env.admin, &s.id, &s.member2, &s.token);
+    assert_balance(env, &s.token, &s.member2, 40);
+    assert_eq!(client.get_escrowed_payout(&s.id, &s.member2, &s.token), 0);
+}
+
+#[test]
+fn test_blocked_member_cannot_move_escrow_to_new_address() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_blocked_share_policy(&test_env.admin, &BlockedSharePolicy::Escrow);
+    client.add_to_denylist(&test_env.admin, &s.member2, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+
+    let fresh = Address::generate(env);
+    assert!(client
+        .try_change_payout_address(&s.member2, &fresh)
+        .is_err());
+    assert!(client
+        .try_claim_pending_payout(&s.id, &fresh, &s.token)
+        .is_err());
+    assert_balance(env, &s.token, &fresh, 0);
+    assert_eq!(client.get_escrowed_payout(&s.id, &s.member2, &s.token), 40);
+}
+
+#[test]
+fn test_escrow_stays_with_blocked_payout_address_owner() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    // The member is clear but their payout address is blocked
+    let payout_address = Address::generate(env);
+    client.set_payout_preferences(&s.id, &s.member2, &None, &Some(payout_address.clone()));
+    client.set_blocked_share_policy(&test_env.admin, &BlockedSharePolicy::Escrow);
+    client.add_to_denylist(&test_env.admin, &payout_address, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+
+    let fresh = Address::generate(env);
+    client.change_payout_address(&s.member2, &fresh);
+    assert_eq!(client.get_escrowed_payout(&s.id, &fresh, &s.token), 0);
+    assert_eq!(client.get_escrowed_payout(&s.id, &s.member2, &s.token), 40);
+    assert!(client
+        .try_claim_pending_payout(&s.id, &fresh, &s.token)
+        .is_err());
+}
+
+#[test]
+fn test_blocked_share_redistributed_to_other_members() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_blocked_share_policy(&test_env.admin, &BlockedSharePolicy::Redistribute);
+    client.add_to_denylist(&test_env.admin, &s.member2, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+
+    assert_balance(env, &s.token, &s.member1, 100);
+    assert_balance(env, &s.token, &s.member2, 0);
+    // The stored split is left untouched
+    assert_eq!(
+        client.get_group_members(&s.id).get(1).unwrap().percentage,
+        40
+    );
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_blocked_payout_address_counts_as_blocked() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let payout_address = Address::generate(env);
+    client.add_to_denylist(&test_env.admin, &payout_address, &REASON_SANCTIONED);
+    client.set_payout_preferences(&s.id, &s.member1, &None, &Some(payout_address));
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_blocked_sender_cannot_distribute() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.add_to_denylist(&test_env.admin, &s.sender, &REASON_SANCTIONED);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_blocked_contributor_cannot_contribute() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env);
+
+    client.start_fundraising(&s.id, &creator, &1_000);
+    client.add_to_denylist(&test_env.admin, &s.sender, &REASON_SANCTIONED);
+    client.contribute(&s.id, &s.token, &100, &s.sender, &None);
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_blocked_address_cannot_be_added_as_member() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env);
+
+    let blocked = Address::generate(env);
+    client.add_to_denylist(&test_env.admin, &blocked, &REASON_SANCTIONED);
+    client.update_members(
+        &s.id,
+        &creator,
+        &vec![env, member(&s.member1, 50), member(&blocked, 50)],
+    );
+}
+
+#[test]
+#[should_panic] // AddressDenylisted
+fn test_blocked_address_cannot_create_group() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    client.add_to_denylist(&test_env.admin, &creator, &REASON_SANCTIONED);
+    create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &vec![env, member(&Address::generate(env), 100)],
+        1,
+        &token,
+    );
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_admin_can_manage_denylist() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+
+    client.add_to_denylist(&creator, &Address::generate(env), &REASON_SANCTIONED);
+}
+
+#[test]
+#[should_panic(expected = "AddressDenylisted")]
+fn test_blocked_member_cannot_claim_pending_payout() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env);
+
+    client.set_min_payout(&s.id, &100, &creator);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+    assert_eq!(client.get_pending_payout(&s.id, &s.member2, &s.token), 40);
+
+    client.add_to_denylist(&test_env.admin, &s.member2, &REASON_SANCTIONED);
+    client.claim_pending_payout(&s.id, &s.member2, &s.token);
+}
@@ -0,0 +1,176 @@
+use crate::base::errors::Error;
+use crate::base::types::BatchDistribution;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Vec};
+
+fn single_member_group(test_env: &TestEnv, usages: u32) -> (BytesN<32>, Address) {
+    let group = create_group_with_members(test_env, &[100], usages);
+    (group.id, group.members.get(0).unwrap())
+}
+
+fn item(group_id: &BytesN<32>, token: &Address, amount: i128) -> BatchDistribution {
+    BatchDistribution {
+        group_id: group_id.clone(),
+        token: token.clone(),
+        amount,
+    }
+}
+
+#[test]
+fn test_distribute_batch_settles_every_group() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id1, member1) = single_member_group(&test_env, 2);
+    let (id2, member2) = single_member_group(&test_env, 3);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 1_000);
+
+    let mut items = Vec::new(env);
+    items.push_back(item(&id1, &token, 300));
+    items.push_back(item(&id2, &token, 500));
+    let results = client.distribute_batch(&items, &sender, &true);
+
+    assert_eq!(results.len(), 2);
+    assert!(results.get(0).unwrap().success);
+    assert!(results.get(1).unwrap().success);
+
+    assert_balance(env, &token, &sender, 200);
+    assert_balance(env, &token, &member1, 300);
+    assert_balance(env, &token, &member2, 500);
+    assert_eq!(client.get_remaining_usages(&id1), 1);
+    assert_eq!(client.get_remaining_usages(&id2), 2);
+    assert_eq!(client.get_group_distributions(&id1).len(), 1);
+    assert_eq!(client.get_group_distributions(&id2).len(), 1);
+}
+
+#[test]
+fn test_distribute_batch_same_group_twice_consumes_two_usages() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, member) = single_member_group(&test_env, 2);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let mut items = Vec::new(env);
+    items.push_back(item(&id, &token, 100));
+    items.push_back(item(&id, &token, 100));
+    client.distribute_batch(&items, &sender, &true);
+
+    assert_balance(env, &token, &member, 200);
+    assert_eq!(client.get_remaining_usages(&id), 0);
+}
+
+#[test]
+#[should_panic] // NoUsagesRemaining
+fn test_distribute_batch_atomic_aborts_on_failure() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _) = single_member_group(&test_env, 1);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let mut items = Vec::new(env);
+    items.push_back(item(&id, &token, 100));
+    items.push_back(item(&id, &token, 100));
+    client.distribute_batch(&items, &sender, &true);
+}
+
+#[test]
+fn test_distribute_batch_non_atomic_reports_and_refunds_failures() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, member) = single_member_group(&test_env, 1);
+    let missing = BytesN::from_array(env, &[99u8; 32]);
+    let unsupported = Address::generate(env);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 1_000);
+
+    let mut items = Vec::new(env);
+    items.push_back(item(&id, &token, 100));
+    items.push_back(item(&id, &token, 200));
+    items.push_back(item(&missing, &token, 300));
+    items.push_back(item(&id, &unsupported, 400));
+    items.push_back(item(&id, &token, 0));
+    let results = client.distribute_batch(&items, &sender, &false);
+
+    assert!(results.get(0).unwrap().success);
+    assert_eq!(
+        results.get(1).unwrap().error_code,
+        Error::NoUsagesRemaining as u32
+    );
+    assert_eq!(results.get(2).unwrap().error_code, Error::NotFound as u32);
+    assert_eq!(
+        results.get(3).unwrap().error_code,
+        Error::UnsupportedToken as u32
+    );
+    assert_eq!(
+        results.get(4).unwrap().error_code,
+        Error::InvalidAmount as u32
+    );
+    assert!(!results.get(4).unwrap().success);
+
+    // Only the successful item leaves the sender's balance
+    assert_balance(env, &token, &sender, 900);
+    assert_balance(env, &token, &member, 100);
+    // Nothing but the usage fee stays in the contract
+    assert_eq!(client.get_contract_balance(&token), 10);
+}
+
+#[test]
+fn test_distribute_batch_non_atomic_reports_settlement_failures() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id1, member1) = single_member_group(&test_env, 1);
+    let (id2, member2) = single_member_group(&test_env, 2);
+    client.add_to_denylist(&test_env.admin, &member2, &1);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 1_000);
+
+    let mut items = Vec::new(env);
+    items.push_back(item(&id1, &token, 300));
+    items.push_back(item(&id2, &token, 500));
+    let results = client.distribute_batch(&items, &sender, &false);
+
+    assert!(results.get(0).unwrap().success);
+    assert_eq!(
+        results.get(1).unwrap().error_code,
+        Error::AddressDenylisted as u32
+    );
+
+    assert_balance(env, &token, &sender, 700);
+    assert_balance(env, &token, &member1, 300);
+    assert_balance(env, &token, &member2, 0);
+    // The failed item's usage is left for a later distribution
+    assert_eq!(client.get(&id2).usage_count, 2);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_distribute_batch_rejects_empty_batch() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let sender = test_env.users.get(1).unwrap();
+    client.distribute_batch(&Vec::new(env), &sender, &true);
+}
@@ -0,0 +1,154 @@
+use crate::base::types::TokenAmount;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, deploy_mock_token, mint_tokens, setup_test_env,
+    TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
+
+struct MultiSetup {
+    id: BytesN<32>,
+    usdc: Address,
+    reward: Address,
+    member1: Address,
+    member2: Address,
+    sender: Address,
+}
+
+fn token_amount(token: &Address, amount: i128) -> TokenAmount {
+    TokenAmount {
+        token: token.clone(),
+        amount,
+    }
+}
+
+fn amounts(env: &Env, entries: &[TokenAmount]) -> Vec<TokenAmount> {
+    let mut list = Vec::new(env);
+    for entry in entries {
+        list.push_back(entry.clone());
+    }
+    list
+}
+
+/// A 70/30 group and a sender holding both the default token and a second
+/// supported reward token.
+fn setup(test_env: &TestEnv) -> MultiSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let reward = deploy_mock_token(
+        env,
+        &String::from_str(env, "Reward"),
+        &String::from_str(env, "RWD"),
+    );
+    client.add_supported_token(&reward, &test_env.admin);
+
+    let group = create_group_with_members(test_env, &[70, 30], 2);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &group.token, &sender, 1_000);
+    mint_tokens(env, &reward, &sender, 1_000);
+
+    MultiSetup {
+        id: group.id,
+        usdc: group.token,
+        reward,
+        member1: group.members.get(0).unwrap(),
+        member2: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+#[test]
+fn test_distribute_multi_splits_every_token_with_one_usage() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let payout = amounts(
+        env,
+        &[token_amount(&s.usdc, 1_000), token_amount(&s.reward, 500)],
+    );
+    client.distribute_multi(&s.id, &payout, &s.sender);
+
+    assert_balance(env, &s.usdc, &s.member1, 700);
+    assert_balance(env, &s.usdc, &s.member2, 300);
+    assert_balance(env, &s.reward, &s.member1, 350);
+    assert_balance(env, &s.reward, &s.member2, 150);
+    assert_eq!(client.get_remaining_usages(&s.id), 1);
+
+    let history = client.get_group_distributions(&s.id);
+    assert_eq!(history.len(), 1);
+    let entry = history.get(0).unwrap();
+    assert_eq!(entry.token_amounts, payout);
+    assert_eq!(entry.token, s.usdc);
+    assert_eq!(entry.total_amount, 1_000);
+    assert_eq!(entry.member_amounts.len(), 4);
+    assert_eq!(entry.member_amounts.get(2).unwrap().token, s.reward);
+    assert_eq!(entry.member_amounts.get(2).unwrap().amount, 350);
+
+    // Member history keeps one record per token
+    let records = client.get_member_distributions(&s.member1);
+    assert_eq!(records.len(), 2);
+    assert_eq!(records.get(1).unwrap().token, s.reward);
+}
+
+#[test]
+fn test_single_token_distribution_lists_its_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(
+        entry.token_amounts,
+        amounts(env, &[token_amount(&s.usdc, 100)])
+    );
+    assert_eq!(entry.member_amounts.get(0).unwrap().token, s.usdc);
+}
+
+#[test]
+#[should_panic] // UnsupportedToken
+fn test_distribute_multi_rejects_unsupported_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let unsupported = Address::generate(env);
+    let payout = amounts(
+        env,
+        &[token_amount(&s.usdc, 100), token_amount(&unsupported, 100)],
+    );
+    client.distribute_multi(&s.id, &payout, &s.sender);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_distribute_multi_rejects_duplicate_tokens() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let payout = amounts(
+        env,
+        &[token_amount(&s.usdc, 100), token_amount(&s.usdc, 100)],
+    );
+    client.distribute_multi(&s.id, &payout, &s.sender);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_distribute_multi_rejects_empty_list() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.distribute_multi(&s.id, &Vec::new(env), &s.sender);
+}
@@ -20,14 +20,20 @@ fn test_distribute_splits_payment_and_decrements_usage() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 50,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 30,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member3.clone(),
         percentage: 20,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let creator = test_env.users.get(0).unwrap().clone();
@@ -42,7 +48,7 @@ fn test_distribute_splits_payment_and_decrements_usage() {
     let token_client = MockTokenClient::new(&env, &token);
     let sender_start = token_client.balance(&sender);
 
-    client.distribute(&id, &token, &amount, &sender);
+    client.distribute(&id, &token, &amount, &sender, &None, &None);
 
     // Verify member balances
     assert_balance(&env, &token, &member1, 500);
@@ -97,10 +103,14 @@ fn test_distribute_fails_when_group_inactive() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 60,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 40,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let creator = test_env.users.get(0).unwrap().clone();
@@ -110,5 +120,5 @@ fn test_distribute_fails_when_group_inactive() {
 
     let sender = test_env.users.get(1).unwrap().clone();
     mint_tokens(&env, &token, &sender, 500);
-    client.distribute(&id, &token, &500, &sender);
+    client.distribute(&id, &token, &500, &sender, &None, &None);
 }
@@ -18,10 +18,14 @@ fn test_get_member_earnings_tracks_cumulative_totals() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 70,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(GroupMember {
         address: member2.clone(),
         percentage: 30,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let creator = test_env.users.get(0).unwrap().clone();
@@ -35,7 +39,7 @@ fn test_get_member_earnings_tracks_cumulative_totals() {
 
     // First distribution: 1000 tokens
     mint_tokens(&env, &token, &sender, 1000);
-    client.distribute(&id, &token, &1000, &sender);
+    client.distribute(&id, &token, &1000, &sender, &None, &None);
 
     // Verify first distribution earnings
     assert_eq!(client.get_member_earnings(&member1, &id), 700);
@@ -43,7 +47,7 @@ fn test_get_member_earnings_tracks_cumulative_totals() {
 
     // Second distribution: 500 tokens
     mint_tokens(&env, &token, &sender, 500);
-    client.distribute(&id, &token, &500, &sender);
+    client.distribute(&id, &token, &500, &sender, &None, &None);
 
     // Verify cumulative earnings
     assert_eq!(client.get_member_earnings(&member1, &id), 700 + 350);
@@ -1,4 +1,4 @@
-use crate::test_utils::setup_test_env;
+use crate::test_utils::{accept_invitations, setup_test_env};
 use crate::AutoShareContractClient;
 use soroban_sdk::testutils::Address as _;
 use soroban_sdk::{Address, BytesN, Vec};
@@ -28,6 +28,8 @@ fn test_contribute_success() {
         &creator,
         &10,
         &token,
+        &None,
+        &None,
     );
 
     // 2. Add Members
@@ -35,12 +37,17 @@ fn test_contribute_success() {
     members.push_back(crate::base::types::GroupMember {
         address: member1.clone(),
         percentage: 60,
+        preferred_token: None,
+        payout_address: None,
     });
     members.push_back(crate::base::types::GroupMember {
         address: member2.clone(),
         percentage: 40,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&group_id, &creator, &members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &group_id);
 
     // 3. Start Fundraising
     let target_amount = 1000i128;
@@ -57,7 +64,7 @@ fn test_contribute_success() {
         contribution_amount,
     );
 
-    client.contribute(&group_id, &token, &contribution_amount, &contributor);
+    client.contribute(&group_id, &token, &contribution_amount, &contributor, &None);
 
     // 5. Verify State
     let status = client.get_fundraising_status(&group_id);
@@ -108,14 +115,19 @@ fn test_contribute_completes_fundraising() {
         &creator,
         &10,
         &token,
+        &None,
+        &None,
     );
 
     let mut members = Vec::new(&test_env.env);
     members.push_back(crate::base::types::GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&group_id, &creator, &members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &group_id);
 
     let target_amount = 1000i128;
     client.start_fundraising(&group_id, &creator, &target_amount);
@@ -124,7 +136,7 @@ fn test_contribute_completes_fundraising() {
     crate::test_utils::fund_user_with_tokens(&test_env.env, &token, &contributor, target_amount);
 
     // Contribute exact amount
-    client.contribute(&group_id, &token, &target_amount, &contributor);
+    client.contribute(&group_id, &token, &target_amount, &contributor, &None);
 
     let status = client.get_fundraising_status(&group_id);
     assert_eq!(status.total_raised, target_amount);
@@ -153,10 +165,12 @@ fn test_contribute_fundraising_not_active() {
         &creator,
         &10,
         &token,
+        &None,
+        &None,
     );
 
     // Contribute without starting fundraising
-    client.contribute(&group_id, &token, &100, &contributor);
+    client.contribute(&group_id, &token, &100, &contributor, &None);
 }
 
 #[test]
@@ -181,9 +195,11 @@ fn test_contribute_paused() {
         &creator,
         &10,
         &token,
+        &None,
+        &None,
     );
     client.start_fundraising(&group_id, &creator, &1000);
 
     client.pause(admin);
-    client.contribute(&group_id, &token, &100, &contributor);
+    client.contribute(&group_id, &token, &100, &contributor, &None);
 }
@@ -30,6 +30,8 @@ fn test_get_fundraising_status_existing() {
         target_amount: 1000,
         total_raised: 500,
         is_active: true,
+        target_value: 0,
+        total_raised_value: 0,
     };
 
     let key = DataKey::GroupFundraising(group_id.clone());
@@ -76,6 +78,7 @@ fn test_get_contributions_populated() {
         token: token.clone(),
         amount,
         timestamp,
+        memo: None,
     };
 
     // Simulate contribution by manually setting storage
@@ -27,9 +27,25 @@ fn test_get_groups_by_member() {
     let name2 = String::from_str(env, "Group 2");
     let usage_count2 = 10u32;
 
-    client.create(&id1, &name1, &creator1, &usage_count1, &token_id);
-
-    client.create(&id2, &name2, &creator2, &usage_count2, &token_id);
+    client.create(
+        &id1,
+        &name1,
+        &creator1,
+        &usage_count1,
+        &token_id,
+        &None,
+        &None,
+    );
+
+    client.create(
+        &id2,
+        &name2,
+        &creator2,
+        &usage_count2,
+        &token_id,
+        &None,
+        &None,
+    );
 
     // Initial check: member1 is not in any group
     let groups = client.get_groups_by_member(&member1);
@@ -37,12 +53,14 @@ fn test_get_groups_by_member() {
 
     // Add member1 to group 1
     client.add_group_member(&id1, &creator1, &member1, &100);
+    client.accept_invitation(&id1, &member1);
     let groups = client.get_groups_by_member(&member1);
     assert_eq!(groups.len(), 1);
     assert_eq!(groups.get(0).unwrap().id, id1);
 
     // Add member1 to group 2
     client.add_group_member(&id2, &creator2, &member1, &100);
+    client.accept_invitation(&id2, &member1);
     let groups = client.get_groups_by_member(&member1);
     assert_eq!(groups.len(), 2);
 
@@ -52,8 +70,11 @@ fn test_get_groups_by_member() {
     new_members.push_back(GroupMember {
         address: admin.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
     client.update_members(&id1, &creator1, &new_members);
+    client.accept_invitation(&id1, &admin);
 
     // admin should now see group 1
     let admin_groups = client.get_groups_by_member(&admin);
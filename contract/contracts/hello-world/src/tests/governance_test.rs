@@ -0,0 +1,381 @@
+use crate::base::types::{GovernanceConfig, ProposalAction};
+use crate::test_utils::{create_group_with_members, member, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, String, Vec,
+};
+
+const VOTING_PERIOD: u64 = 100;
+const EXECUTION_WINDOW: u64 = 50;
+
+struct GovernedGroup {
+    id: BytesN<32>,
+    creator: Address,
+    /// Members holding 50%, 30% and 20%.
+    members: [Address; 3],
+}
+
+/// A 50/30/20 group requiring 60% quorum and a simple majority.
+fn setup(test_env: &TestEnv) -> GovernedGroup {
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[50, 30, 20], 1);
+
+    client.enable_governance(
+        &group.id,
+        &group.creator,
+        &GovernanceConfig {
+            quorum: 60,
+            threshold: 51,
+            voting_period: VOTING_PERIOD,
+            execution_window: EXECUTION_WINDOW,
+        },
+    );
+
+    GovernedGroup {
+        id: group.id,
+        creator: group.creator,
+        members: [
+            group.members.get(0).unwrap(),
+            group.members.get(1).unwrap(),
+            group.members.get(2).unwrap(),
+        ],
+    }
+}
+
+fn rebalance(test_env: &TestEnv, g: &GovernedGroup) -> ProposalAction {
+    let mut split = Vec::new(&test_env.env);
+    split.push_back(member(&g.members[0], 40));
+    split.push_back(member(&g.members[1], 40));
+    split.push_back(member(&g.members[2], 20));
+    ProposalAction::UpdateMembers(split)
+}
+
+fn end_voting(test_env: &TestEnv) {
+    test_env
+        .env
+        .ledger()
+        .with_mut(|li| li.timestamp += VOTING_PERIOD);
+}
+
+#[test]
+fn test_passed_proposal_updates_members() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[1], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+    client.vote(&g.id, &proposal_id, &g.members[1], &false);
+
+    let proposal = client.get_proposals(&g.id).get(proposal_id).unwrap();
+    assert_eq!(proposal.votes_for, 50);
+    assert_eq!(proposal.votes_against, 30);
+
+    end_voting(&test_env);
+    client.execute_proposal(&g.id, &proposal_id);
+
+    let members = client.get_group_members(&g.id);
+    assert_eq!(members.get(0).unwrap().percentage, 40);
+    assert_eq!(members.get(1).unwrap().percentage, 40);
+    assert!(
+        client
+            .get_proposals(&g.id)
+            .get(proposal_id)
+            .unwrap()
+            .executed
+    );
+}
+
+#[test]
+fn test_proposals_cover_name_and_activation() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let name = String::from_str(env, "Band Royalties");
+    let rename = client.propose(&g.id, &g.creator, &ProposalAction::UpdateName(name.clone()));
+    let pause = client.propose(&g.id, &g.members[0], &ProposalAction::SetActive(false));
+    for proposal_id in [rename, pause] {
+        client.vote(&g.id, &proposal_id, &g.members[0], &true);
+        client.vote(&g.id, &proposal_id, &g.members[2], &true);
+    }
+
+    end_voting(&test_env);
+    client.execute_proposal(&g.id, &rename);
+    client.execute_proposal(&g.id, &pause);
+
+    assert_eq!(client.get(&g.id).name, name);
+    assert!(!client.is_group_active(&g.id));
+}
+
+#[test]
+#[should_panic] // GovernanceRequired
+fn test_creator_cannot_update_governed_group_directly() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let mut split = Vec::new(&test_env.env);
+    split.push_back(member(&g.creator, 100));
+    client.update_members(&g.id, &g.creator, &split);
+}
+
+#[test]
+#[should_panic] // ProposalNotPassed
+fn test_proposal_without_quorum_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[2], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[2], &true);
+
+    end_voting(&test_env);
+    client.execute_proposal(&g.id, &proposal_id);
+}
+
+#[test]
+#[should_panic] // ProposalNotPassed
+fn test_proposal_below_threshold_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[1], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[1], &true);
+    client.vote(&g.id, &proposal_id, &g.members[2], &true);
+    client.vote(&g.id, &proposal_id, &g.members[0], &false);
+
+    end_voting(&test_env);
+    client.execute_proposal(&g.id, &proposal_id);
+}
+
+#[test]
+#[should_panic] // ProposalNotPassed
+fn test_proposal_cannot_execute_before_voting_ends() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+    client.vote(&g.id, &proposal_id, &g.members[1], &true);
+
+    client.execute_proposal(&g.id, &proposal_id);
+}
+
+#[test]
+#[should_panic] // VotingClosed
+fn test_vote_after_voting_period_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    end_voting(&test_env);
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+}
+
+#[test]
+#[should_panic] // AlreadyExists
+fn test_member_cannot_vote_twice() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[2], &true);
+    client.vote(&g.id, &proposal_id, &g.members[2], &true);
+}
+
+#[test]
+#[should_panic] // MemberNotFound
+fn test_non_member_cannot_vote() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &Address::generate(env), &true);
+}
+
+#[test]
+fn test_vote_weight_is_snapshotted_at_proposal() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    // A first proposal moves 30% from the first member to the second.
+    let mut split = Vec::new(env);
+    split.push_back(member(&g.members[0], 20));
+    split.push_back(member(&g.members[1], 60));
+    split.push_back(member(&g.members[2], 20));
+    let shift = client.propose(&g.id, &g.members[1], &ProposalAction::UpdateMembers(split));
+    client.vote(&g.id, &shift, &g.members[0], &true);
+    client.vote(&g.id, &shift, &g.members[1], &true);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += VOTING_PERIOD / 2);
+    let proposal_id = client.propose(
+        &g.id,
+        &g.members[0],
+        &ProposalAction::UpdateName(String::from_str(env, "Renamed")),
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp += VOTING_PERIOD / 2);
+    client.execute_proposal(&g.id, &shift);
+    assert_eq!(
+        client.get_group_members(&g.id).get(1).unwrap().percentage,
+        60
+    );
+
+    // The open proposal still counts the weights it was created with.
+    client.vote(&g.id, &proposal_id, &g.members[0], &false);
+    client.vote(&g.id, &proposal_id, &g.members[1], &true);
+    let proposal = client.get_proposals(&g.id).get(proposal_id).unwrap();
+    assert_eq!(proposal.votes_against, 50);
+    assert_eq!(proposal.votes_for, 30);
+}
+
+#[test]
+fn test_new_member_cannot_vote_on_earlier_proposal() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    let outsider = Address::generate(env);
+    assert!(client
+        .try_vote(&g.id, &proposal_id, &outsider, &true)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "VoteOpen")]
+fn test_address_change_blocked_during_open_vote() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+
+    client.change_payout_address(&g.members[0], &Address::generate(env));
+}
+
+#[test]
+fn test_address_change_allowed_once_voting_ends() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+    end_voting(&test_env);
+
+    let new_address = Address::generate(env);
+    client.change_payout_address(&g.members[0], &new_address);
+    assert_eq!(
+        client.get_group_members(&g.id).get(0).unwrap().address,
+        new_address
+    );
+}
+
+#[test]
+#[should_panic(expected = "ProposalNotPassed")]
+fn test_passed_proposal_lapses_after_execution_window() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    client.vote(&g.id, &proposal_id, &g.members[0], &true);
+    client.vote(&g.id, &proposal_id, &g.members[1], &true);
+    end_voting(&test_env);
+    env.ledger().with_mut(|li| li.timestamp += EXECUTION_WINDOW);
+
+    client.execute_proposal(&g.id, &proposal_id);
+}
+
+#[test]
+fn test_executed_proposal_supersedes_open_ones_of_same_kind() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let first = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    let second = client.propose(&g.id, &g.members[1], &rebalance(&test_env, &g));
+    let rename = client.propose(
+        &g.id,
+        &g.members[2],
+        &ProposalAction::UpdateName(String::from_str(env, "Renamed")),
+    );
+    for proposal_id in [first, second, rename] {
+        client.vote(&g.id, &proposal_id, &g.members[0], &true);
+        client.vote(&g.id, &proposal_id, &g.members[1], &true);
+    }
+    end_voting(&test_env);
+
+    client.execute_proposal(&g.id, &first);
+    let proposals = client.get_proposals(&g.id);
+    assert!(proposals.get(1).unwrap().superseded);
+    assert!(!proposals.get(2).unwrap().superseded);
+    assert!(client.try_execute_proposal(&g.id, &second).is_err());
+
+    // A different kind of change is unaffected
+    client.execute_proposal(&g.id, &rename);
+    assert_eq!(client.get(&g.id).name, String::from_str(env, "Renamed"));
+}
+
+#[test]
+fn test_open_proposals_are_limited_and_closed_ones_dropped() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    for _ in 0..10 {
+        client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    }
+    assert!(client
+        .try_propose(&g.id, &g.members[0], &rebalance(&test_env, &g))
+        .is_err());
+
+    // Once the earlier proposals lapse they no longer count and are dropped
+    env.ledger()
+        .with_mut(|li| li.timestamp += VOTING_PERIOD + EXECUTION_WINDOW);
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    assert_eq!(proposal_id, 10);
+    let proposals = client.get_proposals(&g.id);
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals.get(0).unwrap().id, 10);
+}
+
+#[test]
+fn test_address_change_carries_vote_weight_to_new_address() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let g = setup(&test_env);
+
+    let proposal_id = client.propose(&g.id, &g.members[0], &rebalance(&test_env, &g));
+    let new_address = Address::generate(env);
+    client.change_payout_address(&g.members[1], &new_address);
+
+    assert!(client
+        .try_vote(&g.id, &proposal_id, &g.members[1], &true)
+        .is_err());
+    client.vote(&g.id, &proposal_id, &new_address, &true);
+    let proposal = client.get_proposals(&g.id).get(0).unwrap();
+    assert_eq!(proposal.votes_for, 30);
+    assert_eq!(proposal.weights.get(new_address), Some(30));
+}
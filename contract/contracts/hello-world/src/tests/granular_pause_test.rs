@@ -0,0 +1,192 @@
+use crate::base::types::{GroupMember, PauseCategory};
+use crate::test_utils::{assert_balance, create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN};
+
+fn setup_group(test_env: &TestEnv) -> (BytesN<32>, Address) {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let member = Address::generate(env);
+    let members = vec![
+        env,
+        GroupMember {
+            address: member.clone(),
+            percentage: 100,
+            preferred_token: None,
+            payout_address: None,
+        },
+    ];
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        5,
+        &token,
+    );
+    (id, member)
+}
+
+#[test]
+fn test_fund_movements_paused_while_management_continues() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let (id, _) = setup_group(&test_env);
+    client.start_fundraising(&id, &creator, &1_000);
+
+    for category in [
+        PauseCategory::Distributions,
+        PauseCategory::Contributions,
+        PauseCategory::Withdrawals,
+    ] {
+        client.pause_operation(&test_env.admin, &category);
+    }
+    assert!(!client.get_paused_status());
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+    assert!(client
+        .try_distribute(&id, &token, &100, &sender, &None, &None)
+        .is_err());
+    assert!(client
+        .try_contribute(&id, &token, &100, &sender, &None)
+        .is_err());
+    assert!(client
+        .try_withdraw(&test_env.admin, &token, &1, &sender)
+        .is_err());
+
+    // Creators can still fix the split and deactivate the group
+    let members = vec![
+        env,
+        GroupMember {
+            address: creator.clone(),
+            percentage: 100,
+            preferred_token: None,
+            payout_address: None,
+        },
+    ];
+    client.update_members(&id, &creator, &members);
+    client.deactivate_group(&id, &creator);
+    assert!(!client.is_group_active(&id));
+}
+
+#[test]
+fn test_unpaused_category_resumes() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, member) = setup_group(&test_env);
+
+    client.pause_operation(&test_env.admin, &PauseCategory::Distributions);
+    client.unpause_operation(&test_env.admin, &PauseCategory::Distributions);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+    assert_balance(env, &token, &member, 100);
+}
+
+#[test]
+fn test_pause_map_reports_each_category() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    let map = client.get_pause_map();
+    assert_eq!(map.len(), 6);
+    assert!(map.values().iter().all(|paused| !paused));
+
+    client.pause_operation(&test_env.admin, &PauseCategory::Subscriptions);
+    let map = client.get_pause_map();
+    assert_eq!(map.get(PauseCategory::Subscriptions), Some(true));
+    assert_eq!(map.get(PauseCategory::Distributions), Some(false));
+}
+
+#[test]
+fn test_pause_map_reflects_global_pause() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    client.pause(&test_env.admin);
+    let map = client.get_pause_map();
+    assert!(map.values().iter().all(|paused| paused));
+}
+
+#[test]
+fn test_sponsorship_paused_separately_from_contributions() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let sponsor = test_env.users.get(1).unwrap();
+    let (id, _) = setup_group(&test_env);
+    client.start_fundraising(&id, &creator, &1_000);
+
+    client.pause_operation(&test_env.admin, &PauseCategory::Sponsorship);
+    let pool_id = BytesN::from_array(env, &[7u8; 32]);
+    assert!(client
+        .try_create_sponsor_pool(&pool_id, &sponsor, &token, &1)
+        .is_err());
+
+    mint_tokens(env, &token, &sponsor, 100);
+    client.contribute(&id, &token, &100, &sponsor, &None);
+}
+
+#[test]
+#[should_panic(expected = "ContractPaused")]
+fn test_global_pause_blocks_sponsor_refunds() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let sponsor = test_env.users.get(1).unwrap();
+
+    let pool_id = BytesN::from_array(env, &[7u8; 32]);
+    mint_tokens(env, &token, &sponsor, 100);
+    client.create_sponsor_pool(&pool_id, &sponsor, &token, &1);
+    client.fund_sponsor_pool(&pool_id, &sponsor, &100);
+
+    client.pause(&test_env.admin);
+    client.withdraw_sponsor_funds(&pool_id, &sponsor, &100);
+}
+
+#[test]
+#[should_panic] // ContractPaused
+fn test_paused_subscriptions_block_top_ups() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let (id, _) = setup_group(&test_env);
+
+    client.pause_operation(&test_env.admin, &PauseCategory::Subscriptions);
+    mint_tokens(env, &token, &creator, 1_000);
+    client.topup_subscription(&id, &1, &token, &creator, &None, &None);
+}
+
+#[test]
+#[should_panic] // AlreadyPaused
+fn test_category_cannot_be_paused_twice() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    client.pause_operation(&test_env.admin, &PauseCategory::Withdrawals);
+    client.pause_operation(&test_env.admin, &PauseCategory::Withdrawals);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_admin_can_pause_operations() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+
+    client.pause_operation(&creator, &PauseCategory::Distributions);
+}
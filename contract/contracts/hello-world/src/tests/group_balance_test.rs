@@ -0,0 +1,146 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{assert_balance, create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Vec};
+
+fn two_member_group(test_env: &TestEnv, usages: u32) -> (BytesN<32>, Address, Address) {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let member1 = Address::generate(env);
+    let member2 = Address::generate(env);
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: member1.clone(),
+        percentage: 60,
+        preferred_token: None,
+        payout_address: None,
+    });
+    members.push_back(GroupMember {
+        address: member2.clone(),
+        percentage: 40,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        usages,
+        &token,
+    );
+    (id, member1, member2)
+}
+
+#[test]
+fn test_deposits_accumulate_until_distributed() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, member1, member2) = two_member_group(&test_env, 2);
+    let payer1 = test_env.users.get(1).unwrap();
+    let payer2 = test_env.users.get(2).unwrap();
+    mint_tokens(env, &token, &payer1, 300);
+    mint_tokens(env, &token, &payer2, 200);
+
+    client.deposit(&id, &token, &300, &payer1);
+    client.deposit(&id, &token, &200, &payer2);
+    assert_eq!(client.get_group_balance(&id, &token), 500);
+    assert_balance(env, &token, &member1, 0);
+
+    // Anyone can trigger settlement
+    let keeper = Address::generate(env);
+    let distributed = client.distribute_balance(&id, &token, &keeper);
+    assert_eq!(distributed, 500);
+
+    assert_balance(env, &token, &member1, 300);
+    assert_balance(env, &token, &member2, 200);
+    assert_eq!(client.get_group_balance(&id, &token), 0);
+    assert_eq!(client.get_remaining_usages(&id), 1);
+
+    let history = client.get_group_distributions(&id);
+    assert_eq!(history.len(), 1);
+    let entry = history.get(0).unwrap();
+    assert_eq!(entry.sender, keeper);
+    assert_eq!(entry.total_amount, 500);
+}
+
+#[test]
+fn test_balances_are_tracked_per_group() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id1, _, _) = two_member_group(&test_env, 1);
+    let (id2, _, _) = two_member_group(&test_env, 2);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+
+    client.deposit(&id1, &token, &100, &payer);
+    client.deposit(&id2, &token, &400, &payer);
+    client.distribute_balance(&id1, &token, &payer);
+
+    assert_eq!(client.get_group_balance(&id1, &token), 0);
+    assert_eq!(client.get_group_balance(&id2, &token), 400);
+}
+
+#[test]
+#[should_panic] // InsufficientBalance
+fn test_distribute_empty_balance_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _, _) = two_member_group(&test_env, 1);
+    let caller = test_env.users.get(1).unwrap();
+    client.distribute_balance(&id, &token, &caller);
+}
+
+#[test]
+#[should_panic] // NoUsagesRemaining
+fn test_distribute_balance_requires_usage() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _, _) = two_member_group(&test_env, 1);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 200);
+
+    client.deposit(&id, &token, &100, &payer);
+    client.distribute_balance(&id, &token, &payer);
+    client.deposit(&id, &token, &100, &payer);
+    client.distribute_balance(&id, &token, &payer);
+}
+
+#[test]
+#[should_panic] // NotFound
+fn test_deposit_to_unknown_group_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 100);
+    client.deposit(&BytesN::from_array(env, &[9u8; 32]), &token, &100, &payer);
+}
+
+#[test]
+#[should_panic] // UnsupportedToken
+fn test_deposit_unsupported_token_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let (id, _, _) = two_member_group(&test_env, 1);
+    let payer = test_env.users.get(1).unwrap();
+    client.deposit(&id, &Address::generate(env), &100, &payer);
+}
@@ -30,6 +30,8 @@ mod property_tests {
         members.push_back(crate::base::types::GroupMember {
             address: Address::generate(&test_env.env),
             percentage: 100,
+            preferred_token: None,
+            payout_address: None,
         });
 
         // Create the specified number of groups
@@ -68,6 +70,8 @@ mod property_tests {
         members.push_back(crate::base::types::GroupMember {
             address: Address::generate(&test_env.env),
             percentage: 100,
+            preferred_token: None,
+            payout_address: None,
         });
 
         // Create groups
@@ -109,6 +113,8 @@ mod property_tests {
         members.push_back(crate::base::types::GroupMember {
             address: Address::generate(&test_env.env),
             percentage: 100,
+            preferred_token: None,
+            payout_address: None,
         });
 
         // Create initial groups
@@ -0,0 +1,172 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{assert_balance, create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN,
+};
+
+const FREEZE_DURATION: u64 = 3_600;
+const REASON_SUSPICIOUS_UPDATE: u32 = 7;
+
+fn setup_group(test_env: &TestEnv, usages: u32) -> (BytesN<32>, Address) {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let member = Address::generate(env);
+    let members = vec![
+        env,
+        GroupMember {
+            address: member.clone(),
+            percentage: 100,
+            preferred_token: None,
+            payout_address: None,
+        },
+    ];
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        usages,
+        &token,
+    );
+    (id, member)
+}
+
+#[test]
+fn test_guardian_freeze_blocks_distribution_until_expiry() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let (id, member) = setup_group(&test_env, 2);
+    let (other_id, _) = setup_group(&test_env, 3);
+    let guardian = Address::generate(env);
+    client.set_group_guardian(&id, &Some(guardian.clone()), &creator);
+
+    client.freeze_group(&id, &guardian, &REASON_SUSPICIOUS_UPDATE, &FREEZE_DURATION);
+    assert!(client.is_group_frozen(&id));
+    let freeze = client.get_group_freeze(&id).unwrap();
+    assert_eq!(freeze.reason, REASON_SUSPICIOUS_UPDATE);
+    assert_eq!(freeze.frozen_by, guardian);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+    assert!(client
+        .try_distribute(&id, &token, &100, &sender, &None, &None)
+        .is_err());
+
+    // Other groups keep working
+    client.distribute(&other_id, &token, &100, &sender, &None, &None);
+
+    env.ledger().with_mut(|li| li.timestamp += FREEZE_DURATION);
+    assert!(!client.is_group_frozen(&id));
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+    assert_balance(env, &token, &member, 100);
+}
+
+#[test]
+fn test_admin_can_freeze_and_unfreeze() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let (id, _) = setup_group(&test_env, 1);
+
+    client.freeze_group(&id, &test_env.admin, &1, &FREEZE_DURATION);
+    assert!(client.is_group_frozen(&id));
+
+    client.unfreeze_group(&id, &test_env.admin);
+    assert!(!client.is_group_frozen(&id));
+    assert_eq!(client.get_group_freeze(&id), None);
+}
+
+#[test]
+#[should_panic] // GroupFrozen
+fn test_frozen_group_rejects_member_updates() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let (id, _) = setup_group(&test_env, 1);
+
+    client.freeze_group(&id, &test_env.admin, &1, &FREEZE_DURATION);
+    let members = vec![
+        env,
+        GroupMember {
+            address: creator.clone(),
+            percentage: 100,
+            preferred_token: None,
+            payout_address: None,
+        },
+    ];
+    client.update_members(&id, &creator, &members);
+}
+
+#[test]
+#[should_panic] // GroupFrozen
+fn test_frozen_group_rejects_contributions() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let (id, _) = setup_group(&test_env, 1);
+
+    client.start_fundraising(&id, &creator, &1_000);
+    client.freeze_group(&id, &test_env.admin, &1, &FREEZE_DURATION);
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_guardian_or_admin_can_freeze() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let (id, _) = setup_group(&test_env, 1);
+
+    client.freeze_group(&id, &creator, &1, &FREEZE_DURATION);
+}
+
+#[test]
+fn test_creator_cannot_swap_guardian_during_freeze() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let (id, member) = setup_group(&test_env, 1);
+
+    let guardian = Address::generate(env);
+    client.set_group_guardian(&id, &Some(guardian.clone()), &creator);
+    client.freeze_group(&id, &guardian, &REASON_SUSPICIOUS_UPDATE, &FREEZE_DURATION);
+
+    let result = client.try_set_group_guardian(&id, &Some(creator.clone()), &creator);
+    assert!(result.is_err());
+    assert!(client
+        .try_transfer_group_ownership(&id, &creator, &Address::generate(env))
+        .is_err());
+    assert!(client
+        .try_set_payout_preferences(&id, &member, &None, &Some(Address::generate(env)))
+        .is_err());
+    assert!(client
+        .try_change_payout_address(&member, &Address::generate(env))
+        .is_err());
+    assert!(client.is_group_frozen(&id));
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_freeze_duration_cannot_overflow() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let (id, _) = setup_group(&test_env, 1);
+
+    test_env.env.ledger().with_mut(|li| li.timestamp = 1);
+    client.freeze_group(&id, &test_env.admin, &1, &u64::MAX);
+}
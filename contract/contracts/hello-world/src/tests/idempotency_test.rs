@@ -0,0 +1,112 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{assert_balance, create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Vec,
+};
+
+const RETENTION: u64 = 7 * 24 * 60 * 60;
+
+fn setup_group(test_env: &TestEnv, usages: u32) -> (BytesN<32>, Address) {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let member = Address::generate(env);
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: member.clone(),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        usages,
+        &token,
+    );
+    (id, member)
+}
+
+#[test]
+fn test_idempotency_key_is_recorded() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, member) = setup_group(&test_env, 2);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let key = BytesN::from_array(env, &[1u8; 32]);
+    assert!(!client.is_idempotency_key_used(&id, &key));
+
+    client.distribute(&id, &token, &100, &sender, &None, &Some(key.clone()));
+    assert!(client.is_idempotency_key_used(&id, &key));
+    assert_balance(env, &token, &member, 100);
+
+    // A replay is rejected without charging the sender again
+    let replay = client.try_distribute(&id, &token, &100, &sender, &None, &Some(key.clone()));
+    assert!(replay.is_err());
+    assert_balance(env, &token, &sender, 100);
+    assert_eq!(client.get_remaining_usages(&id), 1);
+}
+
+#[test]
+#[should_panic] // AlreadyProcessed
+fn test_replayed_key_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _) = setup_group(&test_env, 2);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let key = Some(BytesN::from_array(env, &[1u8; 32]));
+    client.distribute(&id, &token, &100, &sender, &None, &key);
+    client.distribute(&id, &token, &100, &sender, &None, &key);
+}
+
+#[test]
+fn test_keys_are_scoped_per_group() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id1, _) = setup_group(&test_env, 1);
+    let (id2, _) = setup_group(&test_env, 2);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let key = Some(BytesN::from_array(env, &[1u8; 32]));
+    client.distribute(&id1, &token, &100, &sender, &None, &key);
+    client.distribute(&id2, &token, &100, &sender, &None, &key);
+}
+
+#[test]
+fn test_key_can_be_reused_after_retention_window() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _) = setup_group(&test_env, 2);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+
+    let key = BytesN::from_array(env, &[1u8; 32]);
+    client.distribute(&id, &token, &100, &sender, &None, &Some(key.clone()));
+
+    env.ledger().with_mut(|li| li.timestamp += RETENTION);
+    assert!(!client.is_idempotency_key_used(&id, &key));
+    client.distribute(&id, &token, &100, &sender, &None, &Some(key));
+    assert_eq!(client.get_remaining_usages(&id), 0);
+}
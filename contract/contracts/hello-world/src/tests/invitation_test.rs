@@ -0,0 +1,219 @@
+use crate::autoshare_logic::DataKey;
+use crate::base::types::Invitation;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, member, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Vec};
+
+struct InviteSetup {
+    id: BytesN<32>,
+    creator: Address,
+    member: Address,
+}
+
+/// A group with one member holding 60%, leaving 40% to offer.
+fn setup(test_env: &TestEnv) -> InviteSetup {
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[60, 40], 5);
+    let placeholder = group.members.get(1).unwrap();
+    client.remove_group_member(&group.id, &group.creator, &placeholder);
+
+    InviteSetup {
+        id: group.id,
+        creator: group.creator,
+        member: group.members.get(0).unwrap(),
+    }
+}
+
+#[test]
+fn test_invited_share_applies_after_acceptance() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &invitee, &40);
+
+    // Nothing changes for the invitee until they accept
+    assert!(!client.is_group_member(&s.id, &invitee));
+    assert_eq!(client.get_groups_by_member(&invitee).len(), 0);
+    let invitations = client.get_group_invitations(&s.id);
+    assert_eq!(invitations.len(), 1);
+    assert_eq!(invitations.get(0).unwrap().invitee, invitee);
+    assert_eq!(invitations.get(0).unwrap().percentage, 40);
+
+    client.accept_invitation(&s.id, &invitee);
+
+    assert!(client.is_group_member(&s.id, &invitee));
+    assert_eq!(client.get_groups_by_member(&invitee).len(), 1);
+    assert_eq!(client.get_group_invitations(&s.id).len(), 0);
+
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&s.id, &token, &100, &sender, &None, &None);
+    assert_balance(env, &token, &s.member, 60);
+    assert_balance(env, &token, &invitee, 40);
+}
+
+#[test]
+fn test_update_members_invites_new_addresses() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    let members = vec![env, member(&s.member, 50), member(&invitee, 50)];
+    client.update_members(&s.id, &s.creator, &members);
+
+    // The existing member's new share applies at once, the newcomer is only invited
+    let current = client.get_group_members(&s.id);
+    assert_eq!(current.len(), 1);
+    assert_eq!(current.get(0).unwrap().percentage, 50);
+    assert_eq!(client.get_group_invitations(&s.id).len(), 1);
+    assert_eq!(client.get_groups_by_member(&invitee).len(), 0);
+}
+
+#[test]
+#[should_panic] // InvitationsPending
+fn test_distribute_fails_while_invitations_pending() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.add_group_member(&s.id, &s.creator, &Address::generate(env), &40);
+
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&s.id, &token, &100, &sender, &None, &None);
+}
+
+#[test]
+#[should_panic] // InvitationsPending
+fn test_contribute_fails_while_invitations_pending() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.start_fundraising(&s.id, &s.creator, &1_000);
+    client.add_group_member(&s.id, &s.creator, &Address::generate(env), &40);
+
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&s.id, &token, &100, &contributor, &None);
+}
+
+#[test]
+fn test_declined_invitation_leaves_share_unassigned() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &invitee, &40);
+    client.decline_invitation(&s.id, &invitee);
+
+    assert_eq!(client.get_group_invitations(&s.id).len(), 0);
+    assert!(!client.is_group_member(&s.id, &invitee));
+
+    // The freed share can be offered to someone else
+    let other = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &other, &40);
+    assert_eq!(client.get_group_invitations(&s.id).len(), 1);
+}
+
+#[test]
+fn test_creator_can_withdraw_invitation() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &invitee, &40);
+    client.remove_group_member(&s.id, &s.creator, &invitee);
+
+    assert_eq!(client.get_group_invitations(&s.id).len(), 0);
+    assert!(client.try_accept_invitation(&s.id, &invitee).is_err());
+}
+
+#[test]
+#[should_panic] // InvalidTotalPercentage
+fn test_invitations_cannot_exceed_open_share() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.add_group_member(&s.id, &s.creator, &Address::generate(env), &30);
+    client.add_group_member(&s.id, &s.creator, &Address::generate(env), &20);
+}
+
+#[test]
+#[should_panic] // AlreadyExists
+fn test_cannot_invite_twice() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &invitee, &20);
+    client.add_group_member(&s.id, &s.creator, &invitee, &20);
+}
+
+#[test]
+#[should_panic] // NotFound
+fn test_accept_without_invitation_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.accept_invitation(&s.id, &Address::generate(env));
+}
+
+#[test]
+#[should_panic(expected = "AlreadyExists")]
+fn test_member_cannot_accept_invitation_again() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    // An invitation left over for someone who is already a member
+    let mut invitations = Vec::new(env);
+    invitations.push_back(Invitation {
+        invitee: s.member.clone(),
+        percentage: 40,
+        invited_at: env.ledger().timestamp(),
+    });
+    env.as_contract(&test_env.autoshare_contract, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::GroupInvitations(s.id.clone()), &invitations);
+    });
+
+    client.accept_invitation(&s.id, &s.member);
+}
+
+#[test]
+#[should_panic(expected = "DuplicateMember")]
+fn test_member_cannot_move_to_invited_address() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&s.id, &s.creator, &invitee, &20);
+    client.change_payout_address(&s.member, &invitee);
+}
@@ -0,0 +1,246 @@
+use crate::base::types::{InvoiceStatus, ManagerPermission};
+use crate::test_utils::{
+    assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, String,
+};
+
+const DUE_DATE: u64 = 1_000;
+
+struct InvoiceSetup {
+    group_id: BytesN<32>,
+    invoice_id: BytesN<32>,
+    creator: Address,
+    member: Address,
+}
+
+/// Creates a single-member group and a 500 token invoice against it.
+fn setup_invoice(test_env: &TestEnv, payer: Option<Address>) -> InvoiceSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[100], 5);
+
+    let invoice_id = BytesN::from_array(env, &[21u8; 32]);
+    client.create_invoice(
+        &invoice_id,
+        &group.id,
+        &group.creator,
+        &group.token,
+        &500,
+        &payer,
+        &DUE_DATE,
+        &Some(String::from_str(env, "ORDER-77")),
+    );
+
+    InvoiceSetup {
+        group_id: group.id,
+        invoice_id,
+        creator: group.creator,
+        member: group.members.get(0).unwrap(),
+    }
+}
+
+#[test]
+fn test_pay_invoice_in_full() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_invoice(&test_env, None);
+    assert_eq!(
+        client.get_invoice(&s.invoice_id).status,
+        InvoiceStatus::Open
+    );
+
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+    client.pay_invoice(&s.invoice_id, &payer, &500);
+
+    assert_balance(env, &token, &s.member, 500);
+    let invoice = client.get_invoice(&s.invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::Paid);
+    assert_eq!(invoice.amount_paid, 500);
+
+    // The distribution links back to the invoice and its reference
+    let distribution = client.get_group_distributions(&s.group_id).get(0).unwrap();
+    assert_eq!(distribution.invoice_id, Some(s.invoice_id.clone()));
+    assert_eq!(distribution.memo, Some(String::from_str(env, "ORDER-77")));
+}
+
+#[test]
+fn test_partial_payments_complete_invoice() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_invoice(&test_env, None);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+
+    client.pay_invoice(&s.invoice_id, &payer, &200);
+    let invoice = client.get_invoice(&s.invoice_id);
+    assert_eq!(invoice.status, InvoiceStatus::PartiallyPaid);
+    assert_eq!(invoice.amount_paid, 200);
+
+    client.pay_invoice(&s.invoice_id, &payer, &300);
+    assert_eq!(
+        client.get_invoice(&s.invoice_id).status,
+        InvoiceStatus::Paid
+    );
+    assert_eq!(client.get_group_distributions(&s.group_id).len(), 2);
+    assert_eq!(client.get_remaining_usages(&s.group_id), 3);
+}
+
+#[test]
+fn test_overdue_invoices() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_invoice(&test_env, None);
+    assert_eq!(client.get_group_invoices(&s.group_id).len(), 1);
+    assert_eq!(client.get_overdue_invoices(&s.group_id).len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = DUE_DATE + 1);
+    let overdue = client.get_overdue_invoices(&s.group_id);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue.get(0).unwrap().id, s.invoice_id);
+
+    // Cancelled invoices are no longer overdue
+    client.cancel_invoice(&s.invoice_id, &s.creator);
+    assert_eq!(client.get_overdue_invoices(&s.group_id).len(), 0);
+}
+
+#[test]
+#[should_panic] // InvoiceNotOpen
+fn test_pay_cancelled_invoice_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_invoice(&test_env, None);
+    client.cancel_invoice(&s.invoice_id, &s.creator);
+
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+    client.pay_invoice(&s.invoice_id, &payer, &500);
+}
+
+#[test]
+#[should_panic] // InvalidAmount
+fn test_overpaying_invoice_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_invoice(&test_env, None);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 600);
+    client.pay_invoice(&s.invoice_id, &payer, &501);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_designated_payer_can_pay() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let designated = Address::generate(env);
+    let s = setup_invoice(&test_env, Some(designated));
+
+    let other = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &other, 500);
+    client.pay_invoice(&s.invoice_id, &other, &500);
+}
+
+#[test]
+#[should_panic] // NotFound
+fn test_invoice_for_unknown_group_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    client.create_invoice(
+        &BytesN::from_array(env, &[22u8; 32]),
+        &BytesN::from_array(env, &[99u8; 32]),
+        &test_env.users.get(0).unwrap(),
+        &token,
+        &100,
+        &None,
+        &DUE_DATE,
+        &None,
+    );
+}
+
+#[test]
+fn test_invoice_manager_can_issue_and_cancel_invoices() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup_invoice(&test_env, None);
+
+    let manager = Address::generate(env);
+    client.set_group_manager(
+        &s.group_id,
+        &s.creator,
+        &manager,
+        &vec![env, ManagerPermission::ManageInvoices],
+    );
+    let invoice_id = BytesN::from_array(env, &[23u8; 32]);
+    client.create_invoice(
+        &invoice_id,
+        &s.group_id,
+        &manager,
+        &token,
+        &100,
+        &None,
+        &DUE_DATE,
+        &None,
+    );
+    assert_eq!(client.get_group_invoices(&s.group_id).len(), 2);
+
+    client.cancel_invoice(&invoice_id, &manager);
+    assert_eq!(
+        client.get_invoice(&invoice_id).status,
+        InvoiceStatus::Cancelled
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized")]
+fn test_manager_without_invoice_permission_cannot_issue_invoices() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup_invoice(&test_env, None);
+
+    let manager = Address::generate(env);
+    client.set_group_manager(
+        &s.group_id,
+        &s.creator,
+        &manager,
+        &vec![env, ManagerPermission::ManageMembers],
+    );
+    client.create_invoice(
+        &BytesN::from_array(env, &[23u8; 32]),
+        &s.group_id,
+        &manager,
+        &token,
+        &100,
+        &None,
+        &DUE_DATE,
+        &None,
+    );
+}
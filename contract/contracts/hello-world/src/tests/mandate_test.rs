@@ -0,0 +1,157 @@
+use crate::test_utils::{
+    approve_tokens, assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN,
+};
+
+const DAY: u64 = 86_400;
+
+struct MandateSetup {
+    id: BytesN<32>,
+    creator: Address,
+    payer: Address,
+    member: Address,
+}
+
+/// Creates a group with a single member and a payer who authorizes up to 100
+/// per day for 30 days, with the allowance granted to the contract.
+fn setup_mandate(test_env: &TestEnv, collector: Option<Address>) -> MandateSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[100], 10);
+
+    let payer = Address::generate(env);
+    mint_tokens(env, &group.token, &payer, 1_000);
+    approve_tokens(
+        env,
+        &group.token,
+        &payer,
+        &test_env.autoshare_contract,
+        1_000,
+    );
+    client.create_mandate(
+        &group.id,
+        &payer,
+        &group.token,
+        &100,
+        &DAY,
+        &(30 * DAY),
+        &collector,
+    );
+
+    MandateSetup {
+        id: group.id,
+        creator: group.creator,
+        payer,
+        member: group.members.get(0).unwrap(),
+    }
+}
+
+#[test]
+fn test_creator_collects_within_limit() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_mandate(&test_env, None);
+    client.collect(&s.id, &s.payer, &60, &s.creator);
+    client.collect(&s.id, &s.payer, &40, &s.creator);
+
+    assert_balance(env, &token, &s.payer, 900);
+    assert_balance(env, &token, &s.member, 100);
+    assert_eq!(client.get_remaining_usages(&s.id), 8);
+
+    let mandate = client.get_mandate(&s.id, &s.payer);
+    assert_eq!(mandate.collected_in_period, 100);
+    assert_eq!(mandate.total_collected, 100);
+    assert_eq!(mandate.collection_count, 2);
+
+    // The payer is recorded as the sender of the distribution
+    let history = client.get_group_distributions(&s.id);
+    assert_eq!(history.get(0).unwrap().sender, s.payer);
+}
+
+#[test]
+fn test_designated_collector_can_collect() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let collector = Address::generate(env);
+    let s = setup_mandate(&test_env, Some(collector.clone()));
+    client.collect(&s.id, &s.payer, &50, &collector);
+
+    assert_balance(env, &token, &s.member, 50);
+}
+
+#[test]
+fn test_limit_resets_each_period() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_mandate(&test_env, None);
+    client.collect(&s.id, &s.payer, &100, &s.creator);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * DAY + 10);
+    client.collect(&s.id, &s.payer, &100, &s.creator);
+
+    let mandate = client.get_mandate(&s.id, &s.payer);
+    assert_eq!(mandate.period_start, 2 * DAY);
+    assert_eq!(mandate.collected_in_period, 100);
+    assert_eq!(mandate.total_collected, 200);
+}
+
+#[test]
+#[should_panic] // MandateLimitExceeded
+fn test_collect_over_period_limit_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_mandate(&test_env, None);
+    client.collect(&s.id, &s.payer, &70, &s.creator);
+    client.collect(&s.id, &s.payer, &31, &s.creator);
+}
+
+#[test]
+#[should_panic] // MandateExpired
+fn test_collect_after_expiry_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_mandate(&test_env, None);
+    env.ledger().with_mut(|li| li.timestamp = 30 * DAY);
+    client.collect(&s.id, &s.payer, &10, &s.creator);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_collect_after_revoke_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_mandate(&test_env, None);
+    client.revoke_mandate(&s.id, &s.payer);
+    assert!(!client.get_mandate(&s.id, &s.payer).is_active);
+    client.collect(&s.id, &s.payer, &10, &s.creator);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_stranger_cannot_collect() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let s = setup_mandate(&test_env, None);
+    let stranger = Address::generate(env);
+    client.collect(&s.id, &s.payer, &10, &stranger);
+}
@@ -1,5 +1,5 @@
 use crate::base::types::GroupMember;
-use crate::test_utils::{create_test_group, setup_test_env};
+use crate::test_utils::{accept_invitations, create_test_group, setup_test_env};
 use crate::AutoShareContractClient;
 use soroban_sdk::{testutils::Address as _, Address, Vec};
 
@@ -18,6 +18,8 @@ fn test_update_members_exceeds_max_members() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let group_id = create_test_group(
@@ -36,6 +38,8 @@ fn test_update_members_exceeds_max_members() {
         new_members.push_back(GroupMember {
             address: member,
             percentage: 1,
+            preferred_token: None,
+            payout_address: None,
         });
     }
 
@@ -57,6 +61,8 @@ fn test_max_members_boundary_success() {
     members.push_back(GroupMember {
         address: member1.clone(),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let group_id = create_test_group(
@@ -75,11 +81,14 @@ fn test_max_members_boundary_success() {
         new_members.push_back(GroupMember {
             address: member,
             percentage: 2,
+            preferred_token: None,
+            payout_address: None,
         });
     }
 
     // This should succeed
     client.update_members(&group_id, &creator, &new_members);
+    accept_invitations(&test_env.env, &test_env.autoshare_contract, &group_id);
 
     // Verify all members were added
     let group_members = client.get_group_members(&group_id);
@@ -0,0 +1,244 @@
+use crate::base::types::LeavePolicy;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, create_test_group, mint_tokens, setup_test_env,
+    TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Vec};
+
+struct MemberSetup {
+    id: BytesN<32>,
+    members: Vec<Address>,
+    sender: Address,
+}
+
+/// A 50/30/20 group with a funded sender.
+fn setup(test_env: &TestEnv, usages: u32) -> MemberSetup {
+    let group = create_group_with_members(test_env, &[50, 30, 20], usages);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(&test_env.env, &group.token, &sender, 1_000);
+
+    MemberSetup {
+        id: group.id,
+        members: group.members,
+        sender,
+    }
+}
+
+#[test]
+fn test_leave_group_requires_rebalance_by_default() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 1);
+    let leaver = s.members.get(2).unwrap();
+
+    client.leave_group(&s.id, &leaver);
+    assert!(!client.is_group_member(&s.id, &leaver));
+    assert_eq!(client.get_groups_by_member(&leaver).len(), 0);
+
+    let members = client.get_group_members(&s.id);
+    assert_eq!(members.len(), 2);
+    assert_eq!(members.get(0).unwrap().percentage, 50);
+    assert_eq!(members.get(1).unwrap().percentage, 30);
+
+    // The split no longer adds up, so distributions wait for the creator
+    let result = client.try_distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_leave_group_redistributes_share() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.set_leave_policy(&s.id, &LeavePolicy::Redistribute, &creator);
+    client.leave_group(&s.id, &s.members.get(2).unwrap());
+
+    // 50/30 scaled to 62.5/37.5; the rounding leftover goes to the largest share
+    let members = client.get_group_members(&s.id);
+    assert_eq!(members.get(0).unwrap().percentage, 63);
+    assert_eq!(members.get(1).unwrap().percentage, 37);
+
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 63);
+    assert_balance(env, &token, &s.members.get(1).unwrap(), 37);
+}
+
+#[test]
+#[should_panic] // MemberNotFound
+fn test_non_member_cannot_leave() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env, 1);
+
+    client.leave_group(&s.id, &Address::generate(env));
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_creator_sets_leave_policy() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env, 1);
+
+    client.set_leave_policy(
+        &s.id,
+        &LeavePolicy::Redistribute,
+        &s.members.get(0).unwrap(),
+    );
+}
+
+#[test]
+fn test_change_payout_address_migrates_member() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 2);
+    let old_address = s.members.get(0).unwrap();
+
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_eq!(client.get_member_earnings(&old_address, &s.id), 50);
+
+    let new_address = Address::generate(env);
+    client.change_payout_address(&old_address, &new_address);
+
+    assert!(client.is_group_member(&s.id, &new_address));
+    assert!(!client.is_group_member(&s.id, &old_address));
+    assert_eq!(client.get_groups_by_member(&old_address).len(), 0);
+    assert_eq!(client.get_groups_by_member(&new_address).len(), 1);
+    assert_eq!(client.get_member_earnings(&new_address, &s.id), 50);
+    assert_eq!(client.get_member_earnings(&old_address, &s.id), 0);
+    assert_eq!(client.get_member_distributions(&new_address).len(), 1);
+    assert_eq!(client.get_member_distributions(&old_address).len(), 0);
+
+    // Later payouts go to the new address with the same share
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_balance(env, &token, &new_address, 50);
+    assert_balance(env, &token, &old_address, 50);
+    assert_eq!(client.get_member_earnings(&new_address, &s.id), 100);
+}
+
+#[test]
+#[should_panic] // DuplicateMember
+fn test_change_to_existing_member_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env, 1);
+
+    client.change_payout_address(&s.members.get(0).unwrap(), &s.members.get(1).unwrap());
+}
+
+#[test]
+#[should_panic] // MemberNotFound
+fn test_change_address_without_memberships_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    client.change_payout_address(&Address::generate(env), &Address::generate(env));
+}
+
+#[test]
+fn test_redistribution_leftover_goes_to_largest_share() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(&test_env, &[20, 25, 55], 1);
+    let id = group.id;
+
+    client.set_leave_policy(&id, &LeavePolicy::Redistribute, &group.creator);
+    client.leave_group(&id, &group.members.get(2).unwrap());
+
+    // 20/25 scaled to 44.4/55.6; the leftover goes to the 25% member
+    let members = client.get_group_members(&id);
+    assert_eq!(members.get(0).unwrap().percentage, 44);
+    assert_eq!(members.get(1).unwrap().percentage, 56);
+}
+
+#[test]
+fn test_change_payout_address_moves_pending_in_removed_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env, 1);
+    let old_address = s.members.get(0).unwrap();
+
+    client.set_min_payout(&s.id, &100, &creator);
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    client.remove_supported_token(&token, &test_env.admin);
+
+    let new_address = Address::generate(env);
+    client.change_payout_address(&old_address, &new_address);
+
+    assert_eq!(client.get_pending_payout(&s.id, &new_address, &token), 50);
+    assert_eq!(client.get_pending_payout(&s.id, &old_address, &token), 0);
+}
+
+#[test]
+fn test_change_payout_address_moves_open_invitations() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env, 1);
+    let old_address = s.members.get(2).unwrap();
+
+    // A second group invites the member, who has not answered yet
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let other = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &Vec::new(env),
+        2,
+        &token,
+    );
+    client.add_group_member(&other, &creator, &old_address, &40);
+
+    let new_address = Address::generate(env);
+    client.change_payout_address(&old_address, &new_address);
+
+    let invitations = client.get_group_invitations(&other);
+    assert_eq!(invitations.len(), 1);
+    assert_eq!(invitations.get(0).unwrap().invitee, new_address);
+
+    client.accept_invitation(&other, &new_address);
+    assert!(client.is_group_member(&other, &new_address));
+    assert_eq!(client.get_groups_by_member(&new_address).len(), 2);
+}
+
+#[test]
+fn test_invitee_without_memberships_can_change_address() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &Vec::new(env),
+        3,
+        &token,
+    );
+
+    let invitee = Address::generate(env);
+    client.add_group_member(&id, &creator, &invitee, &10);
+    let new_address = Address::generate(env);
+    client.change_payout_address(&invitee, &new_address);
+
+    assert!(client.try_accept_invitation(&id, &invitee).is_err());
+    client.accept_invitation(&id, &new_address);
+}
@@ -0,0 +1,118 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, String, Vec};
+
+fn setup_group(test_env: &TestEnv, usages: u32) -> BytesN<32> {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: Address::generate(env),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        usages,
+        &token,
+    )
+}
+
+#[test]
+fn test_distribution_memo_is_recorded_and_found() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 3);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 600);
+
+    let inv1 = String::from_str(env, "INV-2024-001");
+    let inv2 = String::from_str(env, "INV-2024-002");
+    client.distribute(&id, &token, &100, &sender, &Some(inv1.clone()), &None);
+    client.distribute(&id, &token, &200, &sender, &None, &None);
+    client.distribute(&id, &token, &300, &sender, &Some(inv2.clone()), &None);
+
+    let history = client.get_group_distributions(&id);
+    assert_eq!(history.get(0).unwrap().memo, Some(inv1.clone()));
+    assert_eq!(history.get(1).unwrap().memo, None);
+
+    let found = client.get_distribution_by_reference(&id, &inv2);
+    assert_eq!(found.total_amount, 300);
+    assert_eq!(found.distribution_number, 2);
+    assert_eq!(
+        client
+            .get_distribution_by_reference(&id, &inv1)
+            .total_amount,
+        100
+    );
+}
+
+#[test]
+#[should_panic] // NotFound
+fn test_unknown_reference_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 1);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+
+    client.get_distribution_by_reference(&id, &String::from_str(env, "ORDER-1"));
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_memo_longer_than_limit_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 1);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+
+    let long_memo = String::from_str(env, &"x".repeat(65));
+    client.distribute(&id, &token, &100, &sender, &Some(long_memo), &None);
+}
+
+#[test]
+fn test_contribution_memo_is_recorded() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let id = setup_group(&test_env, 1);
+    client.start_fundraising(&id, &creator, &1_000);
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    let pledge = String::from_str(env, "PLEDGE-42");
+    client.contribute(&id, &token, &100, &contributor, &Some(pledge.clone()));
+
+    let contributions = client.get_group_contributions(&id);
+    assert_eq!(contributions.get(0).unwrap().memo, Some(pledge.clone()));
+    assert_eq!(
+        client
+            .get_user_contributions(&contributor)
+            .get(0)
+            .unwrap()
+            .memo,
+        Some(pledge)
+    );
+}
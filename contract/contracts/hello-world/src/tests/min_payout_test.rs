@@ -0,0 +1,131 @@
+use crate::test_utils::{
+    assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN};
+
+struct PayoutSetup {
+    id: BytesN<32>,
+    major: Address,
+    minor: Address,
+    sender: Address,
+}
+
+/// A 95/5 group with a minimum payout of 10 set by the creator.
+fn setup(test_env: &TestEnv, usages: u32) -> PayoutSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[95, 5], usages);
+    client.set_min_payout(&group.id, &10, &group.creator);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &group.token, &sender, 1_000);
+
+    PayoutSetup {
+        id: group.id,
+        major: group.members.get(0).unwrap(),
+        minor: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+#[test]
+fn test_small_shares_accumulate_until_threshold() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 3);
+    assert_eq!(client.get_group_settings(&s.id).min_payout, 10);
+
+    // The 5% share of 100 is held back
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_balance(env, &token, &s.major, 95);
+    assert_balance(env, &token, &s.minor, 0);
+    assert_eq!(client.get_pending_payout(&s.id, &s.minor, &token), 5);
+
+    // Earnings still count the share even though it is pending
+    assert_eq!(client.get_member_earnings(&s.minor, &s.id), 5);
+
+    // The second share brings the pending balance to the threshold
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_balance(env, &token, &s.minor, 10);
+    assert_eq!(client.get_pending_payout(&s.id, &s.minor, &token), 0);
+}
+
+#[test]
+fn test_claim_pending_payout_on_demand() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_eq!(client.claim_pending_payout(&s.id, &s.minor, &token), 5);
+    assert_balance(env, &token, &s.minor, 5);
+    assert_eq!(client.get_pending_payout(&s.id, &s.minor, &token), 0);
+}
+
+#[test]
+fn test_claimed_payout_goes_to_payout_address() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    let cold_wallet = Address::generate(env);
+    client.set_payout_preferences(&s.id, &s.minor, &None, &Some(cold_wallet.clone()));
+
+    client.claim_pending_payout(&s.id, &s.minor, &token);
+    assert_balance(env, &token, &cold_wallet, 5);
+    assert_balance(env, &token, &s.minor, 0);
+}
+
+#[test]
+fn test_zero_minimum_pays_every_share() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.set_min_payout(&s.id, &0, &creator);
+    client.distribute(&s.id, &token, &100, &s.sender, &None, &None);
+    assert_balance(env, &token, &s.minor, 5);
+}
+
+#[test]
+#[should_panic] // InsufficientBalance
+fn test_claim_without_pending_balance_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.claim_pending_payout(&s.id, &s.major, &token);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_non_creator_cannot_set_min_payout() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env, 1);
+
+    client.set_min_payout(&s.id, &50, &s.sender);
+}
+
+#[test]
+#[should_panic] // InvalidAmount
+fn test_negative_min_payout_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env, 1);
+
+    client.set_min_payout(&s.id, &-1, &creator);
+}
@@ -1,5 +1,5 @@
 use crate::mock_token::MockTokenClient;
-use crate::test_utils::{assert_balance, deploy_mock_token, setup_test_env};
+use crate::test_utils::{approve_tokens, assert_balance, deploy_mock_token, setup_test_env};
 use soroban_sdk::{testutils::Address as _, Address, String};
 
 #[test]
@@ -88,3 +88,48 @@ fn test_invalid_transfer_amount() {
     client.mint(&user1, &100);
     client.transfer(&user1, &user2, &-10);
 }
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let owner = Address::generate(env);
+    let spender = Address::generate(env);
+    let recipient = Address::generate(env);
+
+    let token = deploy_mock_token(
+        env,
+        &String::from_str(env, "Mock Token"),
+        &String::from_str(env, "MOCK"),
+    );
+    let client = MockTokenClient::new(env, &token);
+
+    client.mint(&owner, &1000);
+    approve_tokens(env, &token, &owner, &spender, 300);
+    assert_eq!(client.allowance(&owner, &spender), 300);
+
+    client.transfer_from(&spender, &owner, &recipient, &200);
+    assert_balance(env, &token, &owner, 800);
+    assert_balance(env, &token, &recipient, 200);
+    assert_eq!(client.allowance(&owner, &spender), 100);
+}
+
+#[test]
+#[should_panic(expected = "Insufficient allowance")]
+fn test_transfer_from_over_allowance() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let owner = Address::generate(env);
+    let spender = Address::generate(env);
+
+    let token = deploy_mock_token(
+        env,
+        &String::from_str(env, "Mock Token"),
+        &String::from_str(env, "MOCK"),
+    );
+    let client = MockTokenClient::new(env, &token);
+
+    client.mint(&owner, &1000);
+    approve_tokens(env, &token, &owner, &spender, 100);
+    client.transfer_from(&spender, &owner, &spender, &101);
+}
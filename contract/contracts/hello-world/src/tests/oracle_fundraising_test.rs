@@ -0,0 +1,227 @@
+use crate::base::types::GroupMember;
+use crate::interfaces::price_oracle::Asset;
+use crate::mock_oracle::MockOracleClient;
+use crate::test_utils::{
+    create_test_group, deploy_mock_oracle, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Vec,
+};
+
+const MAX_PRICE_AGE: u64 = 300;
+// 0.5 in the reference currency, with the oracle's 14 decimals
+const HALF: i128 = 50_000_000_000_000;
+
+/// A group with a valued campaign of 100 reference units (7 decimals) and an
+/// oracle quoting the test token at 0.5.
+fn setup(test_env: &TestEnv) -> (BytesN<32>, Address) {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let oracle = deploy_mock_oracle(env);
+    MockOracleClient::new(env, &oracle).set_price(&Asset::Stellar(token.clone()), &HALF, &1_000);
+    client.set_price_oracle(&oracle, &MAX_PRICE_AGE, &test_env.admin);
+
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: Address::generate(env),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        1,
+        &token,
+    );
+    client.start_valued_fundraising(&id, &creator, &1_000_000_000);
+
+    (id, oracle)
+}
+
+#[test]
+fn test_contributions_are_valued_in_reference_currency() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, _) = setup(&test_env);
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 2_000_000_000);
+
+    // 120 tokens at 0.5 are worth 60
+    client.contribute(&id, &token, &1_200_000_000, &contributor, &None);
+    let status = client.get_fundraising_status(&id);
+    assert_eq!(status.target_amount, 0);
+    assert_eq!(status.target_value, 1_000_000_000);
+    assert_eq!(status.total_raised, 1_200_000_000);
+    assert_eq!(status.total_raised_value, 600_000_000);
+    assert!(status.is_active);
+
+    // The raw total already exceeds 100, but the campaign closes on value
+    client.contribute(&id, &token, &800_000_000, &contributor, &None);
+    let status = client.get_fundraising_status(&id);
+    assert_eq!(status.total_raised_value, 1_000_000_000);
+    assert!(!status.is_active);
+}
+
+#[test]
+fn test_raw_campaigns_do_not_need_an_oracle() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: Address::generate(env),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        1,
+        &token,
+    );
+    client.start_fundraising(&id, &creator, &100);
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+
+    let status = client.get_fundraising_status(&id);
+    assert_eq!(status.total_raised_value, 0);
+    assert!(!status.is_active);
+}
+
+#[test]
+#[should_panic] // StalePrice
+fn test_stale_price_is_rejected() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, _) = setup(&test_env);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = 1_000 + MAX_PRICE_AGE + 1);
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+}
+
+#[test]
+#[should_panic(expected = "StalePrice")]
+fn test_future_dated_price_is_rejected() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, oracle) = setup(&test_env);
+
+    // Far enough ahead that adding the maximum age would overflow
+    MockOracleClient::new(env, &oracle).set_price(
+        &Asset::Stellar(token.clone()),
+        &HALF,
+        &(u64::MAX - 1),
+    );
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+}
+
+#[test]
+fn test_unbounded_price_age_does_not_overflow() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, oracle) = setup(&test_env);
+    client.set_price_oracle(&oracle, &u64::MAX, &test_env.admin);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000_000);
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+    assert_eq!(client.get_fundraising_status(&id).total_raised_value, 50);
+}
+
+#[test]
+fn test_refreshed_price_is_accepted() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, oracle) = setup(&test_env);
+
+    let later = 1_000 + MAX_PRICE_AGE + 1;
+    env.ledger().with_mut(|li| li.timestamp = later);
+    MockOracleClient::new(env, &oracle).set_price(
+        &Asset::Stellar(token.clone()),
+        &(2 * HALF),
+        &later,
+    );
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+    assert_eq!(client.get_fundraising_status(&id).total_raised_value, 100);
+}
+
+#[test]
+#[should_panic] // PriceUnavailable
+fn test_token_without_price_is_rejected() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, oracle) = setup(&test_env);
+
+    let token = test_env.mock_tokens.get(0).unwrap();
+    MockOracleClient::new(env, &oracle).set_price(&Asset::Stellar(token.clone()), &0, &1_000);
+
+    let contributor = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &contributor, 100);
+    client.contribute(&id, &token, &100, &contributor, &None);
+}
+
+#[test]
+#[should_panic] // OracleNotSet
+fn test_valued_campaign_requires_oracle() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: Address::generate(env),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        1,
+        &token,
+    );
+    client.start_valued_fundraising(&id, &creator, &1_000);
+}
@@ -0,0 +1,209 @@
+use crate::base::types::{GroupMember, ManagerPermission};
+use crate::test_utils::{
+    create_group_with_members, create_test_group, member, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Vec};
+
+fn single_member(test_env: &TestEnv, address: &Address) -> Vec<GroupMember> {
+    vec![&test_env.env, member(address, 100)]
+}
+
+fn setup_group(test_env: &TestEnv) -> (BytesN<32>, Address) {
+    let group = create_group_with_members(test_env, &[100], 1);
+    (group.id, group.creator)
+}
+
+#[test]
+fn test_ownership_transfer_takes_two_steps() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    let new_owner = Address::generate(env);
+    client.transfer_group_ownership(&id, &creator, &new_owner);
+    assert_eq!(client.get_pending_group_owner(&id), Some(new_owner.clone()));
+    assert_eq!(client.get(&id).creator, creator);
+
+    client.accept_group_ownership(&id, &new_owner);
+    assert_eq!(client.get(&id).creator, new_owner);
+    assert_eq!(client.get_pending_group_owner(&id), None);
+
+    // The creator lookups follow the new owner
+    assert_eq!(client.get_groups_by_creator(&new_owner).len(), 1);
+    assert_eq!(client.get_groups_by_creator(&creator).len(), 0);
+    let page = client.get_groups_by_creator_paginated(&new_owner, &0, &10);
+    assert_eq!(page.total, 1);
+    assert_eq!(page.groups.get(0).unwrap().id, id);
+
+    // The new owner can manage the group, the previous one cannot
+    client.update_members(&id, &new_owner, &single_member(&test_env, &new_owner));
+    let result = client.try_update_members(&id, &creator, &single_member(&test_env, &creator));
+    assert!(result.is_err());
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_offered_address_can_accept_ownership() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    client.transfer_group_ownership(&id, &creator, &Address::generate(env));
+    client.accept_group_ownership(&id, &Address::generate(env));
+}
+
+#[test]
+fn test_member_manager_can_update_members() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    let manager = Address::generate(env);
+    client.set_group_manager(
+        &id,
+        &creator,
+        &manager,
+        &vec![env, ManagerPermission::ManageMembers],
+    );
+    assert_eq!(client.get_group_managers(&id).len(), 1);
+
+    client.update_members(&id, &manager, &single_member(&test_env, &creator));
+    assert_eq!(
+        client.get_group_members(&id).get(0).unwrap().address,
+        creator
+    );
+
+    // The permission does not extend to deactivation
+    assert!(client.try_deactivate_group(&id, &manager).is_err());
+}
+
+#[test]
+fn test_scoped_managers_can_deactivate_fundraise_and_top_up() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, creator) = setup_group(&test_env);
+
+    let manager = Address::generate(env);
+    client.set_group_manager(
+        &id,
+        &creator,
+        &manager,
+        &vec![
+            env,
+            ManagerPermission::ManageFundraising,
+            ManagerPermission::TopUp,
+            ManagerPermission::Deactivate,
+        ],
+    );
+
+    client.start_fundraising(&id, &manager, &1_000);
+    assert!(client.get_fundraising_status(&id).is_active);
+
+    client.deactivate_group(&id, &manager);
+    assert!(!client.is_group_active(&id));
+
+    // Only the creator and top-up managers can pay for an inactive group
+    mint_tokens(env, &token, &manager, 1_000);
+    client.topup_subscription(&id, &2, &token, &manager, &None, &None);
+    assert_eq!(client.get_remaining_usages(&id), 3);
+    let stranger = Address::generate(env);
+    mint_tokens(env, &token, &stranger, 1_000);
+    let result = client.try_topup_subscription(&id, &2, &token, &stranger, &None, &None);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_anyone_can_top_up_active_group() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let (id, _) = setup_group(&test_env);
+
+    let payer = Address::generate(env);
+    mint_tokens(env, &token, &payer, 1_000);
+    client.topup_subscription(&id, &2, &token, &payer, &None, &None);
+    assert_eq!(client.get_remaining_usages(&id), 3);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_removed_manager_loses_permissions() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    let manager = Address::generate(env);
+    client.set_group_manager(
+        &id,
+        &creator,
+        &manager,
+        &vec![env, ManagerPermission::Deactivate],
+    );
+    client.set_group_manager(&id, &creator, &manager, &Vec::new(env));
+    assert_eq!(client.get_group_managers(&id).len(), 0);
+
+    client.deactivate_group(&id, &manager);
+}
+
+#[test]
+fn test_recreated_group_does_not_inherit_owner_offer_or_managers() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    let nominee = Address::generate(env);
+    let manager = Address::generate(env);
+    client.transfer_group_ownership(&id, &creator, &nominee);
+    client.set_group_manager(
+        &id,
+        &creator,
+        &manager,
+        &vec![env, ManagerPermission::Deactivate],
+    );
+    client.deactivate_group(&id, &creator);
+    client.delete_group(&id, &creator);
+
+    // Another creator registers the same id
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let new_creator = test_env.users.get(1).unwrap();
+    let members = single_member(&test_env, &Address::generate(env));
+    let recreated = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &new_creator,
+        &members,
+        1,
+        &token,
+    );
+    assert_eq!(recreated, id);
+
+    assert_eq!(client.get_pending_group_owner(&id), None);
+    assert_eq!(client.get_group_managers(&id).len(), 0);
+    assert!(client.try_accept_group_ownership(&id, &nominee).is_err());
+    assert!(client.try_deactivate_group(&id, &manager).is_err());
+    assert_eq!(client.get(&id).creator, new_creator);
+    assert!(client.get(&id).is_active);
+}
+
+#[test]
+#[should_panic(expected = "AddressDenylisted")]
+fn test_blocked_address_cannot_accept_ownership() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let (id, creator) = setup_group(&test_env);
+
+    let new_owner = Address::generate(env);
+    client.transfer_group_ownership(&id, &creator, &new_owner);
+    client.add_to_denylist(&test_env.admin, &new_owner, &1);
+    client.accept_group_ownership(&id, &new_owner);
+}
@@ -14,6 +14,8 @@ fn test_get_groups_paginated() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Create 25 groups
@@ -86,6 +88,8 @@ fn test_get_group_count() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     create_test_group(
@@ -129,6 +133,8 @@ fn test_get_groups_by_creator_paginated() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Creator 1 creates 15 groups
@@ -196,6 +202,8 @@ fn test_get_group_count_single_group() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Create exactly one group
@@ -229,6 +237,8 @@ fn test_get_group_count_large_scale() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Create 1000 groups to test performance at scale
@@ -265,6 +275,8 @@ fn test_get_group_count_after_deletion() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Create 5 groups
@@ -356,6 +368,8 @@ fn test_get_group_count_multiple_deletion_scenarios() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&test_env.env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     // Scenario 1: Delete from beginning
@@ -153,7 +153,7 @@ fn test_create_fails_when_paused() {
     let id = BytesN::from_array(&env, &[1u8; 32]);
     let name = String::from_str(&env, "Test Group");
     token_admin_client.mint(&creator, &10000000);
-    client.create(&id, &name, &creator, &100u32, &token_address);
+    client.create(&id, &name, &creator, &100u32, &token_address, &None, &None);
 }
 
 #[test]
@@ -179,13 +179,13 @@ fn test_add_member_fails_when_paused() {
     let name = String::from_str(&env, "Test Group");
 
     token_admin_client.mint(&creator, &10000000);
-    client.create(&id, &name, &creator, &100u32, &token_address);
+    client.create(&id, &name, &creator, &100u32, &token_address, &None, &None);
     client.pause(&admin);
     client.add_group_member(&id, &creator, &member, &50u32);
 }
 
 #[test]
-#[should_panic]
+#[should_panic(expected = "ContractPaused")]
 fn test_topup_subscription_fails_when_paused() {
     let env = Env::default();
     env.mock_all_auths();
@@ -206,7 +206,7 @@ fn test_topup_subscription_fails_when_paused() {
     let name = String::from_str(&env, "Test Group");
 
     token_admin_client.mint(&creator, &10000000);
-    client.create(&id, &name, &creator, &100u32, &token_address);
+    client.create(&id, &name, &creator, &100u32, &token_address, &None, &None);
 
     // Pause the contract
     client.pause(&admin);
@@ -214,7 +214,7 @@ fn test_topup_subscription_fails_when_paused() {
     // Attempt to top up while paused - should fail with ContractPaused
     let payer = Address::generate(&env);
     token_admin_client.mint(&payer, &10000000);
-    client.topup_subscription(&id, &10u32, &token_address, &payer);
+    client.topup_subscription(&id, &10u32, &token_address, &payer, &None, &None);
 }
 
 #[test]
@@ -238,7 +238,7 @@ fn test_read_functions_work_when_paused() {
     let name = String::from_str(&env, "Test Group");
 
     token_admin_client.mint(&creator, &10000000);
-    client.create(&id, &name, &creator, &100u32, &token_address);
+    client.create(&id, &name, &creator, &100u32, &token_address, &None, &None);
     client.pause(&admin);
 
     // These should all work while paused
@@ -275,7 +275,7 @@ fn test_operations_work_after_unpause() {
 
     token_admin_client.mint(&creator, &10000000);
     // Should work after unpause
-    client.create(&id, &name, &creator, &100u32, &token_address);
+    client.create(&id, &name, &creator, &100u32, &token_address, &None, &None);
     let result = client.get(&id);
     assert_eq!(result.name, name);
 }
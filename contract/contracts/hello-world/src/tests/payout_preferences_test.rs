@@ -0,0 +1,321 @@
+use crate::base::types::BatchDistribution;
+use crate::interfaces::price_oracle::Asset;
+use crate::mock_oracle::MockOracleClient;
+use crate::mock_router::MockRouterClient;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, deploy_mock_oracle, deploy_mock_router,
+    deploy_mock_token, member, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, String, Vec};
+
+struct PreferenceSetup {
+    id: BytesN<32>,
+    usdc: Address,
+    eurc: Address,
+    member1: Address,
+    member2: Address,
+    sender: Address,
+}
+
+const MAX_PRICE_AGE: u64 = 300;
+
+/// A 50/50 group paid in USDC, with EURC as a second supported token.
+fn setup(test_env: &TestEnv) -> PreferenceSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let eurc = deploy_mock_token(
+        env,
+        &String::from_str(env, "Euro Coin"),
+        &String::from_str(env, "EURC"),
+    );
+    client.add_supported_token(&eurc, &test_env.admin);
+
+    let group = create_group_with_members(test_env, &[50, 50], 3);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &group.token, &sender, 1_000);
+
+    PreferenceSetup {
+        id: group.id,
+        usdc: group.token,
+        eurc,
+        member1: group.members.get(0).unwrap(),
+        member2: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+/// Configures a router converting 10 USDC into 9 EURC, and an oracle quoting
+/// the same rate.
+fn setup_router(test_env: &TestEnv, s: &PreferenceSetup) -> Address {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let router = deploy_mock_router(env);
+    MockRouterClient::new(env, &router).set_rate(&s.usdc, &s.eurc, &9, &10);
+    mint_tokens(env, &s.eurc, &router, 10_000);
+    client.set_swap_router(&router, &test_env.admin);
+
+    let oracle = deploy_mock_oracle(env);
+    set_oracle_rate(test_env, &oracle, s, 9, 10);
+    client.set_price_oracle(&oracle, &MAX_PRICE_AGE, &test_env.admin);
+    router
+}
+
+/// Quotes USDC at `usdc` and EURC at `eurc` in the reference currency.
+fn set_oracle_rate(
+    test_env: &TestEnv,
+    oracle: &Address,
+    s: &PreferenceSetup,
+    usdc: i128,
+    eurc: i128,
+) {
+    let env = &test_env.env;
+    let oracle = MockOracleClient::new(env, oracle);
+    let now = env.ledger().timestamp();
+    oracle.set_price(&Asset::Stellar(s.usdc.clone()), &usdc, &now);
+    oracle.set_price(&Asset::Stellar(s.eurc.clone()), &eurc, &now);
+}
+
+fn assert_paid_without_swap(test_env: &TestEnv, s: &PreferenceSetup) {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    assert_balance(env, &s.usdc, &s.member1, 50);
+    assert_balance(env, &s.eurc, &s.member1, 0);
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(entry.swaps.len(), 0);
+}
+
+#[test]
+fn test_payout_address_receives_payouts() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let cold_wallet = Address::generate(env);
+    client.set_payout_preferences(&s.id, &s.member1, &None, &Some(cold_wallet.clone()));
+
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.usdc, &cold_wallet, 50);
+    assert_balance(env, &s.usdc, &s.member1, 0);
+    assert_balance(env, &s.usdc, &s.member2, 50);
+
+    // Earnings and history stay with the member
+    assert_eq!(client.get_member_earnings(&s.member1, &s.id), 50);
+}
+
+#[test]
+fn test_preferred_token_is_converted_through_router() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    let router = setup_router(&test_env, &s);
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(s.eurc.clone()), &None);
+    let stored = client.get_group_members(&s.id).get(0).unwrap();
+    assert_eq!(stored.preferred_token, Some(s.eurc.clone()));
+
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.eurc, &s.member1, 45);
+    assert_balance(env, &s.usdc, &s.member1, 0);
+    assert_balance(env, &s.usdc, &s.member2, 50);
+    assert_balance(env, &s.usdc, &router, 50);
+
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(entry.swaps.len(), 1);
+    let swap = entry.swaps.get(0).unwrap();
+    assert_eq!(swap.token_in, s.usdc);
+    assert_eq!(swap.amount_in, 50);
+    assert_eq!(swap.token_out, s.eurc);
+    assert_eq!(swap.amount_out, 45);
+}
+
+#[test]
+fn test_preview_shows_converted_payout() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    setup_router(&test_env, &s);
+
+    let cold_wallet = Address::generate(env);
+    client.set_payout_preferences(
+        &s.id,
+        &s.member1,
+        &Some(s.eurc.clone()),
+        &Some(cold_wallet.clone()),
+    );
+
+    let preview = client.preview_distribution(&s.id, &s.usdc, &100);
+    let converted = preview.payouts.get(0).unwrap();
+    assert_eq!(converted.address, cold_wallet);
+    assert_eq!(converted.token, s.eurc);
+    assert_eq!(converted.amount, 45);
+    let direct = preview.payouts.get(1).unwrap();
+    assert_eq!(direct.address, s.member2);
+    assert_eq!(direct.token, s.usdc);
+    assert_eq!(direct.amount, 50);
+
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.eurc, &cold_wallet, 45);
+}
+
+#[test]
+fn test_preferred_token_without_router_pays_original_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(s.eurc.clone()), &None);
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.usdc, &s.member1, 50);
+    assert_eq!(
+        client
+            .get_group_distributions(&s.id)
+            .get(0)
+            .unwrap()
+            .swaps
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_preferred_token_without_oracle_pays_original_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let router = deploy_mock_router(env);
+    MockRouterClient::new(env, &router).set_rate(&s.usdc, &s.eurc, &9, &10);
+    mint_tokens(env, &s.eurc, &router, 10_000);
+    client.set_swap_router(&router, &test_env.admin);
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(s.eurc.clone()), &None);
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_paid_without_swap(&test_env, &s);
+}
+
+#[test]
+fn test_swap_below_oracle_quote_pays_original_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    let router = setup_router(&test_env, &s);
+
+    // The oracle values EURC at par while the router only offers 0.9
+    let oracle = client.get_price_oracle().unwrap().oracle;
+    set_oracle_rate(&test_env, &oracle, &s, 1, 1);
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(s.eurc.clone()), &None);
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_paid_without_swap(&test_env, &s);
+    assert_balance(env, &s.usdc, &router, 0);
+}
+
+#[test]
+fn test_failed_swap_pays_original_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    setup_router(&test_env, &s);
+
+    // The router has no liquidity for a third token the member prefers
+    let illiquid = deploy_mock_token(
+        env,
+        &String::from_str(env, "Illiquid"),
+        &String::from_str(env, "ILQ"),
+    );
+    client.add_supported_token(&illiquid, &test_env.admin);
+    let oracle = client.get_price_oracle().unwrap().oracle;
+    MockOracleClient::new(env, &oracle).set_price(
+        &Asset::Stellar(illiquid.clone()),
+        &1,
+        &env.ledger().timestamp(),
+    );
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(illiquid), &None);
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    assert_paid_without_swap(&test_env, &s);
+    assert_balance(env, &s.usdc, &s.member2, 50);
+}
+
+#[test]
+fn test_short_swap_in_batch_rolls_back_the_whole_batch() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    let router = setup_router(&test_env, &s);
+
+    // member1 is paid first, then the router under-delivers member2's conversion
+    client.set_payout_preferences(&s.id, &s.member2, &Some(s.eurc.clone()), &None);
+    MockRouterClient::new(env, &router).set_shortfall(&10);
+
+    let mut items = Vec::new(env);
+    items.push_back(BatchDistribution {
+        group_id: s.id.clone(),
+        token: s.usdc.clone(),
+        amount: 100,
+    });
+    let result = client.try_distribute_batch(&items, &s.sender, &false);
+    assert!(result.is_err());
+
+    // Nothing from the partly paid item is left behind
+    assert_balance(env, &s.usdc, &s.sender, 1_000);
+    assert_balance(env, &s.usdc, &s.member1, 0);
+    assert_eq!(client.get_member_earnings(&s.member1, &s.id), 0);
+    assert_eq!(client.get_group_distributions(&s.id).len(), 0);
+    assert_eq!(client.get_remaining_usages(&s.id), 3);
+    assert_eq!(client.get_reserved_balance(&s.usdc), 0);
+}
+
+#[test]
+fn test_update_members_keeps_member_preferences() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let creator = test_env.users.get(0).unwrap();
+    let s = setup(&test_env);
+
+    let cold_wallet = Address::generate(env);
+    client.set_payout_preferences(&s.id, &s.member1, &None, &Some(cold_wallet.clone()));
+
+    let mut members = Vec::new(env);
+    members.push_back(member(&s.member1, 70));
+    members.push_back(member(&s.member2, 30));
+    client.update_members(&s.id, &creator, &members);
+
+    let stored = client.get_group_members(&s.id).get(0).unwrap();
+    assert_eq!(stored.percentage, 70);
+    assert_eq!(stored.payout_address, Some(cold_wallet));
+}
+
+#[test]
+#[should_panic] // MemberNotFound
+fn test_non_member_cannot_set_preferences() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_payout_preferences(&s.id, &Address::generate(env), &None, &None);
+}
+
+#[test]
+#[should_panic] // UnsupportedToken
+fn test_preferred_token_must_be_supported() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_payout_preferences(&s.id, &s.member1, &Some(Address::generate(env)), &None);
+}
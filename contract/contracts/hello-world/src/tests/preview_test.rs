@@ -0,0 +1,151 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Vec};
+
+/// A three-way split that does not divide evenly.
+fn setup_group(test_env: &TestEnv, usages: u32) -> BytesN<32> {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let mut members = Vec::new(env);
+    for percentage in [33u32, 33, 34] {
+        members.push_back(GroupMember {
+            address: Address::generate(env),
+            percentage,
+            preferred_token: None,
+            payout_address: None,
+        });
+    }
+    create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        usages,
+        &token,
+    )
+}
+
+#[test]
+fn test_preview_matches_real_distribution() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 2);
+    let preview = client.preview_distribution(&id, &token, &1001);
+
+    assert_eq!(preview.member_amounts.len(), 3);
+    assert_eq!(preview.member_amounts.get(0).unwrap().amount, 330);
+    assert_eq!(preview.member_amounts.get(1).unwrap().amount, 330);
+    // The last member receives the remainder
+    assert_eq!(preview.member_amounts.get(2).unwrap().amount, 341);
+    assert!(!preview.sponsored_usage);
+    assert_eq!(preview.usage_fee, 0);
+    assert_eq!(preview.remaining_usages_after, 1);
+
+    // Nothing changes until a real distribution happens
+    assert_eq!(client.get_remaining_usages(&id), 2);
+    assert_eq!(client.get_group_distributions(&id).len(), 0);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 1001);
+    client.distribute(&id, &token, &1001, &sender, &None, &None);
+
+    let actual = client.get_group_distributions(&id).get(0).unwrap();
+    assert_eq!(actual.member_amounts, preview.member_amounts);
+    assert_eq!(
+        client.get_remaining_usages(&id),
+        preview.remaining_usages_after
+    );
+}
+
+#[test]
+fn test_preview_reports_sponsored_usage() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 1);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+
+    let sponsor = Address::generate(env);
+    let pool_id = BytesN::from_array(env, &[42u8; 32]);
+    client.create_sponsor_pool(&pool_id, &sponsor, &token, &5);
+    mint_tokens(env, &token, &sponsor, 100);
+    client.fund_sponsor_pool(&pool_id, &sponsor, &100);
+    client.sponsor_group(&pool_id, &sponsor, &id);
+
+    let preview = client.preview_distribution(&id, &token, &100);
+    assert!(preview.sponsored_usage);
+    assert_eq!(preview.usage_fee, 10);
+    assert_eq!(preview.remaining_usages_after, 0);
+
+    // The pool is not charged by a preview
+    assert_eq!(client.get_sponsor_pool(&pool_id).balance, 100);
+}
+
+#[test]
+#[should_panic] // NoUsagesRemaining
+fn test_preview_fails_like_distribute_without_usages() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = setup_group(&test_env, 1);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+
+    client.preview_distribution(&id, &token, &100);
+}
+
+#[test]
+#[should_panic] // UnsupportedToken
+fn test_preview_rejects_unsupported_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let id = setup_group(&test_env, 1);
+    client.preview_distribution(&id, &Address::generate(env), &100);
+}
+
+#[test]
+fn test_preview_reports_held_payouts() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+
+    let id = setup_group(&test_env, 3);
+    let members = client.get_group_members(&id);
+    client.set_min_payout(&id, &34, &creator);
+
+    // The two 33 shares are held; the 34 share is paid
+    let preview = client.preview_distribution(&id, &token, &100);
+    assert_eq!(preview.payouts.len(), 1);
+    assert_eq!(preview.payouts.get(0).unwrap().amount, 34);
+    assert_eq!(preview.held_amounts.len(), 2);
+    let held = preview.held_amounts.get(0).unwrap();
+    assert_eq!(held.address, members.get(0).unwrap().address);
+    assert_eq!(held.amount, 33);
+
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+
+    // Held balances are paid once the next share brings them to the minimum
+    let preview = client.preview_distribution(&id, &token, &100);
+    assert_eq!(preview.held_amounts.len(), 0);
+    assert_eq!(preview.payouts.get(0).unwrap().amount, 66);
+    assert_eq!(preview.payouts.get(2).unwrap().amount, 34);
+}
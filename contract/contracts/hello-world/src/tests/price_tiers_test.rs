@@ -0,0 +1,173 @@
+use crate::base::types::PriceTier;
+use crate::test_utils::{assert_balance, mint_tokens, setup_test_env};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, String, Vec};
+
+fn default_tiers(env: &soroban_sdk::Env) -> Vec<PriceTier> {
+    let mut tiers = Vec::new(env);
+    tiers.push_back(PriceTier {
+        min_usages: 1,
+        discount_percentage: 0,
+    });
+    tiers.push_back(PriceTier {
+        min_usages: 100,
+        discount_percentage: 20,
+    });
+    tiers.push_back(PriceTier {
+        min_usages: 1000,
+        discount_percentage: 40,
+    });
+    tiers
+}
+
+#[test]
+fn test_quote_without_tiers_uses_full_fee() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    assert_eq!(client.get_price_tiers().len(), 0);
+
+    let quote = client.get_usage_quote(&250);
+    assert_eq!(quote.usage_count, 250);
+    assert_eq!(quote.unit_price, 10);
+    assert_eq!(quote.total_cost, 2500);
+}
+
+#[test]
+fn test_quote_applies_highest_reached_tier() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    client.set_price_tiers(&default_tiers(env), &test_env.admin);
+    assert_eq!(client.get_price_tiers().len(), 3);
+
+    let small = client.get_usage_quote(&99);
+    assert_eq!(small.unit_price, 10);
+    assert_eq!(small.total_cost, 990);
+
+    let medium = client.get_usage_quote(&100);
+    assert_eq!(medium.unit_price, 8);
+    assert_eq!(medium.total_cost, 800);
+
+    let large = client.get_usage_quote(&1000);
+    assert_eq!(large.unit_price, 6);
+    assert_eq!(large.total_cost, 6000);
+}
+
+#[test]
+fn test_create_and_topup_charge_tiered_price() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    client.set_price_tiers(&default_tiers(env), &test_env.admin);
+
+    mint_tokens(env, &token, &creator, 20_000);
+    let id = BytesN::from_array(env, &[7u8; 32]);
+    client.create(
+        &id,
+        &String::from_str(env, "Tiered"),
+        &creator,
+        &100,
+        &token,
+        &None,
+        &None,
+    );
+    assert_balance(env, &token, &creator, 20_000 - 800);
+
+    client.topup_subscription(&id, &1000, &token, &creator, &None, &None);
+    assert_balance(env, &token, &creator, 20_000 - 800 - 6000);
+
+    let history = client.get_group_payment_history(&id);
+    assert_eq!(history.len(), 2);
+
+    let creation = history.get(0).unwrap();
+    assert_eq!(creation.usages_purchased, 100);
+    assert_eq!(creation.unit_price, 8);
+    assert_eq!(creation.amount_paid, 800);
+
+    let topup = history.get(1).unwrap();
+    assert_eq!(topup.usages_purchased, 1000);
+    assert_eq!(topup.unit_price, 6);
+    assert_eq!(topup.amount_paid, 6000);
+}
+
+#[test]
+fn test_full_discount_tier_charges_nothing() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    let mut tiers = Vec::new(env);
+    tiers.push_back(PriceTier {
+        min_usages: 1,
+        discount_percentage: 100,
+    });
+    client.set_price_tiers(&tiers, &test_env.admin);
+
+    let id = BytesN::from_array(env, &[8u8; 32]);
+    client.create(
+        &id,
+        &String::from_str(env, "Free"),
+        &creator,
+        &5,
+        &token,
+        &None,
+        &None,
+    );
+
+    assert_eq!(client.get_remaining_usages(&id), 5);
+    let payment = client.get_user_payment_history(&creator).get(0).unwrap();
+    assert_eq!(payment.unit_price, 0);
+    assert_eq!(payment.amount_paid, 0);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_set_price_tiers_rejects_unordered_tiers() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let mut tiers = Vec::new(env);
+    tiers.push_back(PriceTier {
+        min_usages: 100,
+        discount_percentage: 20,
+    });
+    tiers.push_back(PriceTier {
+        min_usages: 50,
+        discount_percentage: 10,
+    });
+    client.set_price_tiers(&tiers, &test_env.admin);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_set_price_tiers_rejects_discount_over_100() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let mut tiers = Vec::new(env);
+    tiers.push_back(PriceTier {
+        min_usages: 10,
+        discount_percentage: 101,
+    });
+    client.set_price_tiers(&tiers, &test_env.admin);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_set_price_tiers_non_admin_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let not_admin = test_env.users.get(1).unwrap();
+    client.set_price_tiers(&default_tiers(env), &not_admin);
+}
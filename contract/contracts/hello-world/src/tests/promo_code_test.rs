@@ -0,0 +1,220 @@
+use crate::test_utils::{assert_balance, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, BytesN, Env, String,
+};
+
+fn code(env: &Env, raw: &str) -> Bytes {
+    Bytes::from_slice(env, raw.as_bytes())
+}
+
+fn code_hash(env: &Env, raw: &str) -> BytesN<32> {
+    env.crypto().sha256(&code(env, raw)).into()
+}
+
+fn create_group(
+    test_env: &TestEnv,
+    creator: &Address,
+    usages: u32,
+    promo: Option<Bytes>,
+) -> BytesN<32> {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let id = BytesN::from_array(env, &[usages as u8; 32]);
+    client.create(
+        &id,
+        &String::from_str(env, "Promo"),
+        creator,
+        &usages,
+        &token,
+        &None,
+        &promo,
+    );
+    id
+}
+
+#[test]
+fn test_discount_promo_code_reduces_price() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.create_promo_code(
+        &code_hash(env, "LAUNCH50"),
+        &50,
+        &0,
+        &10,
+        &1_000,
+        &test_env.admin,
+    );
+
+    mint_tokens(env, &token, &creator, 1_000);
+    let id = create_group(&test_env, &creator, 10, Some(code(env, "LAUNCH50")));
+
+    // 10 usages at half of the fee of 10
+    assert_balance(env, &token, &creator, 1_000 - 50);
+
+    let payment = client.get_group_payment_history(&id).get(0).unwrap();
+    assert_eq!(payment.unit_price, 5);
+    assert_eq!(payment.amount_paid, 50);
+    assert_eq!(payment.promo_code, Some(code_hash(env, "LAUNCH50")));
+    assert!(!payment.fee_waived);
+
+    assert_eq!(
+        client
+            .get_promo_code(&code_hash(env, "LAUNCH50"))
+            .redemptions,
+        1
+    );
+}
+
+#[test]
+fn test_free_usages_promo_code_on_topup() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.create_promo_code(
+        &code_hash(env, "FREE5"),
+        &0,
+        &5,
+        &10,
+        &1_000,
+        &test_env.admin,
+    );
+
+    mint_tokens(env, &token, &creator, 1_000);
+    let id = create_group(&test_env, &creator, 1, None);
+    client.topup_subscription(&id, &8, &token, &creator, &None, &Some(code(env, "FREE5")));
+
+    // 1 usage at creation, then 3 of the 8 top-up usages are charged
+    assert_balance(env, &token, &creator, 1_000 - 10 - 30);
+    assert_eq!(client.get_remaining_usages(&id), 9);
+
+    let topup = client.get_group_payment_history(&id).get(1).unwrap();
+    assert_eq!(topup.usages_purchased, 8);
+    assert_eq!(topup.amount_paid, 30);
+}
+
+#[test]
+fn test_fee_waiver_skips_payment() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.add_fee_waiver(&creator, &test_env.admin);
+    assert!(client.is_fee_waived(&creator));
+
+    // The creator holds no tokens at all
+    let id = create_group(&test_env, &creator, 20, None);
+    assert_balance(env, &token, &creator, 0);
+    assert_eq!(client.get_remaining_usages(&id), 20);
+
+    let payment = client.get_group_payment_history(&id).get(0).unwrap();
+    assert!(payment.fee_waived);
+    assert_eq!(payment.amount_paid, 0);
+
+    client.remove_fee_waiver(&creator, &test_env.admin);
+    assert!(!client.is_fee_waived(&creator));
+}
+
+#[test]
+#[should_panic] // PromoCodeExpired
+fn test_expired_promo_code_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.create_promo_code(
+        &code_hash(env, "OLD"),
+        &10,
+        &0,
+        &10,
+        &1_000,
+        &test_env.admin,
+    );
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+    mint_tokens(env, &token, &creator, 1_000);
+    create_group(&test_env, &creator, 10, Some(code(env, "OLD")));
+}
+
+#[test]
+#[should_panic] // PromoCodeExhausted
+fn test_promo_code_max_redemptions() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.create_promo_code(
+        &code_hash(env, "ONCE"),
+        &10,
+        &0,
+        &1,
+        &1_000,
+        &test_env.admin,
+    );
+
+    mint_tokens(env, &token, &creator, 1_000);
+    let id = create_group(&test_env, &creator, 10, Some(code(env, "ONCE")));
+    client.topup_subscription(&id, &5, &token, &creator, &None, &Some(code(env, "ONCE")));
+}
+
+#[test]
+#[should_panic] // InvalidPromoCode
+fn test_deactivated_promo_code_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    client.create_promo_code(
+        &code_hash(env, "STOP"),
+        &10,
+        &0,
+        &10,
+        &1_000,
+        &test_env.admin,
+    );
+    client.deactivate_promo_code(&code_hash(env, "STOP"), &test_env.admin);
+
+    mint_tokens(env, &token, &creator, 1_000);
+    create_group(&test_env, &creator, 10, Some(code(env, "STOP")));
+}
+
+#[test]
+#[should_panic] // InvalidPromoCode
+fn test_unknown_promo_code_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = Address::generate(env);
+
+    mint_tokens(env, &token, &creator, 1_000);
+    create_group(&test_env, &creator, 10, Some(code(env, "NOPE")));
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_non_admin_cannot_create_promo_code() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let not_admin = test_env.users.get(1).unwrap();
+    client.create_promo_code(&code_hash(env, "X"), &10, &0, &10, &1_000, &not_admin);
+}
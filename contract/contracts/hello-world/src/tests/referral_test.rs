@@ -0,0 +1,142 @@
+use crate::test_utils::{assert_balance, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, String};
+
+fn create_referred_group(test_env: &TestEnv, referrer: &Address, usages: u32) -> BytesN<32> {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    mint_tokens(env, &token, &creator, 10_000);
+    let id = BytesN::from_array(env, &[3u8; 32]);
+    client.create(
+        &id,
+        &String::from_str(env, "Referred"),
+        &creator,
+        &usages,
+        &token,
+        &Some(referrer.clone()),
+        &None,
+    );
+    id
+}
+
+#[test]
+fn test_referral_rate_defaults_to_zero() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    assert_eq!(client.get_referral_rate(), 0);
+
+    // Referrals are still tracked without a reward
+    let referrer = Address::generate(env);
+    let id = create_referred_group(&test_env, &referrer, 10);
+    let stats = client.get_referral_stats(&referrer);
+    assert_eq!(stats.referred_groups.len(), 1);
+    assert_eq!(stats.referred_groups.get(0).unwrap(), id);
+    assert_eq!(stats.total_earned.len(), 0);
+}
+
+#[test]
+fn test_create_and_topup_credit_referrer() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    client.set_referral_rate(&10, &test_env.admin);
+    assert_eq!(client.get_referral_rate(), 10);
+
+    let referrer = Address::generate(env);
+    // 10 usages at fee 10 = 100, referrer earns 10
+    let id = create_referred_group(&test_env, &referrer, 10);
+    // 20 usages at fee 10 = 200, referrer earns 20
+    client.topup_subscription(&id, &20, &token, &creator, &Some(referrer.clone()), &None);
+
+    let stats = client.get_referral_stats(&referrer);
+    assert_eq!(stats.referred_groups.len(), 1);
+    let earned = stats.total_earned.get(0).unwrap();
+    assert_eq!(earned.token, token);
+    assert_eq!(earned.amount, 30);
+    assert_eq!(stats.claimable.get(0).unwrap().amount, 30);
+}
+
+#[test]
+fn test_claim_referral_rewards() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    client.set_referral_rate(&25, &test_env.admin);
+    let referrer = Address::generate(env);
+    create_referred_group(&test_env, &referrer, 10);
+
+    let claimed = client.claim_referral_rewards(&referrer, &token);
+    assert_eq!(claimed, 25);
+    assert_balance(env, &token, &referrer, 25);
+
+    let stats = client.get_referral_stats(&referrer);
+    assert_eq!(stats.claimable.len(), 0);
+    // Lifetime earnings are kept after claiming
+    assert_eq!(stats.total_earned.get(0).unwrap().amount, 25);
+}
+
+#[test]
+#[should_panic] // InsufficientBalance
+fn test_claim_without_rewards_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let referrer = Address::generate(env);
+    client.claim_referral_rewards(&referrer, &token);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_self_referral_fails() {
+    let test_env = setup_test_env();
+    let creator = test_env.users.get(0).unwrap();
+
+    create_referred_group(&test_env, &creator, 10);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_referral_rate_above_100_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    client.set_referral_rate(&101, &test_env.admin);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_non_admin_cannot_set_referral_rate() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+
+    let not_admin = test_env.users.get(1).unwrap();
+    client.set_referral_rate(&10, &not_admin);
+}
+
+#[test]
+#[should_panic(expected = "AddressDenylisted")]
+fn test_blocked_referrer_cannot_claim_rewards() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    client.set_referral_rate(&25, &test_env.admin);
+    let referrer = Address::generate(env);
+    create_referred_group(&test_env, &referrer, 10);
+
+    client.add_to_denylist(&test_env.admin, &referrer, &1);
+    client.claim_referral_rewards(&referrer, &token);
+}
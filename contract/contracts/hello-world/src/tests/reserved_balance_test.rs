@@ -0,0 +1,168 @@
+use crate::base::types::RoundingPolicy;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN};
+
+/// A 60/40 group. Creating it leaves `usages` usage fees of 10 in the contract.
+fn two_member_group(test_env: &TestEnv, usages: u32) -> (BytesN<32>, Address, Address) {
+    let group = create_group_with_members(test_env, &[60, 40], usages);
+    (
+        group.id,
+        group.members.get(0).unwrap(),
+        group.members.get(1).unwrap(),
+    )
+}
+
+#[test]
+fn test_admin_cannot_withdraw_group_deposits() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let recipient = Address::generate(env);
+
+    let (id, _, _) = two_member_group(&test_env, 2);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+    client.deposit(&id, &token, &500, &payer);
+    assert_eq!(client.get_reserved_balance(&token), 500);
+
+    assert!(client
+        .try_withdraw(&test_env.admin, &token, &21, &recipient)
+        .is_err());
+    // The usage fees are still the admin's to withdraw
+    client.withdraw(&test_env.admin, &token, &20, &recipient);
+    assert_balance(env, &token, &recipient, 20);
+    assert_eq!(client.get_group_balance(&id, &token), 500);
+}
+
+#[test]
+fn test_distributed_deposits_are_released() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _, _) = two_member_group(&test_env, 1);
+    let payer = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &payer, 500);
+    client.deposit(&id, &token, &500, &payer);
+
+    client.distribute_balance(&id, &token, &payer);
+    assert_eq!(client.get_reserved_balance(&token), 0);
+}
+
+#[test]
+fn test_sponsor_pool_funds_are_reserved() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let sponsor = test_env.users.get(1).unwrap();
+    let recipient = Address::generate(env);
+
+    let pool_id = BytesN::from_array(env, &[7u8; 32]);
+    mint_tokens(env, &token, &sponsor, 1_000);
+    client.create_sponsor_pool(&pool_id, &sponsor, &token, &5);
+    client.fund_sponsor_pool(&pool_id, &sponsor, &1_000);
+    assert_eq!(client.get_reserved_balance(&token), 1_000);
+    assert!(client
+        .try_withdraw(&test_env.admin, &token, &1, &recipient)
+        .is_err());
+
+    client.withdraw_sponsor_funds(&pool_id, &sponsor, &400);
+    assert_eq!(client.get_reserved_balance(&token), 600);
+}
+
+#[test]
+fn test_pending_payouts_are_reserved_until_claimed() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+
+    let (id, member1, member2) = two_member_group(&test_env, 2);
+    client.set_min_payout(&id, &100, &creator);
+    mint_tokens(env, &token, &sender, 50);
+    client.distribute(&id, &token, &50, &sender, &None, &None);
+    assert_eq!(client.get_reserved_balance(&token), 50);
+
+    client.claim_pending_payout(&id, &member1, &token);
+    assert_eq!(client.get_reserved_balance(&token), 20);
+    assert_eq!(client.get_pending_payout(&id, &member2, &token), 20);
+}
+
+#[test]
+fn test_carried_dust_is_reserved() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+
+    let (id, _, _) = two_member_group(&test_env, 2);
+    client.set_rounding_policy(&id, &RoundingPolicy::CarryForward, &creator);
+    mint_tokens(env, &token, &sender, 200);
+
+    client.distribute(&id, &token, &101, &sender, &None, &None);
+    assert_eq!(client.get_dust_credit(&id, &token), 1);
+    assert_eq!(client.get_reserved_balance(&token), 1);
+
+    client.distribute(&id, &token, &99, &sender, &None, &None);
+    assert_eq!(client.get_dust_credit(&id, &token), 0);
+    assert_eq!(client.get_reserved_balance(&token), 0);
+}
+
+#[test]
+fn test_group_with_deposits_cannot_be_deleted() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let payer = test_env.users.get(1).unwrap();
+
+    let (id, member1, member2) = two_member_group(&test_env, 1);
+    mint_tokens(env, &token, &payer, 500);
+    client.deposit(&id, &token, &500, &payer);
+    client.deactivate_group(&id, &creator);
+    assert!(client.try_delete_group(&id, &creator).is_err());
+    assert_eq!(client.get_group_balance(&id, &token), 500);
+
+    // Once the deposit has been paid out the group can go
+    client.activate_group(&id, &creator);
+    client.distribute_balance(&id, &token, &payer);
+    client.deactivate_group(&id, &creator);
+    client.delete_group(&id, &creator);
+    assert_balance(env, &token, &member1, 300);
+    assert_balance(env, &token, &member2, 200);
+    assert_eq!(client.get_reserved_balance(&token), 0);
+}
+
+#[test]
+fn test_deleting_group_pays_carried_dust_to_creator() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+
+    let (id, _, _) = two_member_group(&test_env, 2);
+    client.set_rounding_policy(&id, &RoundingPolicy::CarryForward, &creator);
+    mint_tokens(env, &token, &sender, 101);
+    client.distribute(&id, &token, &101, &sender, &None, &None);
+    assert_eq!(client.get_dust_credit(&id, &token), 1);
+
+    let creator_balance = token::TokenClient::new(env, &token).balance(&creator);
+    client.deactivate_group(&id, &creator);
+    client.delete_group(&id, &creator);
+    assert_balance(env, &token, &creator, creator_balance + 1);
+    assert_eq!(client.get_dust_credit(&id, &token), 0);
+    assert_eq!(client.get_reserved_balance(&token), 0);
+}
@@ -0,0 +1,223 @@
+use crate::base::types::RoundingPolicy;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, mint_tokens, setup_test_env, TestEnv, TestGroup,
+};
+use crate::AutoShareContractClient;
+use quickcheck::TestResult;
+use quickcheck_macros::quickcheck;
+use soroban_sdk::{testutils::Address as _, token, Address, BytesN};
+
+/// Creates a group with the given split and mints enough for a few distributions.
+fn setup_group(test_env: &TestEnv, percentages: &[u32], usages: u32) -> TestGroup {
+    let group = create_group_with_members(test_env, percentages, usages);
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(&test_env.env, &group.token, &sender, 100_000);
+    group
+}
+
+fn distribute(test_env: &TestEnv, id: &BytesN<32>, amount: i128) {
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let sender = test_env.users.get(1).unwrap();
+    client.distribute(id, &token, &amount, &sender, &None, &None);
+}
+
+#[test]
+fn test_default_policy_gives_dust_to_last_member() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let s = setup_group(&test_env, &[34, 33, 33], 1);
+    assert_eq!(
+        client.get_group_settings(&s.id).rounding_policy,
+        RoundingPolicy::LastMember
+    );
+
+    distribute(&test_env, &s.id, 103);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 35);
+    assert_balance(env, &token, &s.members.get(1).unwrap(), 33);
+    assert_balance(env, &token, &s.members.get(2).unwrap(), 35);
+}
+
+#[test]
+fn test_largest_share_receives_dust() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let s = setup_group(&test_env, &[34, 33, 33], 1);
+    client.set_rounding_policy(&s.id, &RoundingPolicy::LargestShare, &creator);
+
+    distribute(&test_env, &s.id, 103);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 37);
+    assert_balance(env, &token, &s.members.get(1).unwrap(), 33);
+    assert_balance(env, &token, &s.members.get(2).unwrap(), 33);
+}
+
+#[test]
+fn test_creator_receives_dust() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let s = setup_group(&test_env, &[34, 33, 33], 1);
+    client.set_rounding_policy(&s.id, &RoundingPolicy::Creator, &creator);
+
+    let creator_before = token::Client::new(env, &token).balance(&creator);
+    distribute(&test_env, &s.id, 103);
+    assert_balance(env, &token, &s.members.get(2).unwrap(), 33);
+    assert_balance(env, &token, &creator, creator_before + 2);
+
+    // The creator's payout is part of the recorded split
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(entry.member_amounts.len(), 4);
+    assert_eq!(entry.member_amounts.get(3).unwrap().address, creator);
+}
+
+#[test]
+fn test_dust_recipient_receives_dust() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let s = setup_group(&test_env, &[34, 33, 33], 1);
+    let treasury = Address::generate(env);
+    client.set_rounding_policy(
+        &s.id,
+        &RoundingPolicy::DustRecipient(treasury.clone()),
+        &creator,
+    );
+
+    distribute(&test_env, &s.id, 103);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 35);
+    assert_balance(env, &token, &s.members.get(2).unwrap(), 33);
+    assert_balance(env, &token, &treasury, 2);
+}
+
+#[test]
+fn test_largest_remainder_spreads_dust() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    // 9 * 20% = 1.8, 9 * 45% = 4.05, 9 * 35% = 3.15: the first member has
+    // the largest remainder although neither the largest nor the last share
+    let s = setup_group(&test_env, &[20, 45, 35], 1);
+    client.set_rounding_policy(&s.id, &RoundingPolicy::LargestRemainder, &creator);
+
+    distribute(&test_env, &s.id, 9);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 2);
+    assert_balance(env, &token, &s.members.get(1).unwrap(), 4);
+    assert_balance(env, &token, &s.members.get(2).unwrap(), 3);
+}
+
+#[test]
+fn test_carry_forward_credits_next_distribution() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let s = setup_group(&test_env, &[34, 33, 33], 2);
+    client.set_rounding_policy(&s.id, &RoundingPolicy::CarryForward, &creator);
+
+    let held_before = token::Client::new(env, &token).balance(&test_env.autoshare_contract);
+    distribute(&test_env, &s.id, 103);
+    assert_eq!(client.get_dust_credit(&s.id, &token), 2);
+    assert_balance(env, &token, &test_env.autoshare_contract, held_before + 2);
+
+    // 98 + 2 carried splits evenly, so the credit is used up
+    let preview = client.preview_distribution(&s.id, &token, &98);
+    assert_eq!(preview.carried_dust, 0);
+
+    distribute(&test_env, &s.id, 98);
+    assert_eq!(client.get_dust_credit(&s.id, &token), 0);
+    assert_balance(env, &token, &s.members.get(0).unwrap(), 35 + 34);
+    assert_balance(env, &token, &s.members.get(1).unwrap(), 33 + 33);
+    assert_balance(env, &token, &test_env.autoshare_contract, held_before);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_non_creator_cannot_set_rounding_policy() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let other = test_env.users.get(1).unwrap();
+
+    let s = setup_group(&test_env, &[50, 50], 1);
+    client.set_rounding_policy(&s.id, &RoundingPolicy::LargestShare, &other);
+}
+
+fn policy_from_seed(env: &soroban_sdk::Env, seed: u8) -> RoundingPolicy {
+    match seed % 6 {
+        0 => RoundingPolicy::LastMember,
+        1 => RoundingPolicy::LargestShare,
+        2 => RoundingPolicy::Creator,
+        3 => RoundingPolicy::DustRecipient(Address::generate(env)),
+        4 => RoundingPolicy::LargestRemainder,
+        _ => RoundingPolicy::CarryForward,
+    }
+}
+
+// Every payout plus any carried dust adds up to exactly the distributed amount.
+#[quickcheck]
+fn prop_shares_always_sum_to_amount(
+    amount: u16,
+    weights: (u8, u8, u8, u8),
+    seed: u8,
+) -> TestResult {
+    if amount == 0 {
+        return TestResult::discard();
+    }
+
+    // Up to five members: small shares for the first ones, the rest to the last
+    let member_count = 1 + (seed as usize / 6) % 5;
+    let mut percentages = [0u32; 5];
+    let mut allocated = 0;
+    for (idx, weight) in [weights.0, weights.1, weights.2, weights.3]
+        .iter()
+        .take(member_count - 1)
+        .enumerate()
+    {
+        percentages[idx] = 1 + (*weight as u32) % 18;
+        allocated += percentages[idx];
+    }
+    percentages[member_count - 1] = 100 - allocated;
+
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let s = setup_group(&test_env, &percentages[..member_count], 1);
+    client.set_rounding_policy(&s.id, &policy_from_seed(env, seed), &creator);
+
+    let amount = amount as i128;
+    let preview = client.preview_distribution(&s.id, &token, &amount);
+    let mut paid: i128 = 0;
+    for entry in preview.member_amounts.iter() {
+        paid += entry.amount;
+    }
+    if paid + preview.carried_dust != amount {
+        return TestResult::failed();
+    }
+
+    distribute(&test_env, &s.id, amount);
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    TestResult::from_bool(
+        entry.member_amounts == preview.member_amounts
+            && client.get_dust_credit(&s.id, &token) == preview.carried_dust,
+    )
+}
@@ -0,0 +1,240 @@
+use crate::base::types::{BlockedSharePolicy, GovernanceConfig, GroupMember, ProposalAction};
+use crate::test_utils::{
+    assert_balance, create_group_with_members, member, mint_tokens, setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Vec,
+};
+
+const DELAY: u64 = 86_400;
+
+struct TimelockSetup {
+    id: BytesN<32>,
+    creator: Address,
+    token: Address,
+    member1: Address,
+    member2: Address,
+    sender: Address,
+}
+
+fn split(test_env: &TestEnv, first: (&Address, u32), second: (&Address, u32)) -> Vec<GroupMember> {
+    vec![
+        &test_env.env,
+        member(first.0, first.1),
+        member(second.0, second.1),
+    ]
+}
+
+/// A 60/40 group with a one day split timelock.
+fn setup(test_env: &TestEnv) -> TimelockSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(test_env, &[60, 40], 5);
+    client.set_split_timelock(&group.id, &DELAY, &group.creator);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &group.token, &sender, 1_000);
+
+    TimelockSetup {
+        id: group.id,
+        creator: group.creator,
+        token: group.token,
+        member1: group.members.get(0).unwrap(),
+        member2: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+fn queue_rebalance(test_env: &TestEnv, s: &TimelockSetup) {
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let members = split(test_env, (&s.member1, 20), (&s.member2, 80));
+    client.update_members(&s.id, &s.creator, &members);
+}
+
+fn wait(test_env: &TestEnv, seconds: u64) {
+    test_env.env.ledger().with_mut(|li| li.timestamp += seconds);
+}
+
+#[test]
+fn test_queued_change_applies_after_delay() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    let pending = client.get_pending_split_change(&s.id).unwrap();
+    assert_eq!(pending.effective_at, env.ledger().timestamp() + DELAY);
+    assert_eq!(pending.members.get(1).unwrap().percentage, 80);
+
+    // Distributions during the delay use the current split
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.token, &s.member1, 60);
+    assert_balance(env, &s.token, &s.member2, 40);
+
+    wait(&test_env, DELAY);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.token, &s.member1, 80);
+    assert_balance(env, &s.token, &s.member2, 120);
+    assert_eq!(client.get_pending_split_change(&s.id), None);
+}
+
+#[test]
+fn test_cancelled_change_never_applies() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    client.cancel_split_change(&s.id, &s.creator);
+    assert_eq!(client.get_pending_split_change(&s.id), None);
+
+    wait(&test_env, DELAY);
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.token, &s.member1, 60);
+    assert_eq!(
+        client.get_group_members(&s.id).get(0).unwrap().percentage,
+        60
+    );
+}
+
+#[test]
+fn test_anyone_can_apply_due_change() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    wait(&test_env, DELAY);
+    client.apply_split_change(&s.id);
+
+    assert_eq!(
+        client.get_group_members(&s.id).get(0).unwrap().percentage,
+        20
+    );
+}
+
+#[test]
+#[should_panic] // TimelockActive
+fn test_change_cannot_apply_early() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    wait(&test_env, DELAY - 1);
+    client.apply_split_change(&s.id);
+}
+
+#[test]
+#[should_panic] // AlreadyExists
+fn test_only_one_change_can_be_queued() {
+    let test_env = setup_test_env();
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    queue_rebalance(&test_env, &s);
+}
+
+#[test]
+#[should_panic] // InvalidInput
+fn test_timelock_cannot_be_lowered() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.set_split_timelock(&s.id, &0, &s.creator);
+}
+
+#[test]
+#[should_panic] // TimelockActive
+fn test_direct_member_changes_are_blocked() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.remove_group_member(&s.id, &s.creator, &s.member2);
+}
+
+#[test]
+fn test_preview_uses_due_change() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    wait(&test_env, DELAY);
+
+    let preview = client.preview_distribution(&s.id, &s.token, &100);
+    assert_eq!(preview.member_amounts.get(0).unwrap().amount, 20);
+    assert_eq!(preview.member_amounts.get(1).unwrap().amount, 80);
+}
+
+#[test]
+fn test_change_that_cannot_apply_stays_queued() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    queue_rebalance(&test_env, &s);
+    client.set_blocked_share_policy(&test_env.admin, &BlockedSharePolicy::Escrow);
+    client.add_to_denylist(&test_env.admin, &s.member2, &1);
+    wait(&test_env, DELAY);
+
+    // Distributions go on with the current split
+    client.distribute(&s.id, &s.token, &100, &s.sender, &None, &None);
+    assert_balance(env, &s.token, &s.member1, 60);
+    assert!(client.get_pending_split_change(&s.id).is_some());
+    assert!(client.try_apply_split_change(&s.id).is_err());
+
+    client.remove_from_denylist(&test_env.admin, &s.member2);
+    client.apply_split_change(&s.id);
+    assert_eq!(client.get_pending_split_change(&s.id), None);
+}
+
+#[test]
+fn test_executed_proposal_waits_for_timelock() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.enable_governance(
+        &s.id,
+        &s.creator,
+        &GovernanceConfig {
+            quorum: 50,
+            threshold: 51,
+            voting_period: 10,
+            execution_window: 10,
+        },
+    );
+    let members = split(&test_env, (&s.member1, 20), (&s.member2, 80));
+    let proposal_id = client.propose(&s.id, &s.member1, &ProposalAction::UpdateMembers(members));
+    client.vote(&s.id, &proposal_id, &s.member1, &true);
+    wait(&test_env, 10);
+    client.execute_proposal(&s.id, &proposal_id);
+
+    assert!(client.get_pending_split_change(&s.id).is_some());
+    assert_eq!(
+        client.get_group_members(&s.id).get(0).unwrap().percentage,
+        60
+    );
+}
+
+#[test]
+#[should_panic(expected = "InvitationsPending")]
+fn test_timelock_cannot_be_enabled_with_pending_invitations() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(&test_env, &[60, 40], 1);
+    let (id, creator) = (group.id, group.creator);
+
+    client.remove_group_member(&id, &creator, &group.members.get(1).unwrap());
+    client.add_group_member(&id, &creator, &Address::generate(env), &40);
+    client.set_split_timelock(&id, &DELAY, &creator);
+}
@@ -0,0 +1,187 @@
+use crate::base::types::GroupMember;
+use crate::test_utils::{assert_balance, create_test_group, mint_tokens, setup_test_env, TestEnv};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Vec};
+
+fn setup_exhausted_group(test_env: &TestEnv) -> (BytesN<32>, Address) {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let member = Address::generate(env);
+    let mut members = Vec::new(env);
+    members.push_back(GroupMember {
+        address: member.clone(),
+        percentage: 100,
+        preferred_token: None,
+        payout_address: None,
+    });
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &members,
+        1,
+        &token,
+    );
+
+    // Use up the only paid usage
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+    assert_eq!(client.get_remaining_usages(&id), 0);
+
+    (id, member)
+}
+
+fn setup_pool(test_env: &TestEnv, sponsor: &Address, per_group_limit: u32) -> BytesN<32> {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let pool_id = BytesN::from_array(env, &[42u8; 32]);
+    client.create_sponsor_pool(&pool_id, sponsor, &token, &per_group_limit);
+    mint_tokens(env, &token, sponsor, 1_000);
+    client.fund_sponsor_pool(&pool_id, sponsor, &1_000);
+    pool_id
+}
+
+#[test]
+fn test_sponsored_group_distributes_after_running_out() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, member) = setup_exhausted_group(&test_env);
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+    client.sponsor_group(&pool_id, &sponsor, &id);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+    client.distribute(&id, &token, &200, &sender, &None, &None);
+
+    assert_balance(env, &token, &member, 300);
+    assert_eq!(client.get_remaining_usages(&id), 0);
+    assert_eq!(client.get_total_usages_paid(&id), 2);
+
+    let pool = client.get_sponsor_pool(&pool_id);
+    assert_eq!(pool.balance, 990);
+    assert_eq!(pool.total_spent, 10);
+
+    let spending = client.get_sponsor_spending(&pool_id);
+    assert_eq!(spending.len(), 1);
+    let entry = spending.get(0).unwrap();
+    assert_eq!(entry.group_id, id);
+    assert_eq!(entry.usages_sponsored, 1);
+    assert_eq!(entry.amount_spent, 10);
+
+    // The sponsor appears in the group's payment history
+    let history = client.get_group_payment_history(&id);
+    let sponsored = history.get(history.len() - 1).unwrap();
+    assert_eq!(sponsored.user, sponsor);
+    assert_eq!(sponsored.usages_purchased, 1);
+    assert_eq!(sponsored.amount_paid, 10);
+}
+
+#[test]
+fn test_creator_sponsorship_covers_creator_groups() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let (id, _) = setup_exhausted_group(&test_env);
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+    client.sponsor_creator(&pool_id, &sponsor, &creator);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+
+    assert_eq!(client.get_sponsor_pool(&pool_id).balance, 990);
+}
+
+#[test]
+#[should_panic] // NoUsagesRemaining
+fn test_sponsorship_stops_at_per_group_limit() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _) = setup_exhausted_group(&test_env);
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 1);
+    client.sponsor_group(&pool_id, &sponsor, &id);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 200);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+}
+
+#[test]
+#[should_panic] // NoUsagesRemaining
+fn test_unsponsored_group_still_fails_without_usages() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let (id, _) = setup_exhausted_group(&test_env);
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+    client.sponsor_group(&pool_id, &sponsor, &id);
+    client.unsponsor_group(&pool_id, &sponsor, &id);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &token, &sender, 100);
+    client.distribute(&id, &token, &100, &sender, &None, &None);
+}
+
+#[test]
+fn test_sponsor_withdraws_unspent_funds() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let token = test_env.mock_tokens.get(0).unwrap();
+
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+    client.withdraw_sponsor_funds(&pool_id, &sponsor, &400);
+
+    assert_balance(env, &token, &sponsor, 400);
+    assert_eq!(client.get_sponsor_pool(&pool_id).balance, 600);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_non_sponsor_cannot_manage_pool() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let (id, _) = setup_exhausted_group(&test_env);
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+
+    let intruder = Address::generate(env);
+    client.sponsor_group(&pool_id, &intruder, &id);
+}
+
+#[test]
+#[should_panic] // InsufficientBalance
+fn test_withdraw_more_than_pool_balance_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+
+    let sponsor = Address::generate(env);
+    let pool_id = setup_pool(&test_env, &sponsor, 5);
+    client.withdraw_sponsor_funds(&pool_id, &sponsor, &1_001);
+}
@@ -0,0 +1,157 @@
+use crate::base::types::SwapReceipt;
+use crate::mock_router::MockRouterClient;
+use crate::test_utils::{
+    assert_balance, create_group_with_members, deploy_mock_router, deploy_mock_token, mint_tokens,
+    setup_test_env, TestEnv,
+};
+use crate::AutoShareContractClient;
+use soroban_sdk::{testutils::Address as _, Address, BytesN, String};
+
+struct SwapSetup {
+    id: BytesN<32>,
+    router: Address,
+    xlm: Address,
+    usdc: Address,
+    member1: Address,
+    member2: Address,
+    sender: Address,
+}
+
+/// A 60/40 group paid in USDC, a sender holding XLM, and a router quoting
+/// 1 XLM = 0.5 USDC with enough USDC to fill swaps.
+fn setup(test_env: &TestEnv) -> SwapSetup {
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let usdc = test_env.mock_tokens.get(0).unwrap();
+
+    let xlm = deploy_mock_token(
+        env,
+        &String::from_str(env, "Lumens"),
+        &String::from_str(env, "XLM"),
+    );
+    let router = deploy_mock_router(env);
+    MockRouterClient::new(env, &router).set_rate(&xlm, &usdc, &1, &2);
+    mint_tokens(env, &usdc, &router, 10_000);
+    client.set_swap_router(&router, &test_env.admin);
+
+    let group = create_group_with_members(test_env, &[60, 40], 2);
+
+    let sender = test_env.users.get(1).unwrap();
+    mint_tokens(env, &xlm, &sender, 1_000);
+
+    SwapSetup {
+        id: group.id,
+        router,
+        xlm,
+        usdc,
+        member1: group.members.get(0).unwrap(),
+        member2: group.members.get(1).unwrap(),
+        sender,
+    }
+}
+
+#[test]
+fn test_distribute_with_swap_splits_output_token() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+    assert_eq!(client.get_swap_router(), Some(s.router.clone()));
+
+    let received = client.distribute_with_swap(&s.id, &s.xlm, &1_000, &s.usdc, &490, &s.sender);
+    assert_eq!(received, 500);
+
+    assert_balance(env, &s.xlm, &s.sender, 0);
+    assert_balance(env, &s.xlm, &s.router, 1_000);
+    assert_balance(env, &s.usdc, &s.member1, 300);
+    assert_balance(env, &s.usdc, &s.member2, 200);
+    assert_eq!(client.get_remaining_usages(&s.id), 1);
+
+    // History records the realized swap next to the distributed amount
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(entry.token, s.usdc);
+    assert_eq!(entry.total_amount, 500);
+    assert_eq!(entry.swaps.len(), 1);
+    assert_eq!(
+        entry.swaps.get(0).unwrap(),
+        SwapReceipt {
+            router: s.router.clone(),
+            token_in: s.xlm.clone(),
+            amount_in: 1_000,
+            token_out: s.usdc.clone(),
+            amount_out: 500,
+        }
+    );
+}
+
+#[test]
+fn test_plain_distribution_records_no_swap() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    mint_tokens(env, &s.usdc, &s.sender, 100);
+    client.distribute(&s.id, &s.usdc, &100, &s.sender, &None, &None);
+    let entry = client.get_group_distributions(&s.id).get(0).unwrap();
+    assert_eq!(entry.swaps.len(), 0);
+}
+
+#[test]
+fn test_slippage_failure_reverts_everything() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    let result = client.try_distribute_with_swap(&s.id, &s.xlm, &1_000, &s.usdc, &501, &s.sender);
+    assert!(result.is_err());
+    assert_balance(env, &s.xlm, &s.sender, 1_000);
+    assert_balance(env, &s.usdc, &s.member1, 0);
+    assert_eq!(client.get_remaining_usages(&s.id), 2);
+}
+
+#[test]
+#[should_panic] // SwapFailed
+fn test_output_below_minimum_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    client.distribute_with_swap(&s.id, &s.xlm, &1_000, &s.usdc, &501, &s.sender);
+}
+
+#[test]
+#[should_panic] // SwapRouterNotSet
+fn test_swap_without_router_fails() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let group = create_group_with_members(&test_env, &[100], 1);
+    let (id, usdc) = (group.id, group.token);
+
+    let sender = test_env.users.get(1).unwrap();
+    client.distribute_with_swap(&id, &Address::generate(env), &100, &usdc, &1, &sender);
+}
+
+#[test]
+#[should_panic] // UnsupportedToken
+fn test_swap_into_unsupported_token_fails() {
+    let test_env = setup_test_env();
+    let client = AutoShareContractClient::new(&test_env.env, &test_env.autoshare_contract);
+    let s = setup(&test_env);
+
+    // The input token does not need to be supported, but the output does
+    client.distribute_with_swap(&s.id, &s.usdc, &100, &s.xlm, &1, &s.sender);
+}
+
+#[test]
+#[should_panic] // Unauthorized
+fn test_only_admin_sets_router() {
+    let test_env = setup_test_env();
+    let env = &test_env.env;
+    let client = AutoShareContractClient::new(env, &test_env.autoshare_contract);
+    let other = test_env.users.get(1).unwrap();
+
+    client.set_swap_router(&Address::generate(env), &other);
+}
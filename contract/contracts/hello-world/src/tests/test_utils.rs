@@ -1,3 +1,6 @@
+use crate::base::types::GroupMember;
+use crate::mock_oracle::MockOracle;
+use crate::mock_router::MockRouter;
 use crate::mock_token::{MockToken, MockTokenClient};
 use crate::{AutoShareContract, AutoShareContractClient};
 use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
@@ -63,30 +66,22 @@ pub fn deploy_mock_token(env: &Env, name: &String, symbol: &String) -> Address {
     contract_id
 }
 
+pub fn deploy_mock_router(env: &Env) -> Address {
+    env.register(MockRouter, ())
+}
+
+pub fn deploy_mock_oracle(env: &Env) -> Address {
+    env.register(MockOracle, ())
+}
+
 pub fn mint_tokens(env: &Env, token: &Address, to: &Address, amount: i128) {
     let client = MockTokenClient::new(env, token);
     client.mint(to, &amount);
 }
 
-// MockToken doesn't support approve, but we implement the signature as requested.
-// We'll log or do nothing if not supported, but here it's a test util, so we can't really "fake" it if the contract doesn't support it.
-// Assuming for now we might add standard token interface later or this is for a different token.
-pub fn approve_tokens(
-    _env: &Env,
-    _token: &Address,
-    _from: &Address,
-    _spender: &Address,
-    _amount: i128,
-) {
-    // Note: MockToken provided in this repo does not support approve/allowance.
-    // usage of this function with current MockToken will fail if we try to call non-existent method.
-    // For now we will look for 'approve' method on client, if not generic refactor might be needed.
-    // But since MockTokenClient is generated from MockToken struct, and MockToken struct doesn't have approve,
-    // we cannot call client.approve.
-    // We will comment this out or leave it empty to allow compilation if the user really wants the function to exist.
-    // However, the best approach is to implement it assuming standard token interface if possible,
-    // but without trait definition imported, we can't easily cast.
-    // We will leave it as a placeholder.
+pub fn approve_tokens(env: &Env, token: &Address, from: &Address, spender: &Address, amount: i128) {
+    let client = MockTokenClient::new(env, token);
+    client.approve(from, spender, &amount, &(env.ledger().sequence() + 1000));
 }
 
 pub fn deploy_autoshare_contract(env: &Env, _admin: &Address) -> Address {
@@ -118,6 +113,8 @@ pub fn create_test_members(env: &Env, count: u32) -> Vec<crate::base::types::Gro
         members.push_back(crate::base::types::GroupMember {
             address: Address::generate(env),
             percentage,
+            preferred_token: None,
+            payout_address: None,
         });
     }
     members
@@ -143,15 +140,78 @@ pub fn create_test_group(
     let id = BytesN::from_array(env, &id_bytes);
     let name = String::from_str(env, "Test Group");
 
-    client.create(&id, &name, creator, &usages, token);
+    client.create(&id, &name, creator, &usages, token, &None, &None);
 
     if !members.is_empty() {
         client.update_members(&id, creator, members);
+        accept_invitations(env, contract, &id);
     }
 
     id
 }
 
+/// A group member with no payout preferences.
+pub fn member(address: &Address, percentage: u32) -> GroupMember {
+    GroupMember {
+        address: address.clone(),
+        percentage,
+        preferred_token: None,
+        payout_address: None,
+    }
+}
+
+/// A group whose members have all accepted their invitations.
+pub struct TestGroup {
+    pub id: BytesN<32>,
+    pub creator: Address,
+    pub token: Address,
+    pub members: Vec<Address>,
+}
+
+/// Creates a group owned by the first test user and paid in the first mock
+/// token, with one new member per entry in `percentages`. The group id comes
+/// from `usages`, so groups in one test need different usage counts.
+pub fn create_group_with_members(
+    test_env: &TestEnv,
+    percentages: &[u32],
+    usages: u32,
+) -> TestGroup {
+    let env = &test_env.env;
+    let token = test_env.mock_tokens.get(0).unwrap();
+    let creator = test_env.users.get(0).unwrap();
+
+    let mut members = Vec::new(env);
+    let mut split = Vec::new(env);
+    for percentage in percentages {
+        let address = Address::generate(env);
+        split.push_back(member(&address, *percentage));
+        members.push_back(address);
+    }
+    let id = create_test_group(
+        env,
+        &test_env.autoshare_contract,
+        &creator,
+        &split,
+        usages,
+        &token,
+    );
+
+    TestGroup {
+        id,
+        creator,
+        token,
+        members,
+    }
+}
+
+/// Accepts every pending invitation of a group on behalf of the invitees.
+pub fn accept_invitations(env: &Env, contract: &Address, id: &BytesN<32>) {
+    let client = AutoShareContractClient::new(env, contract);
+    for invitation in client.get_group_invitations(id).iter() {
+        client.accept_invitation(id, &invitation.invitee);
+    }
+}
+
 pub fn fund_user_with_tokens(env: &Env, token: &Address, user: &Address, amount: i128) {
     mint_tokens(env, token, user, amount);
 }
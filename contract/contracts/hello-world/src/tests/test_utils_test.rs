@@ -59,6 +59,8 @@ fn test_autoshare_helpers() {
     members.push_back(crate::base::types::GroupMember {
         address: Address::generate(&env),
         percentage: 100,
+        preferred_token: None,
+        payout_address: None,
     });
 
     let group_id = create_test_group(&env, &contract, &creator, &members, 1, &token);